@@ -1,22 +1,31 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::event::{KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     layout::Rect,
     text::{Line, Span},
     Frame,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::time::{Duration, Instant};
 
 use crate::async_loader::AsyncLoader;
 use crate::config::Config;
+use crate::drafts::{DraftComment, DraftStore};
 use crate::event::KeyInput;
-use crate::git::{DiffStats, GitClient, TimelinePosition};
-use crate::github::{GitHubClient, PrInfo};
+use crate::export::{self, Feedback, FeedbackComment, FeedbackItem};
+use crate::git::{DiffStats, GitClient, LineMap, TimelinePosition};
+use crate::last_review::LastReviewStore;
+use crate::return_point::ReturnPoint;
+use crate::forge::{self, Forge, MetadataKind, PrInfo, PrSummary};
+use crate::seen::{self, Activity, SeenStore};
+use crate::viewed::{self, ViewedStore};
+use crate::pr_template;
 use crate::ui::{
-    centered_rect, Action, AppLayout, DiffView, DiffViewState, FileList, FileListState, HelpModal,
-    Highlighter, InputModal, InputModalState, InputResult, LayoutAreas, PrDetailsView,
-    PrDetailsViewState, PrListPanel, PrListPanelState, PreviewContent, ReviewAction,
+    centered_rect, format_age, ActivityLog, ActivityLogState, resolve_path, Action, AppLayout, CheckLog, CreatePrForm, CreatePrState, DiffView,
+    DiffViewState, FileList, FileListState, HelpModal, Highlighter, InputModal, InputModalState, InputResult, LayoutAreas, Picker, PickerState,
+    PrDetailsView, PrDetailsViewState, PrListPanel, PrListPanelState, PreviewContent, RangeDiffModal, RangeDiffState,
+    ReviewAction, RevisionChoice,
 };
 
 /// Which window is focused
@@ -88,7 +97,8 @@ pub struct App {
     pub running: bool,
     pub config: Config,
     git: GitClient,
-    github: GitHubClient,
+    forge: Box<dyn Forge>,
+    drafts: DraftStore,
 
     // State
     pub focused: FocusedWindow,
@@ -99,14 +109,32 @@ pub struct App {
 
     // Data
     pub branch: String,
+    /// PR whose head is shown in place of HEAD (nothing checked out)
+    pub reviewing_pr: Option<u64>,
+    /// Branch (and stash) to go back to after checking out a PR
+    return_point: Option<ReturnPoint>,
     pub diff_stats: DiffStats,
     pub selected_pr: Option<PrInfo>,
 
     // Notifications
     pub toast: Option<Toast>,
-    pub gh_available: bool,
+    /// PR activity the user has looked at
+    seen: SeenStore,
+    /// Activity at the previous PR list poll (None before the first)
+    polled_activity: Option<HashMap<u64, Activity>>,
+    /// Files marked viewed, per branch or PR
+    viewed: ViewedStore,
+    /// PR head at the user's last review of each PR
+    last_review: LastReviewStore,
+    pub forge_available: bool,
     pub tick_count: usize,
 
+    // Follow mode
+    /// Jump to files as they change on disk (e.g. while an agent edits)
+    pub follow: bool,
+    /// Diff of each changed file when it was last followed
+    followed_diffs: HashMap<String, String>,
+
     // Async loading
     async_loader: AsyncLoader,
     last_pr_list_poll: Instant,
@@ -117,6 +145,10 @@ pub struct App {
     pub diff_view_state: DiffViewState,
     pub pr_details_view_state: PrDetailsViewState,
     pub input_modal_state: InputModalState,
+    pub create_pr_state: CreatePrState,
+    pub picker_state: PickerState,
+    pub range_diff_state: RangeDiffState,
+    pub activity_log_state: ActivityLogState,
 
     // Syntax highlighting
     highlighter: Highlighter,
@@ -129,47 +161,72 @@ pub struct App {
 impl App {
     pub fn new(path: &str) -> Result<Self> {
         let git = GitClient::open(path)?;
-        let mut github = GitHubClient::new();
+        let repo_path = git.path().to_path_buf();
+        let mut forge = forge::detect(&repo_path);
 
-        // Check gh CLI availability upfront
-        let gh_available = github.is_available();
+        // Check forge access upfront
+        let forge_available = forge.is_available();
+        let setup_hint = forge.setup_hint();
 
         let branch = git.current_branch().unwrap_or_else(|_| "HEAD".to_string());
 
         let config = Config::default();
         let pr_poll_interval = config.timing.pr_poll_interval;
         let highlighter = Highlighter::for_theme(config.theme);
+        let drafts = DraftStore::new(git.git_dir());
+        let return_point = ReturnPoint::load(git.git_dir());
+        let seen = SeenStore::load(git.git_dir());
+        let viewed = ViewedStore::load(git.git_dir());
+        let last_review = LastReviewStore::load(git.git_dir());
         let mut app = Self {
             running: true,
             git,
-            github,
+            forge,
+            drafts,
             focused: FocusedWindow::FileList,
             show_help: false,
             pending_command: AppCommand::None,
             timeline_position: TimelinePosition::default(),
             commit_count: 0,
             branch,
+            reviewing_pr: None,
+            return_point,
             diff_stats: DiffStats::default(),
             selected_pr: None,
             toast: None,
-            gh_available,
+            seen,
+            polled_activity: None,
+            viewed,
+            last_review,
+            forge_available,
             tick_count: 0,
-            async_loader: AsyncLoader::new(),
+            follow: false,
+            followed_diffs: HashMap::new(),
+            async_loader: AsyncLoader::new(&repo_path),
             last_pr_list_poll: Instant::now() - pr_poll_interval - Duration::from_secs(1), // Force immediate load
             file_list_state: FileListState::new(),
             pr_list_panel_state: PrListPanelState::new(),
             diff_view_state: DiffViewState::new(),
             pr_details_view_state: PrDetailsViewState::new(),
             input_modal_state: InputModalState::new(),
+            create_pr_state: CreatePrState::new(),
+            picker_state: PickerState::new(),
+            range_diff_state: RangeDiffState::new(),
+            activity_log_state: ActivityLogState::new(),
             highlighter,
             config,
             layout_areas: None,
         };
 
         // Initialize PR list panel
-        app.pr_list_panel_state.set_gh_available(gh_available);
+        app.pr_list_panel_state.set_forge_available(forge_available, setup_hint);
+        app.pr_list_panel_state.push_repo = forge::push_remote(app.git.path()).map(|r| r.path).unwrap_or_default();
         app.pr_list_panel_state.set_current_branch(app.branch.clone());
 
+        // Baseline for the first edit's checkpoint
+        if let Err(e) = app.git.take_checkpoint(app.config.checkpoint_limit) {
+            log::warn!("Failed to take checkpoint: {}", e);
+        }
         app.refresh()?;
         Ok(app)
     }
@@ -189,12 +246,21 @@ impl App {
         // Cache merge base OID once per refresh so downstream calls reuse it
         self.git.refresh_merge_base_cache();
 
+        // HEAD may have moved on from the checkpoints being looked at
+        self.git.refresh_checkpoints();
+        if matches!(self.timeline_position, TimelinePosition::Checkpoint(n) if n > self.git.checkpoint_count()) {
+            self.timeline_position = TimelinePosition::Wip;
+        }
+
         // Update commit count for timeline
         self.commit_count = self.git.commit_count_since_base().unwrap_or(0);
 
+        self.sync_last_review();
+
         // Load files based on timeline position
         let files = self.git.status_at_position(self.timeline_position)?;
         self.file_list_state.set_files(files);
+        self.refresh_viewed();
 
         // Auto-select first file if cursor is at root and there are files
         // (Skip root "./" entry at index 0, select first actual file at index 1+)
@@ -230,6 +296,119 @@ impl App {
         Ok(())
     }
 
+    /// Checkpoint the working tree and refresh after files changed on disk
+    /// (`paths` relative to the repo root); when following, jump to the
+    /// newest change
+    pub fn handle_file_change(&mut self, paths: Vec<String>) -> Result<()> {
+        // Before the checkpoint, which becomes the next change's baseline
+        self.record_activity(&paths);
+        match self.git.take_checkpoint(self.config.checkpoint_limit) {
+            // Keep showing the same checkpoint now that it's one further back
+            Ok(true) => {
+                if let TimelinePosition::Checkpoint(n) = self.timeline_position {
+                    self.timeline_position = TimelinePosition::Checkpoint(n + 1);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => log::warn!("Failed to take checkpoint: {}", e),
+        }
+        self.refresh()?;
+        if self.follow {
+            self.follow_change(paths)?;
+        }
+        Ok(())
+    }
+
+    /// Show the most recently modified of `paths`, scrolled to its newest hunk.
+    /// Waits while a modal is open or the PR list has focus.
+    fn follow_change(&mut self, mut paths: Vec<String>) -> Result<()> {
+        let busy = self.input_modal_state.visible
+            || self.create_pr_state.visible
+            || self.picker_state.visible
+            || self.range_diff_state.visible
+            || self.activity_log_state.visible
+            || self.show_help
+            || self.focused == FocusedWindow::PrList;
+        if self.reviewing_pr.is_some() || busy {
+            return Ok(());
+        }
+
+        // Edits land in the working tree, shown by the full diff and wip
+        if !matches!(self.timeline_position, TimelinePosition::FullDiff | TimelinePosition::Wip) {
+            self.switch_timeline(TimelinePosition::FullDiff)?;
+        }
+
+        let listed: HashSet<&str> = self.file_list_state.files.iter().map(|f| f.path.as_str()).collect();
+        paths.retain(|p| listed.contains(p.as_str()));
+        paths.sort();
+        paths.dedup();
+        let newest = paths
+            .iter()
+            .filter_map(|path| {
+                let modified = std::fs::metadata(self.git.path().join(path)).and_then(|m| m.modified()).ok()?;
+                Some((modified, path))
+            })
+            .max()
+            .map(|(_, path)| path.clone());
+        let Some(path) = newest else {
+            return Ok(());
+        };
+
+        self.pr_details_view_state.clear();
+        self.file_list_state.reveal(&path);
+        self.update_preview();
+
+        // Remember every changed file's diff to tell its next new hunk
+        let mut previous = None;
+        for changed in &paths {
+            let diff = self.git.diff_at_position(changed, self.timeline_position).unwrap_or_default();
+            let before = self.followed_diffs.insert(changed.clone(), truncate_large_content(diff));
+            if *changed == path {
+                previous = before;
+            }
+        }
+        self.diff_view_state.go_to_newest_hunk(previous.as_deref());
+        Ok(())
+    }
+
+    /// Add the changed files to the activity log
+    fn record_activity(&mut self, paths: &[String]) {
+        let mut seen = HashSet::new();
+        for path in paths {
+            if !seen.insert(path) {
+                continue;
+            }
+            if let Some((added, removed)) = self.git.change_stats(path) {
+                self.activity_log_state.record(path.clone(), added, removed);
+            }
+        }
+    }
+
+    /// Show the diff of a file from the activity log
+    fn open_activity_entry(&mut self, path: &str) -> Result<()> {
+        // Working-tree changes are in the full diff
+        let listed = |app: &Self| app.file_list_state.files.iter().any(|f| f.path == path);
+        if !listed(self) && self.timeline_position != TimelinePosition::FullDiff {
+            self.switch_timeline(TimelinePosition::FullDiff)?;
+        }
+        if !listed(self) {
+            self.toast = Some(Toast::error(format!("{} has no changes in the diff", path)));
+            return Ok(());
+        }
+
+        self.pr_details_view_state.clear();
+        self.file_list_state.reveal(path);
+        self.focused = FocusedWindow::Preview;
+        self.update_preview();
+        Ok(())
+    }
+
+    fn toggle_follow(&mut self) {
+        self.follow = !self.follow;
+        self.followed_diffs.clear();
+        self.toast = Some(Toast::success(if self.follow { "Following changes" } else { "Follow paused" }));
+    }
+
     /// Switch to a new timeline position, preserving view state
     fn switch_timeline(&mut self, new_position: TimelinePosition) -> Result<()> {
         if new_position == self.timeline_position {
@@ -249,6 +428,7 @@ impl App {
 
         let files = self.git.status_at_position(self.timeline_position)?;
         self.file_list_state.set_files(files);
+        self.refresh_viewed();
 
         if entering_browse {
             self.file_list_state.initialize_browse_mode();
@@ -260,6 +440,147 @@ impl App {
         Ok(())
     }
 
+    /// Viewed files are kept per PR reviewed in place, else per branch
+    fn viewed_scope(&self) -> String {
+        match self.reviewing_pr {
+            Some(pr_number) => format!("pr-{}", pr_number),
+            None => self.branch.clone(),
+        }
+    }
+
+    /// PR whose changes are shown: the one reviewed in place, else the
+    /// current branch's
+    fn shown_pr(&self) -> Option<u64> {
+        self.reviewing_pr
+            .or_else(|| self.pr_list_panel_state.current_branch_pr().map(|pr| pr.number))
+    }
+
+    /// PR whose viewed files mirror the forge's
+    fn viewed_sync_pr(&self) -> Option<u64> {
+        if !self.config.sync_viewed || !self.forge_available {
+            return None;
+        }
+        self.shown_pr()
+    }
+
+    /// Point the since-review position at the shown PR's last reviewed
+    /// commit, leaving that position if there's none
+    fn sync_last_review(&mut self) {
+        let pr_number = self.shown_pr();
+        let sha = pr_number.and_then(|n| self.last_review.get(n)).map(str::to_string);
+        let available = self.git.set_reviewed_commit(pr_number.unwrap_or_default(), sha.as_deref());
+        if !available && self.timeline_position == TimelinePosition::SinceReview {
+            if let Err(e) = self.switch_timeline(TimelinePosition::FullDiff) {
+                log::warn!("{:#}", e);
+            }
+        }
+    }
+
+    /// Remember the commit a PR was reviewed at: the head shown for it, not
+    /// the one last polled, which may be stale. Nothing is recorded for a PR
+    /// that isn't the one shown.
+    fn record_review(&mut self, pr_number: u64) {
+        if self.shown_pr() != Some(pr_number) {
+            return;
+        }
+        let Some(head) = self.git.head_oid() else {
+            return;
+        };
+        if let Err(e) = self.last_review.record(pr_number, &head.to_string()) {
+            log::warn!("{:#}", e);
+        }
+        self.sync_last_review();
+    }
+
+    /// Whether a timeline position can be shown right now
+    fn timeline_available(&self, position: TimelinePosition) -> bool {
+        match position {
+            // A PR reviewed in place has no working tree
+            TimelinePosition::Wip => self.reviewing_pr.is_none(),
+            TimelinePosition::SinceReview => self.git.has_reviewed_commit(),
+            TimelinePosition::Checkpoint(n) => n <= self.git.checkpoint_count(),
+            _ => true,
+        }
+    }
+
+    /// Hash of a file's full diff, the version a viewed mark applies to
+    fn full_diff_hash(&self, path: &str) -> Option<String> {
+        self.git
+            .diff_at_position(path, TimelinePosition::FullDiff)
+            .ok()
+            .map(|diff| viewed::diff_hash(&diff))
+    }
+
+    /// Recompute which listed files are still viewed; a file whose diff
+    /// changed since it was marked is no longer
+    fn refresh_viewed(&mut self) {
+        let mut paths = HashSet::new();
+        if self.timeline_position == TimelinePosition::FullDiff {
+            let scope = self.viewed_scope();
+            for file in &self.file_list_state.files {
+                if !self.viewed.has_entry(&scope, &file.path) {
+                    continue;
+                }
+                if let Some(hash) = self.full_diff_hash(&file.path) {
+                    if self.viewed.is_viewed(&scope, &file.path, &hash) {
+                        paths.insert(file.path.clone());
+                    }
+                }
+            }
+        }
+        self.file_list_state.set_viewed(paths);
+    }
+
+    /// Mark a file viewed at its current diff, or unviewed if it was
+    fn toggle_viewed(&mut self, path: &str) {
+        if self.timeline_position != TimelinePosition::FullDiff {
+            self.toast = Some(Toast::error("Viewed files are tracked in the full diff"));
+            return;
+        }
+        let now_viewed = !self.file_list_state.is_viewed(path);
+        let hash = if now_viewed { self.full_diff_hash(path) } else { None };
+        if now_viewed && hash.is_none() {
+            self.toast = Some(Toast::error(format!("No diff for {}", path)));
+            return;
+        }
+        let scope = self.viewed_scope();
+        if let Err(e) = self.viewed.set(&scope, path, hash) {
+            self.toast = Some(Toast::error(format!("{:#}", e)));
+            return;
+        }
+        self.refresh_viewed();
+
+        if let Some(pr_number) = self.viewed_sync_pr() {
+            if let Err(e) = self.forge.set_file_viewed(pr_number, path, now_viewed) {
+                self.toast = Some(Toast::error(format!("{:#}", e)));
+            }
+        }
+    }
+
+    /// Adopt files viewed on the forge that have no local mark yet, at
+    /// their current diff
+    fn merge_forge_viewed(&mut self, pr_number: u64, paths: Vec<String>) {
+        if self.viewed_sync_pr() != Some(pr_number) {
+            return;
+        }
+        let scope = self.viewed_scope();
+        let mut changed = false;
+        for path in paths {
+            if self.viewed.has_entry(&scope, &path) {
+                continue;
+            }
+            if let Some(hash) = self.full_diff_hash(&path) {
+                if let Err(e) = self.viewed.set(&scope, &path, Some(hash)) {
+                    log::warn!("{:#}", e);
+                }
+                changed = true;
+            }
+        }
+        if changed {
+            self.refresh_viewed();
+        }
+    }
+
     /// Load PR details for a specific PR number
     fn load_pr_details(&mut self, pr_number: u64) {
         let already_loaded = self.selected_pr.as_ref().map(|p| p.number) == Some(pr_number);
@@ -268,20 +589,43 @@ impl App {
         if !already_loaded && !already_loading {
             self.async_loader.load_pr_details(pr_number);
         }
+        self.sync_pending_comments();
+    }
+
+    /// Show the selected PR's pending review comments in the diff and details views
+    fn sync_pending_comments(&mut self) {
+        let pending = match self.pr_list_panel_state.selected_number() {
+            Some(pr_number) => self.drafts.comments(pr_number),
+            None => Vec::new(),
+        };
+        self.diff_view_state.set_pending_comments(pending.clone());
+        self.pr_details_view_state.set_pending_comments(pending);
     }
 
     /// Apply full PR details to state
     fn apply_pr_details(&mut self, pr: PrInfo) {
         // Update file comments indicator
         let comments: HashMap<String, bool> = pr
-            .file_comments
+            .file_threads
             .keys()
             .map(|k| (k.clone(), true))
             .collect();
         self.file_list_state.set_comments(comments);
 
         // Update diff view with PR for inline comments
-        self.diff_view_state.set_pr(Some(pr.clone()));
+        if let Some(path) = self.diff_view_state.get_current_file().map(|p| p.to_string()) {
+            let anchors = self.thread_anchors(&pr, &path);
+            self.diff_view_state.set_thread_anchors(anchors);
+        }
+        self.diff_view_state.set_pr_highlighted(Some(pr.clone()), &self.highlighter);
+
+        // Reviews given on the forge move the since-review position too
+        if let Some(sha) = pr.viewer_review_commit() {
+            if let Err(e) = self.last_review.record(pr.number, sha) {
+                log::warn!("{:#}", e);
+            }
+            self.sync_last_review();
+        }
 
         self.selected_pr = Some(pr);
 
@@ -306,8 +650,15 @@ impl App {
 
         // Poll for completed PR list loading
         if let Some(prs) = self.async_loader.poll_pr_list() {
+            self.note_pr_activity(&prs);
             self.pr_list_panel_state.set_prs(prs);
             self.last_pr_list_poll = Instant::now();
+            // The current branch's PR may have just shown up
+            self.sync_last_review();
+
+            if let Some(pr_number) = self.viewed_sync_pr() {
+                self.async_loader.load_viewed_files(pr_number);
+            }
 
             // Auto-load details for selected PR
             if let Some(pr_num) = self.pr_list_panel_state.selected_number() {
@@ -330,12 +681,33 @@ impl App {
             }
         }
 
+        // Show a fetched job log if it's still the one waited for
+        if let Some((name, result)) = self.async_loader.poll_check_log() {
+            if self.pr_details_view_state.log_name() == Some(name.as_str()) {
+                match result {
+                    Ok(lines) => self.pr_details_view_state.show_log(CheckLog::new(name, lines)),
+                    Err(e) => {
+                        self.pr_details_view_state.close_log();
+                        self.toast = Some(Toast::error(format!("{:#}", e)));
+                    }
+                }
+            }
+        }
+
+        // Take in files viewed on the forge
+        if let Some((pr_number, result)) = self.async_loader.poll_viewed_files() {
+            match result {
+                Ok(paths) => self.merge_forge_viewed(pr_number, paths),
+                Err(e) => log::warn!("{:#}", e),
+            }
+        }
+
         // Update loading state in PR panel
         self.pr_list_panel_state.loading = self.async_loader.is_pr_list_loading();
 
         // Trigger PR list loading if needed (on startup and periodically)
-        // Skip if gh CLI is not available
-        if self.gh_available {
+        // Skip if the forge is not available
+        if self.forge_available {
             let should_load_pr_list = self.last_pr_list_poll.elapsed() >= pr_poll_interval;
             if should_load_pr_list && !self.async_loader.is_pr_list_loading() {
                 self.async_loader.load_pr_list();
@@ -357,6 +729,37 @@ impl App {
             return Ok(());
         }
 
+        if self.create_pr_state.visible {
+            if self.create_pr_state.handle_key(key) == InputResult::Submit {
+                self.submit_create_pr();
+            }
+            return Ok(());
+        }
+
+        if self.picker_state.visible {
+            if self.picker_state.handle_key(key) == InputResult::Submit {
+                self.submit_metadata();
+            }
+            return Ok(());
+        }
+
+        if self.range_diff_state.visible {
+            if self.range_diff_state.handle_key(key) == InputResult::Submit {
+                self.show_range_diff();
+            }
+            return Ok(());
+        }
+
+        if self.activity_log_state.visible {
+            if self.activity_log_state.handle_key(key) == InputResult::Submit {
+                if let Some(path) = self.activity_log_state.selected_path() {
+                    self.activity_log_state.hide();
+                    self.open_activity_entry(&path)?;
+                }
+            }
+            return Ok(());
+        }
+
         // Help modal takes priority
         if self.show_help {
             if KeyInput::is_help(&key) || KeyInput::is_escape(&key) {
@@ -365,6 +768,12 @@ impl App {
             return Ok(());
         }
 
+        // PR search captures typing
+        if self.focused == FocusedWindow::PrList && self.pr_list_panel_state.is_searching() {
+            let action = self.pr_list_panel_state.handle_key(&key);
+            return self.dispatch(action);
+        }
+
         // Global keys
         if KeyInput::is_quit(&key) {
             self.running = false;
@@ -382,12 +791,30 @@ impl App {
             return Ok(());
         }
 
+        if KeyInput::is_return_to_branch(&key) {
+            self.return_to_previous_branch()?;
+            return Ok(());
+        }
+
         // Toggle diff view mode (global - works from any pane)
         if KeyInput::is_toggle_view_mode(&key) {
             self.diff_view_state.toggle_view_mode();
             return Ok(());
         }
 
+        // Toggle hiding resolved review threads (global - affects diff and PR details)
+        if KeyInput::is_unresolved_filter(&key) {
+            let unresolved_only = !self.diff_view_state.unresolved_only;
+            self.diff_view_state.set_unresolved_only(unresolved_only);
+            self.pr_details_view_state.set_unresolved_only(unresolved_only);
+            self.toast = Some(Toast::success(if unresolved_only {
+                "Showing unresolved threads only"
+            } else {
+                "Showing all threads"
+            }));
+            return Ok(());
+        }
+
         // Tab cycles through all panes
         if KeyInput::is_tab(&key) {
             self.focused = self.focused.next();
@@ -415,12 +842,21 @@ impl App {
                         self.checkout_pr(pr.number)?;
                     }
                 }
-                FocusedWindow::Preview => {}
+                FocusedWindow::Preview => {
+                    // Checks and job log lines act on Enter
+                    if self.pr_details_view_state.pr.is_some() {
+                        let action = self.pr_details_view_state.handle_key(&key);
+                        return self.dispatch(action);
+                    }
+                }
             }
             return Ok(());
         }
 
-        // Escape goes back to left pane
+        // Escape closes a job log, then goes back to left pane
+        if KeyInput::is_escape(&key) && self.pr_details_view_state.close_log() {
+            return Ok(());
+        }
         if KeyInput::is_escape(&key) && self.focused == FocusedWindow::Preview {
             // Go back to PrList if viewing PR details, otherwise FileList
             if self.pr_details_view_state.pr.is_some() || self.pr_details_view_state.loading_message.is_some() {
@@ -432,21 +868,55 @@ impl App {
             return Ok(());
         }
 
+        if KeyInput::is_range_diff(&key) {
+            self.open_range_diff();
+            return Ok(());
+        }
+
+        if KeyInput::is_activity_log(&key) {
+            self.activity_log_state.show();
+            return Ok(());
+        }
+
+        if KeyInput::is_export_review(&key) {
+            self.export_review();
+            return Ok(());
+        }
+
         if KeyInput::is_yank(&key) {
             self.yank_path();
             return Ok(());
         }
 
+        if KeyInput::is_follow(&key) {
+            self.toggle_follow();
+            return Ok(());
+        }
+
         // Timeline navigation: , goes left (older), . goes right (newer)
         if KeyInput::is_timeline_next(&key) {
             // , key - go older (left on timeline)
-            let new_pos = self.timeline_position.prev(self.commit_count);
+            let checkpoints = self.git.checkpoint_count();
+            let mut new_pos = self.timeline_position.prev(self.commit_count, checkpoints);
+            while !self.timeline_available(new_pos) {
+                let older = new_pos.prev(self.commit_count, checkpoints);
+                if older == new_pos {
+                    new_pos = self.timeline_position;
+                    break;
+                }
+                new_pos = older;
+            }
             self.switch_timeline(new_pos)?;
             return Ok(());
         }
         if KeyInput::is_timeline_prev(&key) {
             // . key - go newer (right on timeline)
-            let new_pos = self.timeline_position.next();
+            let checkpoints = self.git.checkpoint_count();
+            let mut new_pos = self.timeline_position.next(checkpoints);
+            // Browse, the newest position, is always available
+            while !self.timeline_available(new_pos) {
+                new_pos = new_pos.next(checkpoints);
+            }
             self.switch_timeline(new_pos)?;
             return Ok(());
         }
@@ -457,7 +927,7 @@ impl App {
                 FocusedWindow::PrList => {
                     // Open selected PR in browser
                     if let Some(pr) = self.pr_list_panel_state.selected() {
-                        let _ = self.github.open_pr_in_browser(pr.number);
+                        let _ = self.forge.open_pr_in_browser(pr.number);
                     }
                 }
                 _ => {
@@ -495,7 +965,13 @@ impl App {
 
     /// Handle mouse input
     pub fn handle_mouse(&mut self, mouse: MouseEvent) -> Result<()> {
-        if self.input_modal_state.visible || self.show_help {
+        if self.input_modal_state.visible
+            || self.create_pr_state.visible
+            || self.picker_state.visible
+            || self.range_diff_state.visible
+            || self.activity_log_state.visible
+            || self.show_help
+        {
             return Ok(());
         }
         let Some(areas) = self.layout_areas.clone() else { return Ok(()) };
@@ -541,7 +1017,10 @@ impl App {
                 }
                 FocusedWindow::Preview => {
                     if self.pr_details_view_state.pr.is_some() {
-                        self.pr_details_view_state.scroll.click_at(row as usize);
+                        let action = self.pr_details_view_state.click_at(row as usize);
+                        if let Err(e) = self.dispatch(action) {
+                            self.toast = Some(Toast::error(format!("{:#}", e)));
+                        }
                     } else {
                         self.diff_view_state.scroll.click_at(row as usize);
                     }
@@ -584,14 +1063,29 @@ impl App {
             }
 
             Action::PrSelected(pr_number) => {
+                self.mark_pr_seen(pr_number);
                 self.load_pr_details(pr_number);
                 self.show_selected_pr_in_preview();
             }
 
+            Action::ToggleViewed(path) => {
+                self.toggle_viewed(&path);
+            }
+
             Action::CheckoutPr(pr_number) => {
                 self.checkout_pr(pr_number)?;
             }
 
+            Action::ReviewInPlace(pr_number) => {
+                self.review_in_place(pr_number)?;
+            }
+
+            Action::CreatePr => {
+                if let Err(e) = self.open_create_pr() {
+                    self.toast = Some(Toast::error(format!("{:#}", e)));
+                }
+            }
+
             Action::ExpandIgnoredDir(dir_path) => {
                 if let Ok(entries) = self.git.list_ignored_dir(&dir_path) {
                     self.file_list_state.insert_ignored_dir_contents(&dir_path, entries);
@@ -599,7 +1093,28 @@ impl App {
             }
 
             Action::OpenReviewModal(review_action) => {
-                self.input_modal_state.show(review_action);
+                self.open_review_modal(review_action);
+            }
+
+            Action::EditMetadata { pr_number, kind } => {
+                if let Err(e) = self.open_picker(pr_number, kind) {
+                    self.toast = Some(Toast::error(format!("{:#}", e)));
+                }
+            }
+
+            Action::LoadCheckLog { job_id, name } => {
+                self.pr_details_view_state.show_log(CheckLog::loading(name.clone()));
+                self.async_loader.load_check_log(job_id, name);
+            }
+
+            Action::OpenUrl(url) => {
+                if let Err(e) = forge::open_url(&url) {
+                    self.toast = Some(Toast::error(format!("{:#}", e)));
+                }
+            }
+
+            Action::OpenLocation { path, line } => {
+                self.open_location(&path, line);
             }
         }
 
@@ -611,6 +1126,69 @@ impl App {
         Ok(())
     }
 
+    /// Open the input modal, prefilling edited drafts and listing the pending
+    /// comments that a verdict will submit
+    fn open_review_modal(&mut self, action: ReviewAction) {
+        match &action {
+            ReviewAction::EditDraft { pr_number, index } => {
+                let body = self
+                    .drafts
+                    .comments(*pr_number)
+                    .get(*index)
+                    .map(|d| d.body.clone())
+                    .unwrap_or_default();
+                self.input_modal_state.show_with_input(action, body);
+            }
+            ReviewAction::EditComment { comment_id, .. } => {
+                let body = self
+                    .selected_pr
+                    .as_ref()
+                    .and_then(|pr| pr.find_comment(*comment_id))
+                    .map(|c| c.body.clone())
+                    .unwrap_or_default();
+                self.input_modal_state.show_with_input(action, body);
+            }
+            ReviewAction::EditReview { review_id, .. } => {
+                let body = self
+                    .selected_pr
+                    .as_ref()
+                    .and_then(|pr| pr.find_review(*review_id))
+                    .map(|r| r.body.clone())
+                    .unwrap_or_default();
+                self.input_modal_state.show_with_input(action, body);
+            }
+            ReviewAction::Approve { pr_number }
+            | ReviewAction::RequestChanges { pr_number }
+            | ReviewAction::Comment { pr_number } => {
+                let pending = self.drafts.comments(*pr_number);
+                // A comment review may consist of inline comments alone
+                let body_optional = !pending.is_empty() && matches!(action, ReviewAction::Comment { .. });
+                self.input_modal_state.show(action);
+                self.input_modal_state.context = pending_summary(&pending);
+                self.input_modal_state.body_optional = body_optional;
+            }
+            ReviewAction::Merge { pr_number, .. } => {
+                // Requirements are checked up front; the merge is refused while any are unmet
+                let status = self.forge.merge_status(*pr_number);
+                self.input_modal_state.show(action);
+                match status {
+                    Ok(status) => {
+                        self.input_modal_state.context = status.summary();
+                        if !status.blockers.is_empty() {
+                            self.input_modal_state.blocked =
+                                Some("Merge requirements not met".to_string());
+                        }
+                    }
+                    Err(e) => {
+                        self.input_modal_state.context = vec![format!("{:#}", e)];
+                        self.input_modal_state.blocked = Some("Couldn't check mergeability".to_string());
+                    }
+                }
+            }
+            _ => self.input_modal_state.show(action),
+        }
+    }
+
     fn show_selected_pr_in_preview(&mut self) {
         // Show loading indicator if fetching PR details
         if self.async_loader.is_pr_detail_loading() {
@@ -624,7 +1202,7 @@ impl App {
 
         // Show selected PR details in preview
         if let Some(pr) = self.selected_pr.clone() {
-            self.pr_details_view_state.set_pr(Some(pr));
+            self.pr_details_view_state.set_pr_highlighted(Some(pr), &self.highlighter);
         } else if let Some(summary) = self.pr_list_panel_state.selected() {
             // Show basic info from summary if full details not loaded yet
             self.pr_details_view_state.set_loading(
@@ -635,12 +1213,75 @@ impl App {
         }
     }
 
+    /// Take in a fresh PR list: announce what changed since the previous
+    /// poll and update the unread markers
+    fn note_pr_activity(&mut self, prs: &[PrSummary]) {
+        if let Err(e) = self.seen.sync(prs) {
+            log::warn!("{:#}", e);
+        }
+        // The PR shown while the list is focused is being looked at
+        if self.focused == FocusedWindow::PrList {
+            let selected = self.pr_list_panel_state.selected_number();
+            if let Some(pr) = prs.iter().find(|pr| Some(pr.number) == selected) {
+                if let Err(e) = self.seen.mark_seen(pr) {
+                    log::warn!("{:#}", e);
+                }
+            }
+        }
+
+        let unread: HashSet<u64> = prs
+            .iter()
+            .filter(|pr| !self.seen.news(pr).is_empty())
+            .map(|pr| pr.number)
+            .collect();
+
+        // An empty list may be a failed fetch; keep comparing with the last good one
+        if !prs.is_empty() {
+            let activity = prs.iter().map(|pr| (pr.number, Activity::of(pr))).collect();
+            match self.polled_activity.replace(activity) {
+                Some(previous) => {
+                    let news = seen::poll_news(&previous, prs);
+                    if !news.is_empty() {
+                        self.announce(&news.join("; "));
+                    }
+                }
+                None if !unread.is_empty() => {
+                    let count = unread.len();
+                    let plural = if count == 1 { "" } else { "s" };
+                    self.toast = Some(Toast::success(format!("{} PR{} with new activity", count, plural)));
+                }
+                None => {}
+            }
+        }
+        self.pr_list_panel_state.unread = unread;
+    }
+
+    /// Toast about new activity, plus the configured terminal notification
+    fn announce(&mut self, message: &str) {
+        self.toast = Some(Toast::success(message));
+        if let Some(sequence) = self.config.notify.sequence(message) {
+            let mut stdout = std::io::stdout();
+            let _ = stdout.write_all(sequence.as_bytes()).and_then(|_| stdout.flush());
+        }
+    }
+
+    /// Clear a PR's unread marker, remembering its activity as seen
+    fn mark_pr_seen(&mut self, pr_number: u64) {
+        self.pr_list_panel_state.unread.remove(&pr_number);
+        if let Some(pr) = self.pr_list_panel_state.prs.iter().find(|pr| pr.number == pr_number) {
+            if let Err(e) = self.seen.mark_seen(pr) {
+                log::warn!("{:#}", e);
+            }
+        }
+    }
+
     fn on_focus_change(&mut self) {
         if self.focused == FocusedWindow::PrList {
             // Show selected PR in preview when PR list is focused
             self.show_selected_pr_in_preview();
             // Load details if needed
             if let Some(pr_num) = self.pr_list_panel_state.selected_number() {
+                self.mark_pr_seen(pr_num);
                 self.load_pr_details(pr_num);
             }
         } else if self.focused == FocusedWindow::Preview {
@@ -668,9 +1309,7 @@ impl App {
         let is_browse_mode = matches!(self.timeline_position, TimelinePosition::Browse);
 
         let content = if let Some(entry) = self.file_list_state.selected() {
-            if entry.is_root {
-                PreviewContent::Empty
-            } else if entry.is_dir {
+            if entry.is_root || entry.is_dir {
                 PreviewContent::Empty
             } else if is_browse_mode {
                 // Browse mode - show file content
@@ -693,6 +1332,10 @@ impl App {
                     .diff_at_position(&entry.path, self.timeline_position)
                     .unwrap_or_default();
                 let diff = truncate_large_content(diff);
+                if let Some(pr) = &self.selected_pr {
+                    let anchors = self.thread_anchors(pr, &entry.path);
+                    self.diff_view_state.set_thread_anchors(anchors);
+                }
                 let content = PreviewContent::FileDiff {
                     path: entry.path.clone(),
                     content: diff,
@@ -708,6 +1351,143 @@ impl App {
         self.diff_view_state.set_content(content);
     }
 
+    /// Show the diff of the changed file a job log path refers to, at `line`
+    fn open_location(&mut self, log_path: &str, line: u32) {
+        let files = self.file_list_state.files.iter().map(|f| f.path.as_str());
+        let Some(path) = resolve_path(log_path, files).map(str::to_string) else {
+            self.toast = Some(Toast::error(format!("{} is not among the changed files", log_path)));
+            return;
+        };
+
+        self.pr_details_view_state.clear();
+        self.file_list_state.reveal(&path);
+        self.focused = FocusedWindow::Preview;
+        self.update_preview();
+        self.diff_view_state.go_to_line(line as usize);
+    }
+
+    /// Reposition a file's review threads onto the version being displayed by
+    /// diffing the commit each comment was made on against it. Threads whose
+    /// commit isn't available locally are left out (views use GitHub's line).
+    fn thread_anchors(&self, pr: &PrInfo, path: &str) -> HashMap<u64, Option<u32>> {
+        let mut anchors = HashMap::new();
+        let Some(threads) = pr.file_threads.get(path) else {
+            return anchors;
+        };
+
+        let mut maps: HashMap<&str, Option<LineMap>> = HashMap::new();
+        for thread in threads {
+            let root = thread.root();
+            if root.side.as_deref() == Some("LEFT") {
+                continue;
+            }
+            let Some((commit, line)) = root.anchor() else {
+                continue;
+            };
+            let map = maps
+                .entry(commit)
+                .or_insert_with(|| self.git.line_map(path, commit, self.timeline_position).ok());
+            if let Some(map) = map {
+                anchors.insert(thread.id(), map.map(line));
+            }
+        }
+        anchors
+    }
+
+    /// Write the shown PR's review threads and pending comments to
+    /// `.timecop/` for a coding agent (see `export.rs`)
+    fn export_review(&mut self) {
+        let Some(pr_number) = self.shown_pr() else {
+            self.toast = Some(Toast::error("No PR for this branch to export"));
+            return;
+        };
+        let Some(pr) = self.selected_pr.as_ref().filter(|pr| pr.number == pr_number) else {
+            self.load_pr_details(pr_number);
+            self.toast = Some(Toast::error("PR details are still loading, try again"));
+            return;
+        };
+
+        let mut items = Vec::new();
+        let mut paths: Vec<&String> = pr.file_threads.keys().collect();
+        paths.sort();
+        for path in paths {
+            let anchors = self.export_anchors(pr, path);
+            for thread in &pr.file_threads[path] {
+                let root = thread.root();
+                let side = root.side.clone().unwrap_or_else(|| "RIGHT".to_string());
+                // Removed lines keep their old line number; others move onto the working tree
+                let (line, outdated) = match anchors.get(&thread.id()) {
+                    Some(Some(line)) => (Some(*line), false),
+                    Some(None) => (root.anchor().map(|(_, line)| line), true),
+                    None => (root.anchor().map(|(_, line)| line), false),
+                };
+                let status = match (thread.resolved, outdated) {
+                    (true, _) => "resolved",
+                    (false, true) => "outdated",
+                    (false, false) => "unresolved",
+                };
+                let snippet = line.filter(|_| !outdated).and_then(|line| self.export_snippet(path, line, &side));
+                let comments = thread
+                    .comments
+                    .iter()
+                    .map(|c| FeedbackComment { author: c.author.clone(), body: c.body.clone() })
+                    .collect();
+                items.push(FeedbackItem { path: path.clone(), line, side, status, snippet, comments });
+            }
+        }
+        let title = pr.title.clone();
+        let url = pr.url.clone();
+
+        for draft in self.drafts.comments(pr_number) {
+            let snippet = self.export_snippet(&draft.path, draft.line, &draft.side);
+            items.push(FeedbackItem {
+                path: draft.path,
+                line: Some(draft.line),
+                side: draft.side,
+                status: "pending",
+                snippet,
+                comments: vec![FeedbackComment { author: "you (pending)".to_string(), body: draft.body }],
+            });
+        }
+
+        let mut feedback = Feedback { pr: pr_number, title, url, items };
+        feedback.sort();
+        self.toast = Some(match feedback.write(self.git.path()) {
+            Ok(_) => Toast::success(format!(
+                "Exported {} items to {}/review.md",
+                feedback.items.len(),
+                export::EXPORT_DIR
+            )),
+            Err(e) => Toast::error(format!("Export failed: {:#}", e)),
+        });
+    }
+
+    /// Lines of a PR's threads on `path` in the full diff, like `thread_anchors`
+    fn export_anchors(&self, pr: &PrInfo, path: &str) -> HashMap<u64, Option<u32>> {
+        let mut anchors = HashMap::new();
+        for thread in pr.file_threads.get(path).into_iter().flatten() {
+            let root = thread.root();
+            let Some((commit, line)) = root.anchor().filter(|_| root.side.as_deref() != Some("LEFT")) else {
+                continue;
+            };
+            if let Ok(map) = self.git.line_map(path, commit, TimelinePosition::FullDiff) {
+                anchors.insert(thread.id(), map.map(line));
+            }
+        }
+        anchors
+    }
+
+    /// Code around a line of the full diff, or of the file when the line is
+    /// outside the diff's hunks
+    fn export_snippet(&self, path: &str, line: u32, side: &str) -> Option<export::Snippet> {
+        let old_side = side == "LEFT";
+        let diff = self.git.diff_at_position(path, TimelinePosition::FullDiff).unwrap_or_default();
+        export::diff_snippet(&diff, line, old_side).or_else(|| {
+            let content = self.git.displayed_content(path, TimelinePosition::FullDiff).ok()?;
+            export::content_snippet(&String::from_utf8_lossy(&content), line).filter(|_| !old_side)
+        })
+    }
+
     fn yank_path(&self) {
         let path = if self.focused == FocusedWindow::Preview {
             // Get path with line number from diff view
@@ -806,29 +1586,306 @@ impl App {
         std::mem::replace(&mut self.pending_command, AppCommand::None)
     }
 
-    /// Checkout a PR branch directly
+    /// Checkout a PR branch directly, offering to stash uncommitted changes first
     fn checkout_pr(&mut self, pr_number: u64) -> Result<()> {
-        // Block checkout if there are uncommitted changes
+        if self.git.has_uncommitted_changes() {
+            self.open_review_modal(ReviewAction::StashAndCheckout { pr_number });
+            return Ok(());
+        }
+
+        match self.switch_to_pr(pr_number, None) {
+            Ok(()) => self.toast = Some(Toast::success("Switched to PR branch")),
+            Err(e) => self.toast = Some(Toast::error(format!("Checkout failed: {:#}", e))),
+        }
+        Ok(())
+    }
+
+    /// Stash all changes (untracked files included) and check out the PR.
+    /// The stash is put back right away if the checkout itself fails; once the
+    /// branch is switched it stays stashed for the return point.
+    fn stash_and_checkout(&mut self, pr_number: u64) -> Result<()> {
+        let stash = self
+            .git
+            .stash_all(&format!("timecop: before checking out PR #{}", pr_number))?;
+
+        if let Err(e) = self.switch_to_pr(pr_number, Some(stash)) {
+            return match self.git.pop_stash(stash) {
+                Ok(_) => Err(e.context("Checkout failed, stashed changes restored")),
+                Err(pop) => Err(e.context(format!("Checkout failed. {:#}", pop))),
+            };
+        }
+        Ok(())
+    }
+
+    /// Check out a PR and remember where we came from. Only fails before the
+    /// branch is switched; a failing refresh afterwards is just logged.
+    fn switch_to_pr(&mut self, pr_number: u64, stash: Option<git2::Oid>) -> Result<()> {
+        let previous = self.git.head_name()?;
+        let base_branch = self.forge.checkout_pr(pr_number)?;
+
+        // Fall back to the base branch from the PR list if the forge didn't report one
+        let base_branch = if base_branch.is_empty() {
+            self.pr_list_panel_state
+                .prs
+                .iter()
+                .find(|pr| pr.number == pr_number)
+                .map(|pr| pr.base_branch.clone())
+                .unwrap_or_default()
+        } else {
+            base_branch
+        };
+        self.reviewing_pr = None;
+        self.git.set_review_head(None);
+        if !base_branch.is_empty() {
+            self.git.set_base_branch(&base_branch);
+        }
+
+        // Hopping between PRs keeps the original return point unless new
+        // work was stashed on the way
+        let return_point = match self.return_point.take() {
+            Some(point) if stash.is_none() => point,
+            _ => ReturnPoint {
+                branch: previous,
+                stash: stash.map(|oid| oid.to_string()),
+            },
+        };
+        if let Err(e) = return_point.save(self.git.git_dir()) {
+            log::warn!("Failed to save return point: {:#}", e);
+        }
+        self.return_point = Some(return_point);
+
+        if let Err(e) = self.refresh() {
+            log::warn!("Failed to refresh after checking out PR #{}: {:#}", pr_number, e);
+        }
+        Ok(())
+    }
+
+    /// Go back to the branch a PR checkout left and restore its stash
+    fn return_to_previous_branch(&mut self) -> Result<()> {
+        let Some(point) = self.return_point.clone() else {
+            self.toast = Some(Toast::error("No previous branch to return to"));
+            return Ok(());
+        };
         if self.git.has_uncommitted_changes() {
             self.toast = Some(Toast::error("Commit or stash changes before switching branches"));
             return Ok(());
         }
+        if let Err(e) = self.git.switch_branch(&point.branch) {
+            self.toast = Some(Toast::error(format!("{:#}", e)));
+            return Ok(());
+        }
 
-        match self.github.checkout_pr(pr_number) {
-            Ok(base_branch) => {
-                if !base_branch.is_empty() {
-                    self.git.set_base_branch(&base_branch);
-                }
-                self.toast = Some(Toast::success("Switched to PR branch"));
-                self.refresh()?;
+        self.return_point = None;
+        if let Err(e) = ReturnPoint::clear(self.git.git_dir()) {
+            log::warn!("Failed to clear return point: {:#}", e);
+        }
+        self.reviewing_pr = None;
+        self.git.set_review_head(None);
+
+        let stash = point.stash.as_deref().and_then(|s| git2::Oid::from_str(s).ok());
+        self.toast = Some(match stash.map(|oid| self.git.pop_stash(oid)) {
+            None => Toast::success(format!("Returned to {}", point.branch)),
+            Some(Ok(true)) => Toast::success(format!("Returned to {}, stashed changes restored", point.branch)),
+            Some(Ok(false)) => Toast::error(format!("Returned to {}, but its stash entry is gone", point.branch)),
+            Some(Err(e)) => Toast::error(format!("Returned to {}. {:#}", point.branch, e)),
+        });
+        self.refresh()
+    }
+
+    /// Review a PR's head without checking it out: it's fetched into a hidden
+    /// ref and diffed from the object database. Toggles off for the PR
+    /// already being reviewed.
+    fn review_in_place(&mut self, pr_number: u64) -> Result<()> {
+        if self.reviewing_pr == Some(pr_number) {
+            self.reviewing_pr = None;
+            self.git.set_review_head(None);
+            self.git.refresh_base_branch();
+            self.toast = Some(Toast::success(format!("Back to {}", self.branch)));
+        } else {
+            let head_ref = self.forge.head_ref(pr_number);
+            if let Err(e) = self.git.review_pr_head(&head_ref, pr_number) {
+                self.toast = Some(Toast::error(format!("Fetching PR failed: {:#}", e)));
+                return Ok(());
             }
-            Err(e) => {
-                self.toast = Some(Toast::error(format!("Checkout failed: {}", e)));
+            let base_branch = self
+                .pr_list_panel_state
+                .prs
+                .iter()
+                .find(|pr| pr.number == pr_number)
+                .map(|pr| pr.base_branch.clone())
+                .unwrap_or_default();
+            if base_branch.is_empty() {
+                self.git.refresh_base_branch();
+            } else {
+                self.git.set_base_branch(&base_branch);
             }
+            self.reviewing_pr = Some(pr_number);
+            self.toast = Some(Toast::success(format!("Reviewing PR #{} in place", pr_number)));
         }
+
+        // Commits and stats belong to a different head now
+        self.diff_stats = DiffStats::default();
+        if self.timeline_position != TimelinePosition::Browse {
+            self.timeline_position = TimelinePosition::default();
+        }
+        self.refresh()
+    }
+
+    /// Open the create PR form for the current branch, prefilled from its
+    /// commits and the repository's PR template
+    fn open_create_pr(&mut self) -> Result<()> {
+        if let Some(pr_number) = self.reviewing_pr {
+            anyhow::bail!("Reviewing PR #{} in place; press i to stop first", pr_number);
+        }
+        let head = self.git.head_name()?;
+        if head != self.branch {
+            anyhow::bail!("HEAD is detached; check out a branch to open a PR from");
+        }
+        if let Some(pr) = self.pr_list_panel_state.current_branch_pr() {
+            anyhow::bail!("PR #{} is already open for {}", pr.number, head);
+        }
+        let base = self.git.base_branch_name().context("No base branch found")?.to_string();
+        if base == head {
+            anyhow::bail!("{} is the base branch; open a PR from a feature branch", head);
+        }
+        let messages = self.git.commit_messages_since_base()?;
+        if messages.is_empty() {
+            anyhow::bail!("No commits on {} since {}", head, base);
+        }
+
+        let mut bases = vec![base.clone()];
+        bases.extend(
+            self.git
+                .remote_branches()
+                .into_iter()
+                .filter(|b| *b != base && *b != head),
+        );
+        let template = pr_template::find_template(self.git.path());
+        let (title, body) = pr_template::compose(&messages, template.as_deref());
+
+        self.create_pr_state.show(head.clone(), bases, title, body);
+        let mut context = vec![format!(
+            "{} commit{} since {}",
+            messages.len(),
+            if messages.len() == 1 { "" } else { "s" },
+            base
+        )];
+        if !self.git.has_upstream(&head) {
+            context.push(format!("{} has no upstream; it will be pushed to {}", head, self.git.remotes().push));
+        }
+        self.create_pr_state.context = context;
+        Ok(())
+    }
+
+    /// Push the branch if it has no upstream yet, then open the PR
+    fn submit_create_pr(&mut self) {
+        let mut pr = self.create_pr_state.new_pr();
+        if self.git.remotes().is_fork() {
+            pr.head_repo = forge::push_remote(self.git.path()).map(|r| r.path);
+        }
+        let result = (|| -> Result<u64> {
+            if !self.git.has_upstream(&pr.head) {
+                self.git.push_branch(&pr.head)?;
+            }
+            self.forge.create_pr(&pr)
+        })();
+
+        match result {
+            Ok(pr_number) => {
+                self.create_pr_state.hide();
+                self.toast = Some(Toast::success(format!("PR #{} opened", pr_number)));
+                // Pick up the new PR on the next tick
+                self.last_pr_list_poll =
+                    Instant::now() - self.config.timing.pr_poll_interval - Duration::from_secs(1);
+            }
+            Err(e) => self.create_pr_state.set_error(format!("Error: {:#}", e)),
+        }
+    }
+
+    /// Open the picker for a PR's reviewers, assignees or labels, checking
+    /// the current entries from the loaded PR details
+    fn open_picker(&mut self, pr_number: u64, kind: MetadataKind) -> Result<()> {
+        let Some(pr) = self.selected_pr.as_ref().filter(|pr| pr.number == pr_number) else {
+            anyhow::bail!("PR #{} is still loading", pr_number);
+        };
+        let current = kind.current(pr).to_vec();
+        let author = pr.author.clone();
+        let mut options = self.forge.metadata_options(kind)?;
+        // Authors can't review their own PR
+        if kind == MetadataKind::Reviewers {
+            options.retain(|o| *o != author);
+        }
+        self.picker_state.show(pr_number, kind, options, &current);
         Ok(())
     }
 
+    /// Open the range-diff with the branch's earlier revisions: the shown
+    /// PR's force-pushes on the forge, then the branch and upstream reflogs
+    fn open_range_diff(&mut self) {
+        let mut revisions: Vec<(String, String)> = Vec::new();
+        let pr_number = self.shown_pr();
+        if let Some(pr_number) = pr_number.filter(|_| self.forge_available) {
+            match self.forge.head_revisions(pr_number) {
+                Ok(heads) => revisions.extend(
+                    heads
+                        .into_iter()
+                        .map(|head| (head.sha, format!("{} {}", head.note, forge::short_timestamp(&head.at)))),
+                ),
+                Err(e) => log::warn!("{:#}", e),
+            }
+        }
+        revisions.extend(self.git.reflog_revisions());
+
+        let mut choices: Vec<RevisionChoice> = Vec::new();
+        for (sha, label) in revisions {
+            if choices.iter().all(|c| c.sha != sha) {
+                let summary = self.git.commit_summary(&sha).unwrap_or_default();
+                choices.push(RevisionChoice { sha, label, summary });
+            }
+        }
+        let title = match pr_number {
+            Some(n) => format!("PR #{}", n),
+            None => self.branch.clone(),
+        };
+        self.range_diff_state.show(title, choices);
+    }
+
+    /// Compute the range-diff of the two picked revisions
+    fn show_range_diff(&mut self) {
+        let Some((old, new)) = self.range_diff_state.picked_shas() else {
+            return;
+        };
+        match self.git.range_diff(&old, &new) {
+            Ok(entries) => self.range_diff_state.show_entries(entries),
+            Err(e) => self.range_diff_state.set_error(format!("Error: {:#}", e)),
+        }
+    }
+
+    /// Apply the picker's changes and reload the PR
+    fn submit_metadata(&mut self) {
+        let (add, remove) = self.picker_state.changes();
+        if add.is_empty() && remove.is_empty() {
+            self.picker_state.hide();
+            return;
+        }
+        let (pr_number, kind) = (self.picker_state.pr_number, self.picker_state.kind);
+        match self.forge.update_metadata(pr_number, kind, &add, &remove) {
+            Ok(()) => {
+                self.picker_state.hide();
+                self.toast = Some(Toast::success(format!("{} updated", kind.label())));
+                self.reload_pr(pr_number);
+            }
+            Err(e) => self.picker_state.set_error(format!("Error: {:#}", e)),
+        }
+    }
+
+    /// Reload a changed PR's details, and the list that summarizes it
+    fn reload_pr(&mut self, pr_number: u64) {
+        self.selected_pr = None;
+        self.async_loader.load_pr_details(pr_number);
+        self.last_pr_list_poll = Instant::now() - self.config.timing.pr_poll_interval - Duration::from_secs(1);
+    }
+
     /// Submit the review action from the input modal
     fn submit_review_action(&mut self) -> Result<()> {
         let Some(action) = self.input_modal_state.action.clone() else {
@@ -838,18 +1895,52 @@ impl App {
         let body = self.input_modal_state.take_input();
 
         let result = match &action {
-            ReviewAction::Approve { pr_number } => {
-                self.github.approve_pr(*pr_number)
+            ReviewAction::Approve { .. }
+            | ReviewAction::RequestChanges { .. }
+            | ReviewAction::Comment { .. } => self.submit_verdict(&action, &body),
+            ReviewAction::LineComment { pr_number, path, line, side } => {
+                let draft = DraftComment {
+                    path: path.clone(),
+                    line: *line,
+                    side: side.to_string(),
+                    body: body.clone(),
+                };
+                self.drafts.add(*pr_number, draft)
             }
-            ReviewAction::RequestChanges { pr_number } => {
-                self.github.request_changes(*pr_number, &body)
+            ReviewAction::EditDraft { pr_number, index } => {
+                self.drafts.update(*pr_number, *index, body.clone())
             }
-            ReviewAction::Comment { pr_number } => {
-                self.github.comment_pr(*pr_number, &body)
+            ReviewAction::DeleteDraft { pr_number, index } => {
+                self.drafts.remove(*pr_number, *index)
             }
-            ReviewAction::LineComment { pr_number, path, line } => {
-                self.github.add_line_comment(*pr_number, path, *line, &body)
+            ReviewAction::ReplyToThread { pr_number, comment_id, .. } => {
+                match self.selected_pr.as_ref().and_then(|pr| pr.find_thread(*comment_id)) {
+                    Some(thread) => self.forge.reply_to_thread(*pr_number, thread, &body),
+                    None => Err(anyhow::anyhow!("Thread no longer exists")),
+                }
+            }
+            ReviewAction::ResolveThread { pr_number, thread_id, resolve } => {
+                self.forge.set_thread_resolved(*pr_number, thread_id, *resolve)
+            }
+            ReviewAction::EditComment { pr_number, comment_id } => {
+                let conversation = self.is_conversation_comment(*comment_id);
+                self.forge.edit_comment(*pr_number, *comment_id, conversation, &body)
+            }
+            ReviewAction::DeleteComment { pr_number, comment_id } => {
+                let conversation = self.is_conversation_comment(*comment_id);
+                self.forge.delete_comment(*pr_number, *comment_id, conversation)
+            }
+            ReviewAction::ConversationComment { pr_number } => {
+                self.forge.add_conversation_comment(*pr_number, &body)
+            }
+            ReviewAction::EditReview { pr_number, review_id } => {
+                self.forge.edit_review(*pr_number, *review_id, &body)
             }
+            ReviewAction::StashAndCheckout { pr_number } => self.stash_and_checkout(*pr_number),
+            ReviewAction::Merge { pr_number, method, delete_branch } => {
+                self.forge.merge_pr(*pr_number, *method, *delete_branch)
+            }
+            ReviewAction::SetDraft { pr_number, draft } => self.forge.set_draft(*pr_number, *draft),
         };
 
         match result {
@@ -858,34 +1949,104 @@ impl App {
 
                 // Show success toast
                 let success_msg = match &action {
-                    ReviewAction::Approve { .. } => "PR approved",
-                    ReviewAction::RequestChanges { .. } => "Changes requested",
-                    ReviewAction::Comment { .. } => "Comment posted",
-                    ReviewAction::LineComment { .. } => "Line comment added",
+                    ReviewAction::Approve { .. } => "PR approved".to_string(),
+                    ReviewAction::RequestChanges { .. } => "Changes requested".to_string(),
+                    ReviewAction::Comment { .. } => "Comment posted".to_string(),
+                    ReviewAction::LineComment { pr_number, .. } => {
+                        format!("Added to pending review ({})", self.drafts.count(*pr_number))
+                    }
+                    ReviewAction::EditDraft { .. } => "Pending comment updated".to_string(),
+                    ReviewAction::DeleteDraft { .. } => "Pending comment discarded".to_string(),
+                    ReviewAction::ReplyToThread { .. } => "Reply posted".to_string(),
+                    ReviewAction::ResolveThread { resolve: true, .. } => "Thread resolved".to_string(),
+                    ReviewAction::ResolveThread { resolve: false, .. } => "Thread reopened".to_string(),
+                    ReviewAction::EditComment { .. } => "Comment updated".to_string(),
+                    ReviewAction::DeleteComment { .. } => "Comment deleted".to_string(),
+                    ReviewAction::EditReview { .. } => "Review updated".to_string(),
+                    ReviewAction::ConversationComment { .. } => "Comment posted".to_string(),
+                    ReviewAction::StashAndCheckout { .. } => "Changes stashed, switched to PR branch".to_string(),
+                    ReviewAction::Merge { pr_number, method, .. } => {
+                        format!("PR #{} merged ({})", pr_number, method.label())
+                    }
+                    ReviewAction::SetDraft { draft: true, .. } => "PR converted to draft".to_string(),
+                    ReviewAction::SetDraft { draft: false, .. } => "PR ready for review".to_string(),
                 };
                 self.toast = Some(Toast::success(success_msg));
 
-                // Refresh PR details to show the new comment/review
-                if let Some(pr_num) = self.pr_list_panel_state.selected_number() {
+                if let ReviewAction::Approve { pr_number }
+                | ReviewAction::RequestChanges { pr_number }
+                | ReviewAction::Comment { pr_number } = action
+                {
+                    self.record_review(pr_number);
+                }
+
+                if matches!(action, ReviewAction::Merge { .. }) {
+                    // The merged PR leaves the open list
+                    self.selected_pr = None;
+                    self.last_pr_list_poll =
+                        Instant::now() - self.config.timing.pr_poll_interval - Duration::from_secs(1);
+                } else if let ReviewAction::SetDraft { pr_number, .. } = action {
+                    self.reload_pr(pr_number);
+                } else if action.is_local() {
+                    // Only the local pending review changed
+                    self.sync_pending_comments();
+                    if self.focused == FocusedWindow::PrList {
+                        self.show_selected_pr_in_preview();
+                    }
+                } else if let Some(pr_num) = self.pr_list_panel_state.selected_number() {
+                    // Refresh PR details to show the new comment/review
                     // Force reload by clearing the current PR
                     self.selected_pr = None;
                     self.async_loader.load_pr_details(pr_num);
                 }
             }
             Err(e) => {
-                self.input_modal_state.set_error(format!("Error: {}", e));
+                self.input_modal_state.set_error(format!("Error: {:#}", e));
             }
         }
 
         Ok(())
     }
 
+    /// Whether a comment of the selected PR lives on the conversation (issues API)
+    fn is_conversation_comment(&self, comment_id: u64) -> bool {
+        self.selected_pr
+            .as_ref()
+            .and_then(|pr| pr.find_comment(comment_id))
+            .is_some_and(|c| c.conversation)
+    }
+
+    /// Submit a review verdict, bundling any pending line comments into the
+    /// same review so they are published together
+    fn submit_verdict(&mut self, action: &ReviewAction, body: &str) -> Result<()> {
+        let (pr_number, event) = match action {
+            ReviewAction::Approve { pr_number } => (*pr_number, "APPROVE"),
+            ReviewAction::RequestChanges { pr_number } => (*pr_number, "REQUEST_CHANGES"),
+            ReviewAction::Comment { pr_number } => (*pr_number, "COMMENT"),
+            _ => return Ok(()),
+        };
+
+        let pending = self.drafts.comments(pr_number);
+        if pending.is_empty() {
+            return match action {
+                ReviewAction::Approve { .. } => self.forge.approve_pr(pr_number),
+                ReviewAction::RequestChanges { .. } => self.forge.request_changes(pr_number, body),
+                _ => self.forge.comment_pr(pr_number, body),
+            };
+        }
+
+        self.forge.submit_review(pr_number, event, body, &pending)?;
+        self.drafts.clear(pr_number)?;
+        self.sync_pending_comments();
+        Ok(())
+    }
+
     /// Render the UI
     pub fn render(&mut self, frame: &mut Frame) {
         let area = frame.area();
         let colors = &self.config.colors;
         let layout = AppLayout::default();
-        let areas = layout.compute(area, self.pr_list_panel_state.prs.len());
+        let areas = layout.compute(area, self.pr_list_panel_state.display_rows());
 
         // Store layout areas for mouse hit testing
         self.layout_areas = Some(areas.clone());
@@ -941,6 +2102,30 @@ impl App {
             frame.render_widget(input_modal, modal_area);
         }
 
+        if self.picker_state.visible {
+            let modal_area = centered_rect(40, 60, area);
+            let picker = Picker::new(colors, &mut self.picker_state);
+            frame.render_widget(picker, modal_area);
+        }
+
+        if self.range_diff_state.visible {
+            let modal_area = centered_rect(80, 80, area);
+            let modal = RangeDiffModal::new(colors, &mut self.range_diff_state);
+            frame.render_widget(modal, modal_area);
+        }
+
+        if self.activity_log_state.visible {
+            let modal_area = centered_rect(80, 70, area);
+            let log = ActivityLog::new(colors, &mut self.activity_log_state);
+            frame.render_widget(log, modal_area);
+        }
+
+        if self.create_pr_state.visible {
+            let modal_area = centered_rect(60, 60, area);
+            let form = CreatePrForm::new(colors, &self.create_pr_state);
+            frame.render_widget(form, modal_area);
+        }
+
         // Render toast notification
         if let Some(ref toast) = self.toast {
             self.render_toast(frame, area, toast);
@@ -985,8 +2170,10 @@ impl App {
             .fg(colors.muted);
 
         // Timeline layout (left to right, older to newer):
-        // T─I─M─E─C─O─P─○─○─○─●─[full]─[files]
-        //               -3-2-1 wip full  files
+        // T─I─M─E─C─O─P─○─○─○─··●─[rev]─[full]─[files]
+        //               -3-2-1 ~~ wip since  full  files
+        //                          review (after a review)
+        // (· are working-tree checkpoints, ~2 ~1)
 
         let mut spans = Vec::new();
 
@@ -1011,12 +2198,27 @@ impl App {
             spans.push(Span::styled("─", if is_selected { highlight_bold } else { primary_bold }));
         }
 
+        // Checkpoint dots (snapshots of uncommitted edits) - oldest first
+        for i in (1..=self.git.checkpoint_count()).rev() {
+            let is_selected = matches!(self.timeline_position, TimelinePosition::Checkpoint(n) if n == i);
+            spans.push(Span::styled("·", if is_selected { highlight_bold } else { primary_bold }));
+        }
+
         // Wip marker (● filled dot - uncommitted changes, like a commit in progress)
         let wip_selected = matches!(self.timeline_position, TimelinePosition::Wip);
         let wip_style = if wip_selected { highlight_bold } else { primary_bold };
         spans.push(Span::styled("●", wip_style));
         spans.push(Span::styled("─", if wip_selected { highlight_bold } else { primary_bold }));
 
+        // [rev] marker (changes since the last review, when there was one)
+        if self.git.has_reviewed_commit() {
+            let rev_selected = matches!(self.timeline_position, TimelinePosition::SinceReview);
+            spans.push(Span::styled("[", primary_bold));
+            spans.push(Span::styled("rev", if rev_selected { highlight_bold } else { primary_bold }));
+            spans.push(Span::styled("]", primary_bold));
+            spans.push(Span::styled("─", primary_bold));
+        }
+
         // [full] marker (full diff - all committed changes)
         let full_selected = matches!(self.timeline_position, TimelinePosition::FullDiff);
         spans.push(Span::styled("[", primary_bold));
@@ -1031,9 +2233,15 @@ impl App {
         spans.push(Span::styled("]", primary_bold));
 
         // State label
+        let checkpoint_label;
         let state_label = match self.timeline_position {
+            TimelinePosition::Checkpoint(n) => {
+                checkpoint_label = format!("~{}", n);
+                checkpoint_label.as_str()
+            }
             TimelinePosition::Browse => "files",
             TimelinePosition::Wip => "wip",
+            TimelinePosition::SinceReview => "since review",
             TimelinePosition::FullDiff => "full diff",
             TimelinePosition::CommitDiff(n) => match n {
                 1 => "-1", 2 => "-2", 3 => "-3", 4 => "-4", 5 => "-5",
@@ -1042,7 +2250,7 @@ impl App {
                 _ => "-16",
             },
         };
-        const LABEL_WIDTH: usize = 11;
+        const LABEL_WIDTH: usize = 12;
         let padded_label = format!("  {:width$}", state_label, width = LABEL_WIDTH);
         spans.push(Span::styled(padded_label, dim_style));
 
@@ -1060,19 +2268,35 @@ impl App {
         let colors = &self.config.colors;
         let total_width = area.width as usize;
 
-        // Left: branch (+stats in full diff mode)
-        let left_content = if matches!(self.timeline_position, TimelinePosition::FullDiff)
+        // Left: branch or PR reviewed in place (+stats in full diff mode)
+        let head = match self.reviewing_pr {
+            Some(pr_number) => format!("PR #{} (in place)", pr_number),
+            None => self.branch.clone(),
+        };
+        let mut left_content = if matches!(self.timeline_position, TimelinePosition::FullDiff)
             && (self.diff_stats.added > 0 || self.diff_stats.removed > 0) {
-            format!(" {}  +{} -{}", self.branch, format_count(self.diff_stats.added), format_count(self.diff_stats.removed))
+            format!(" {}  +{} -{}", head, format_count(self.diff_stats.added), format_count(self.diff_stats.removed))
         } else {
-            format!(" {}", self.branch)
+            format!(" {}", head)
         };
+        let viewed_count = self.file_list_state.viewed_count();
+        if viewed_count > 0 {
+            left_content.push_str(&format!("  viewed {}/{}", viewed_count, self.file_list_state.file_count()));
+        }
+        if self.follow {
+            left_content.push_str("  ⟳ following");
+        }
 
         // Right: position info
         let right_content = match self.timeline_position {
             TimelinePosition::Browse => "all files ".to_string(),
             TimelinePosition::FullDiff => "full diff (base → head) ".to_string(),
             TimelinePosition::Wip => "uncommitted (wip) ".to_string(),
+            TimelinePosition::SinceReview => "since your last review (reviewed → head) ".to_string(),
+            TimelinePosition::Checkpoint(n) => match self.git.checkpoint_time(n) {
+                Some(taken) => format!("checkpoint ~{}, {} ago ", n, format_age(taken)),
+                None => format!("checkpoint ~{} ", n),
+            },
             TimelinePosition::CommitDiff(n) => {
                 if let Some(msg) = self.timeline_commit_message() {
                     let max_len = 40;
@@ -1120,6 +2344,25 @@ impl App {
     }
 }
 
+/// Modal context lines describing the pending comments a verdict will submit
+fn pending_summary(pending: &[DraftComment]) -> Vec<String> {
+    const MAX_LISTED: usize = 5;
+
+    if pending.is_empty() {
+        return Vec::new();
+    }
+
+    let noun = if pending.len() == 1 { "comment" } else { "comments" };
+    let mut lines = vec![format!("Includes {} pending {}:", pending.len(), noun)];
+    for draft in pending.iter().take(MAX_LISTED) {
+        lines.push(format!("  ✎ {}:{}", draft.path, draft.line));
+    }
+    if pending.len() > MAX_LISTED {
+        lines.push(format!("  … and {} more", pending.len() - MAX_LISTED));
+    }
+    lines
+}
+
 /// Truncate content that exceeds the max line limit to avoid slow rendering
 const MAX_PREVIEW_LINES: usize = 10_000;
 
@@ -1,12 +1,17 @@
 //! Async loading utilities for PR data
 
+use anyhow::Result;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::thread;
 
-use crate::github::{GitHubClient, PrInfo, PrSummary};
+use crate::forge::{self, PrInfo, PrSummary};
 
 /// Manages async loading of PR data
 pub struct AsyncLoader {
+    /// Repository whose forge is queried
+    repo: PathBuf,
+
     // PR list loading
     pr_list_rx: Option<Receiver<Vec<PrSummary>>>,
     pr_list_loading: bool,
@@ -15,22 +20,25 @@ pub struct AsyncLoader {
     pr_detail_rx: Option<Receiver<Option<PrInfo>>>,
     pr_detail_loading: bool,
     pr_detail_number: Option<u64>,
-}
 
-impl Default for AsyncLoader {
-    fn default() -> Self {
-        Self::new()
-    }
+    // CI job log loading (check name, log lines)
+    check_log_rx: Option<Receiver<(String, Result<Vec<String>>)>>,
+
+    // Files viewed on the forge (PR number, paths)
+    viewed_rx: Option<Receiver<(u64, Result<Vec<String>>)>>,
 }
 
 impl AsyncLoader {
-    pub fn new() -> Self {
+    pub fn new(repo: &Path) -> Self {
         Self {
+            repo: repo.to_path_buf(),
             pr_list_rx: None,
             pr_list_loading: false,
             pr_detail_rx: None,
             pr_detail_loading: false,
             pr_detail_number: None,
+            check_log_rx: None,
+            viewed_rx: None,
         }
     }
 
@@ -63,10 +71,11 @@ impl AsyncLoader {
         self.pr_list_rx = Some(rx);
         self.pr_list_loading = true;
 
+        let repo = self.repo.clone();
         thread::spawn(move || {
-            let mut github = GitHubClient::new();
-            if github.is_available() {
-                match github.list_open_prs() {
+            let mut forge = forge::detect(&repo);
+            if forge.is_available() {
+                match forge.list_open_prs() {
                     Ok(prs) => {
                         let _ = tx.send(prs);
                     }
@@ -76,7 +85,7 @@ impl AsyncLoader {
                     }
                 }
             } else {
-                log::debug!("GitHub CLI not available, skipping PR list load");
+                log::debug!("{} not available, skipping PR list load", forge.name());
                 let _ = tx.send(vec![]);
             }
         });
@@ -93,10 +102,11 @@ impl AsyncLoader {
         self.pr_detail_loading = true;
         self.pr_detail_number = Some(pr_number);
 
+        let repo = self.repo.clone();
         thread::spawn(move || {
-            let mut github = GitHubClient::new();
-            if github.is_available() {
-                match github.get_pr_by_number(pr_number) {
+            let mut forge = forge::detect(&repo);
+            if forge.is_available() {
+                match forge.get_pr_by_number(pr_number) {
                     Ok(pr) => {
                         let _ = tx.send(pr);
                     }
@@ -111,6 +121,67 @@ impl AsyncLoader {
         });
     }
 
+    /// Spawn background thread to fetch a CI job log. A newer request
+    /// replaces one still in flight.
+    pub fn load_check_log(&mut self, job_id: u64, name: String) {
+        let (tx, rx) = mpsc::channel();
+        self.check_log_rx = Some(rx);
+
+        let repo = self.repo.clone();
+        thread::spawn(move || {
+            let forge = forge::detect(&repo);
+            let _ = tx.send((name, forge.check_log(job_id)));
+        });
+    }
+
+    /// Spawn background thread to fetch the files viewed on the forge
+    pub fn load_viewed_files(&mut self, pr_number: u64) {
+        if self.viewed_rx.is_some() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        self.viewed_rx = Some(rx);
+
+        let repo = self.repo.clone();
+        thread::spawn(move || {
+            let forge = forge::detect(&repo);
+            let _ = tx.send((pr_number, forge.viewed_files(pr_number)));
+        });
+    }
+
+    /// Poll for fetched viewed files: (PR number, paths or error)
+    pub fn poll_viewed_files(&mut self) -> Option<(u64, Result<Vec<String>>)> {
+        let rx = self.viewed_rx.as_ref()?;
+        match rx.try_recv() {
+            Ok(result) => {
+                self.viewed_rx = None;
+                Some(result)
+            }
+            Err(TryRecvError::Disconnected) => {
+                self.viewed_rx = None;
+                None
+            }
+            Err(TryRecvError::Empty) => None,
+        }
+    }
+
+    /// Poll for a fetched job log: (check name, log lines or error)
+    pub fn poll_check_log(&mut self) -> Option<(String, Result<Vec<String>>)> {
+        let rx = self.check_log_rx.as_ref()?;
+        match rx.try_recv() {
+            Ok(result) => {
+                self.check_log_rx = None;
+                Some(result)
+            }
+            Err(TryRecvError::Disconnected) => {
+                log::debug!("Check log loader disconnected");
+                self.check_log_rx = None;
+                None
+            }
+            Err(TryRecvError::Empty) => None,
+        }
+    }
+
     /// Poll for completed PR list loading
     pub fn poll_pr_list(&mut self) -> Option<Vec<PrSummary>> {
         let rx = self.pr_list_rx.as_ref()?;
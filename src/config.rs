@@ -10,6 +10,11 @@ pub struct Config {
     pub colors: Colors,
     pub timing: Timing,
     pub theme: ThemeMode,
+    pub notify: Notify,
+    /// Mirror viewed files to and from the forge (`TIMECOP_SYNC_VIEWED=1`)
+    pub sync_viewed: bool,
+    /// Working-tree checkpoints kept per branch
+    pub checkpoint_limit: usize,
 }
 
 impl Default for Config {
@@ -19,6 +24,9 @@ impl Default for Config {
             colors: Colors::for_theme(theme),
             timing: Timing::default(),
             theme,
+            notify: Notify::from_env(),
+            sync_viewed: std::env::var(SYNC_VIEWED_ENV).is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+            checkpoint_limit: 30,
         }
     }
 }
@@ -162,3 +170,41 @@ impl Default for Timing {
         }
     }
 }
+
+/// Environment variable turning on syncing viewed files with GitHub
+pub const SYNC_VIEWED_ENV: &str = "TIMECOP_SYNC_VIEWED";
+
+/// Environment variable choosing a terminal notification for new PR activity
+/// ("bell" or "osc9")
+pub const NOTIFY_ENV: &str = "TIMECOP_NOTIFY";
+
+/// How new PR activity is announced besides the toast
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Notify {
+    #[default]
+    Off,
+    /// Terminal bell (BEL)
+    Bell,
+    /// Desktop notification via OSC 9 (iTerm2, WezTerm, kitty, ...)
+    Osc9,
+}
+
+impl Notify {
+    pub fn from_env() -> Self {
+        match std::env::var(NOTIFY_ENV).unwrap_or_default().to_lowercase().as_str() {
+            "bell" => Self::Bell,
+            "osc9" => Self::Osc9,
+            _ => Self::Off,
+        }
+    }
+
+    /// Escape sequence to write to the terminal, if any
+    pub fn sequence(self, message: &str) -> Option<String> {
+        match self {
+            Self::Off => None,
+            Self::Bell => Some("\x07".to_string()),
+            // Control characters would end the sequence early
+            Self::Osc9 => Some(format!("\x1b]9;{}\x07", message.replace(char::is_control, " "))),
+        }
+    }
+}
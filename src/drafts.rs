@@ -0,0 +1,154 @@
+//! Pending review drafts
+//!
+//! Line comments are collected locally and submitted together as a single
+//! review. Drafts are persisted per PR under `.git/timecop/` so they survive
+//! app restarts.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A line comment waiting to be submitted with a review
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DraftComment {
+    pub path: String,
+    pub line: u32,
+    pub side: String, // "LEFT" or "RIGHT"
+    pub body: String,
+}
+
+/// Per-PR draft comments, loaded lazily and written through to disk
+pub struct DraftStore {
+    dir: PathBuf,
+    drafts: HashMap<u64, Vec<DraftComment>>,
+}
+
+impl DraftStore {
+    /// Create a store rooted in the repository's git directory
+    pub fn new(git_dir: &Path) -> Self {
+        Self {
+            dir: git_dir.join("timecop"),
+            drafts: HashMap::new(),
+        }
+    }
+
+    fn file_path(&self, pr_number: u64) -> PathBuf {
+        self.dir.join(format!("review-{}.json", pr_number))
+    }
+
+    /// Load drafts for a PR from disk if not already cached
+    fn load(&mut self, pr_number: u64) -> &mut Vec<DraftComment> {
+        if !self.drafts.contains_key(&pr_number) {
+            let comments = std::fs::read(self.file_path(pr_number))
+                .ok()
+                .and_then(|data| serde_json::from_slice(&data).ok())
+                .unwrap_or_default();
+            self.drafts.insert(pr_number, comments);
+        }
+        self.drafts.entry(pr_number).or_default()
+    }
+
+    fn save(&self, pr_number: u64) -> Result<()> {
+        let path = self.file_path(pr_number);
+        let comments = self.drafts.get(&pr_number).map(|c| c.as_slice()).unwrap_or(&[]);
+
+        if comments.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(&path).context("Failed to remove review draft")?;
+            }
+            return Ok(());
+        }
+
+        std::fs::create_dir_all(&self.dir).context("Failed to create draft directory")?;
+        let data = serde_json::to_vec_pretty(comments)?;
+        std::fs::write(&path, data).context("Failed to write review draft")
+    }
+
+    /// All pending comments for a PR
+    pub fn comments(&mut self, pr_number: u64) -> Vec<DraftComment> {
+        self.load(pr_number).clone()
+    }
+
+    pub fn count(&mut self, pr_number: u64) -> usize {
+        self.load(pr_number).len()
+    }
+
+    pub fn add(&mut self, pr_number: u64, comment: DraftComment) -> Result<()> {
+        self.load(pr_number).push(comment);
+        self.save(pr_number)
+    }
+
+    pub fn update(&mut self, pr_number: u64, index: usize, body: String) -> Result<()> {
+        let comment = self
+            .load(pr_number)
+            .get_mut(index)
+            .context("Draft comment no longer exists")?;
+        comment.body = body;
+        self.save(pr_number)
+    }
+
+    pub fn remove(&mut self, pr_number: u64, index: usize) -> Result<()> {
+        let comments = self.load(pr_number);
+        if index >= comments.len() {
+            anyhow::bail!("Draft comment no longer exists");
+        }
+        comments.remove(index);
+        self.save(pr_number)
+    }
+
+    /// Drop all drafts for a PR (after the review was submitted)
+    pub fn clear(&mut self, pr_number: u64) -> Result<()> {
+        self.load(pr_number).clear();
+        self.save(pr_number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn draft(body: &str) -> DraftComment {
+        DraftComment {
+            path: "src/main.rs".to_string(),
+            line: 7,
+            side: "RIGHT".to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn drafts_persist_across_stores() {
+        let dir = TempDir::new("drafts-persist");
+        let mut store = DraftStore::new(&dir);
+        store.add(1, draft("first")).unwrap();
+        store.add(1, draft("second")).unwrap();
+
+        let mut reopened = DraftStore::new(&dir);
+        assert_eq!(reopened.comments(1), vec![draft("first"), draft("second")]);
+        assert_eq!(reopened.count(2), 0);
+    }
+
+    #[test]
+    fn update_and_remove_drafts() {
+        let dir = TempDir::new("drafts-edit");
+        let mut store = DraftStore::new(&dir);
+        store.add(3, draft("typo")).unwrap();
+        store.add(3, draft("keep")).unwrap();
+        store.update(3, 0, "fixed".to_string()).unwrap();
+        store.remove(3, 1).unwrap();
+        assert_eq!(store.comments(3), vec![draft("fixed")]);
+        assert!(store.remove(3, 5).is_err());
+    }
+
+    #[test]
+    fn clear_removes_draft_file() {
+        let dir = TempDir::new("drafts-clear");
+        let mut store = DraftStore::new(&dir);
+        store.add(4, draft("x")).unwrap();
+        assert!(store.file_path(4).exists());
+        store.clear(4).unwrap();
+        assert!(!store.file_path(4).exists());
+    }
+}
@@ -16,8 +16,9 @@ pub enum AppEvent {
     Key(KeyEvent),
     /// Mouse event
     Mouse(MouseEvent),
-    /// File system change detected
-    FileChanged,
+    /// File system change detected, with the changed paths relative to the
+    /// repo root (empty when only `.git/index` changed)
+    FileChanged(Vec<String>),
     /// Tick for periodic updates
     Tick,
 }
@@ -56,23 +57,19 @@ impl EventHandler {
 
                     if let Ok(event) = event::read() {
                         match event {
-                            Event::Key(key) => {
-                                if event_tx.send(AppEvent::Key(key)).is_err() {
-                                    break;
-                                }
+                            Event::Key(key) if event_tx.send(AppEvent::Key(key)).is_err() => {
+                                break;
                             }
-                            Event::Mouse(mouse) => {
-                                if event_tx.send(AppEvent::Mouse(mouse)).is_err() {
-                                    break;
-                                }
+                            Event::Mouse(mouse) if event_tx.send(AppEvent::Mouse(mouse)).is_err() => {
+                                break;
                             }
                             _ => {}
                         }
                     }
-                } else if !paused_clone.load(Ordering::Relaxed) {
-                    if event_tx.send(AppEvent::Tick).is_err() {
-                        break;
-                    }
+                } else if !paused_clone.load(Ordering::Relaxed)
+                    && event_tx.send(AppEvent::Tick).is_err()
+                {
+                    break;
                 }
             }
         });
@@ -101,15 +98,22 @@ impl EventHandler {
 
         let debouncer = new_debouncer(Duration::from_millis(300), move |res: DebounceEventResult| {
             if let Ok(events) = res {
+                let mut changed = false;
+                let mut paths = Vec::new();
                 for event in events {
                     if matches!(event.kind, DebouncedEventKind::Any) {
                         // Filter out .git internal changes (except index)
                         let rel_path = event.path.strip_prefix(&repo_path).ok();
 
+                        let git_path = rel_path
+                            .map(|p| p.to_string_lossy().starts_with(".git/"))
+                            .unwrap_or(false);
+                        // TimeCop's own exports aren't changes to review
                         let dominated_by_git = rel_path
                             .map(|p| {
                                 let p_str = p.to_string_lossy();
-                                p_str.starts_with(".git/") && !p_str.starts_with(".git/index")
+                                (p_str.starts_with(".git/") && !p_str.starts_with(".git/index"))
+                                    || p_str.starts_with(".timecop/")
                             })
                             .unwrap_or(false);
 
@@ -119,11 +123,16 @@ impl EventHandler {
                             .unwrap_or(false);
 
                         if !dominated_by_git && !is_ignored {
-                            let _ = tx.send(AppEvent::FileChanged);
-                            break;
+                            changed = true;
+                            if let Some(p) = rel_path.filter(|_| !git_path && !event.path.is_dir()) {
+                                paths.push(p.to_string_lossy().into_owned());
+                            }
                         }
                     }
                 }
+                if changed {
+                    let _ = tx.send(AppEvent::FileChanged(paths));
+                }
             }
         });
 
@@ -265,6 +274,10 @@ impl KeyInput {
         key.code == KeyCode::Char('y') && key.modifiers == KeyModifiers::NONE
     }
 
+    pub fn is_follow(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('f') && key.modifiers == KeyModifiers::NONE
+    }
+
     pub fn is_open(key: &KeyEvent) -> bool {
         key.code == KeyCode::Char('o') && key.modifiers == KeyModifiers::NONE
     }
@@ -289,6 +302,88 @@ impl KeyInput {
         key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::NONE
     }
 
+    pub fn is_merge(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('M') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_range_diff(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('F') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_activity_log(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('H') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_export_review(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('E') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_create_pr(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('N') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_edit_reviewers(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('V') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_edit_assignees(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('A') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_edit_labels(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('L') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_toggle_draft(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('D') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_resolve_thread(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('R') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_unresolved_filter(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('U') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    pub fn is_edit(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('e') && key.modifiers == KeyModifiers::NONE
+    }
+
+    pub fn is_delete(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('d') && key.modifiers == KeyModifiers::NONE
+    }
+
+    pub fn is_toggle_viewed(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('v') && key.modifiers == KeyModifiers::NONE
+    }
+
+    pub fn is_review_in_place(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('i') && key.modifiers == KeyModifiers::NONE
+    }
+
+    pub fn is_return_to_branch(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('b') && key.modifiers == KeyModifiers::NONE
+    }
+
+    pub fn is_search(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('/')
+    }
+
+    pub fn is_cycle_sort(key: &KeyEvent) -> bool {
+        key.code == KeyCode::Char('S') && key.modifiers == KeyModifiers::SHIFT
+    }
+
+    /// Number key 1-9 (e.g. tab selection)
+    pub fn digit(key: &KeyEvent) -> Option<usize> {
+        match key.code {
+            KeyCode::Char(c @ '1'..='9') if key.modifiers == KeyModifiers::NONE => {
+                c.to_digit(10).map(|d| d as usize)
+            }
+            _ => None,
+        }
+    }
+
     pub fn is_timeline_next(key: &KeyEvent) -> bool {
         key.code == KeyCode::Char(',') && key.modifiers == KeyModifiers::NONE
     }
@@ -0,0 +1,255 @@
+//! Review feedback for coding agents
+//!
+//! A PR's review threads and pending local comments are written to
+//! `.timecop/review.md`, a prompt an agent can be pointed at, and
+//! `.timecop/review.json`, the same items for tools. Each item has its path,
+//! line range, the code around it and the comments. The directory ignores
+//! itself so exports never show up as changes.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::ui::parse_hunk_header;
+
+/// Directory exports are written to, relative to the repository root
+pub const EXPORT_DIR: &str = ".timecop";
+
+/// Lines of context around the commented line
+const CONTEXT_LINES: usize = 3;
+
+/// Code around a commented line
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snippet {
+    /// First and last line shown, on the commented side
+    pub start: u32,
+    pub end: u32,
+    /// Diff lines (with +/-/space prefixes) or plain file lines
+    pub text: String,
+    pub is_diff: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedbackComment {
+    pub author: String,
+    pub body: String,
+}
+
+/// One review thread or pending comment
+#[derive(Debug, Clone, Serialize)]
+pub struct FeedbackItem {
+    pub path: String,
+    /// Line commented on, in the working tree (or the old file for removed lines)
+    pub line: Option<u32>,
+    /// "RIGHT" (new code) or "LEFT" (removed code)
+    pub side: String,
+    /// "unresolved", "resolved", "outdated" (the line changed since) or "pending"
+    pub status: &'static str,
+    pub snippet: Option<Snippet>,
+    pub comments: Vec<FeedbackComment>,
+}
+
+/// Everything exported for one PR
+#[derive(Debug, Clone, Serialize)]
+pub struct Feedback {
+    pub pr: u64,
+    pub title: String,
+    pub url: String,
+    pub items: Vec<FeedbackItem>,
+}
+
+impl Feedback {
+    /// Sort open feedback first, then by path and line
+    pub fn sort(&mut self) {
+        self.items
+            .sort_by(|a, b| (a.status == "resolved", &a.path, a.line).cmp(&(b.status == "resolved", &b.path, b.line)));
+    }
+
+    /// Markdown prompt listing every item
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Review feedback: PR #{} {}\n\n", self.pr, self.title);
+        if !self.url.is_empty() {
+            out.push_str(&format!("{}\n\n", self.url));
+        }
+        out.push_str(
+            "Address the unresolved and pending comments below. Line numbers refer to the \
+             current working tree; in diff snippets `+` lines were added and `-` lines removed.\n",
+        );
+        if self.items.is_empty() {
+            out.push_str("\nNo review comments.\n");
+        }
+
+        for (i, item) in self.items.iter().enumerate() {
+            // The commented line, with the snippet's range when it shows more
+            let location = match (item.line, &item.snippet) {
+                (Some(line), Some(s)) if s.start != s.end => {
+                    format!("{}:{} (snippet {}-{})", item.path, line, s.start, s.end)
+                }
+                (Some(line), _) => format!("{}:{}", item.path, line),
+                (None, _) => item.path.clone(),
+            };
+            let side = if item.side == "LEFT" { ", removed code" } else { "" };
+            out.push_str(&format!("\n## {}. {}, {}{}\n\n", i + 1, location, item.status, side));
+
+            if let Some(snippet) = &item.snippet {
+                let lang = if snippet.is_diff { "diff" } else { "" };
+                out.push_str(&format!("```{}\n{}\n```\n\n", lang, snippet.text.trim_end()));
+            }
+            for comment in &item.comments {
+                out.push_str(&format!("**{}:** {}\n\n", comment.author, comment.body.trim()));
+            }
+        }
+        out
+    }
+
+    /// Write `review.md` and `review.json` under `root/.timecop`; returns the markdown path
+    pub fn write(&self, root: &Path) -> Result<PathBuf> {
+        let dir = root.join(EXPORT_DIR);
+        std::fs::create_dir_all(&dir).context("Failed to create export directory")?;
+        let ignore = dir.join(".gitignore");
+        if !ignore.exists() {
+            std::fs::write(&ignore, "*\n").context("Failed to write .gitignore")?;
+        }
+
+        let markdown = dir.join("review.md");
+        std::fs::write(&markdown, self.to_markdown()).context("Failed to write review.md")?;
+        let json = serde_json::to_vec_pretty(self)?;
+        std::fs::write(dir.join("review.json"), json).context("Failed to write review.json")?;
+        Ok(markdown)
+    }
+}
+
+/// Lines of a file diff around `line` (of the old file when `old_side`),
+/// staying within its hunk
+pub fn diff_snippet(diff: &str, line: u32, old_side: bool) -> Option<Snippet> {
+    // (hunk, old line, new line, text) for every line in a hunk
+    let mut rows: Vec<(usize, Option<u32>, Option<u32>, &str)> = Vec::new();
+    let (mut hunk, mut old, mut new) = (0, 0, 0);
+    for text in diff.lines() {
+        if text.starts_with("@@") {
+            let (old_start, new_start) = parse_hunk_header(text)?;
+            hunk += 1;
+            old = old_start as u32;
+            new = new_start as u32;
+            continue;
+        }
+        if hunk == 0 || text.starts_with('\\') {
+            continue;
+        }
+        let (old_num, new_num) = match text.chars().next() {
+            Some('+') => (None, Some(new)),
+            Some('-') => (Some(old), None),
+            _ => (Some(old), Some(new)),
+        };
+        old += old_num.is_some() as u32;
+        new += new_num.is_some() as u32;
+        rows.push((hunk, old_num, new_num, text));
+    }
+
+    let side = |row: &(usize, Option<u32>, Option<u32>, &str)| if old_side { row.1 } else { row.2 };
+    let at = rows.iter().position(|row| side(row) == Some(line))?;
+    let in_hunk: Vec<_> = rows
+        .iter()
+        .enumerate()
+        .filter(|(i, row)| row.0 == rows[at].0 && i.abs_diff(at) <= CONTEXT_LINES)
+        .map(|(_, row)| row)
+        .collect();
+
+    let numbers: Vec<u32> = in_hunk.iter().filter_map(|row| side(row)).collect();
+    Some(Snippet {
+        start: *numbers.first()?,
+        end: *numbers.last()?,
+        text: in_hunk.iter().map(|row| row.3).collect::<Vec<_>>().join("\n"),
+        is_diff: true,
+    })
+}
+
+/// Lines of a file around `line`, for comments outside the diff's hunks
+pub fn content_snippet(content: &str, line: u32) -> Option<Snippet> {
+    let index = (line as usize).checked_sub(1)?;
+    let lines: Vec<&str> = content.lines().collect();
+    if index >= lines.len() {
+        return None;
+    }
+    let first = index.saturating_sub(CONTEXT_LINES);
+    let last = (index + CONTEXT_LINES).min(lines.len() - 1);
+    Some(Snippet {
+        start: first as u32 + 1,
+        end: last as u32 + 1,
+        text: lines[first..=last].join("\n"),
+        is_diff: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/f b/f\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n@@ -20,2 +20,3 @@\n x\n+y\n z\n";
+
+    // --- snippets ---
+
+    #[test]
+    fn diff_snippet_stays_within_the_hunk() {
+        let snippet = diff_snippet(DIFF, 2, false).unwrap();
+        assert_eq!((snippet.start, snippet.end), (1, 3));
+        assert_eq!(snippet.text, " a\n-b\n+B\n c");
+
+        // Removed lines are found by their old line number
+        assert_eq!(diff_snippet(DIFF, 2, true).map(|s| s.start), Some(1));
+        assert_eq!(diff_snippet(DIFF, 21, false).map(|s| (s.start, s.end)), Some((20, 22)));
+        assert_eq!(diff_snippet(DIFF, 10, false), None);
+    }
+
+    #[test]
+    fn content_snippet_clamps_to_the_file() {
+        let snippet = content_snippet("1\n2\n3\n4\n5\n6", 2).unwrap();
+        assert_eq!((snippet.start, snippet.end), (1, 5));
+        assert_eq!(content_snippet("1\n2", 3), None);
+    }
+
+    // --- Feedback ---
+
+    #[test]
+    fn markdown_lists_items_with_snippets_and_comments() {
+        let feedback = Feedback {
+            pr: 7,
+            title: "Add parser".to_string(),
+            url: String::new(),
+            items: vec![FeedbackItem {
+                path: "f".to_string(),
+                line: Some(2),
+                side: "RIGHT".to_string(),
+                status: "unresolved",
+                snippet: diff_snippet(DIFF, 2, false),
+                comments: vec![FeedbackComment { author: "alice".to_string(), body: "Why uppercase?".to_string() }],
+            }],
+        };
+        let markdown = feedback.to_markdown();
+        assert!(markdown.starts_with("# Review feedback: PR #7 Add parser\n"));
+        // The heading names the commented line, not just the snippet's range
+        assert!(markdown.contains("## 1. f:2 (snippet 1-3), unresolved\n\n```diff\n a\n-b\n+B\n c\n```\n"));
+        assert!(markdown.contains("**alice:** Why uppercase?"));
+    }
+
+    #[test]
+    fn markdown_heading_names_the_commented_line() {
+        let item = |line: u32, snippet: Option<Snippet>| FeedbackItem {
+            path: "f".to_string(),
+            line: Some(line),
+            side: "RIGHT".to_string(),
+            status: "pending",
+            snippet,
+            comments: Vec::new(),
+        };
+        let feedback = Feedback {
+            pr: 7,
+            title: String::new(),
+            url: String::new(),
+            items: vec![item(21, diff_snippet(DIFF, 21, false)), item(5, None)],
+        };
+        let markdown = feedback.to_markdown();
+        assert!(markdown.contains("## 1. f:21 (snippet 20-22), pending\n"));
+        assert!(markdown.contains("## 2. f:5, pending\n"));
+    }
+}
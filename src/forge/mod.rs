@@ -0,0 +1,1211 @@
+//! Code hosting services (forges)
+//!
+//! Pull/merge request data shared by every forge, and the `Forge` trait each
+//! hosting service implements. Which one is used is decided from the base
+//! remote's URL (see `detect` and `git::Remotes`).
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::drafts::DraftComment;
+use crate::git::Remotes;
+use crate::github::GitHubClient;
+use crate::gitlab::GitLabClient;
+
+/// Environment variable forcing a forge ("github" or "gitlab")
+pub const FORGE_ENV: &str = "TIMECOP_FORGE";
+
+/// Pull/merge request information
+#[derive(Debug, Clone, Default)]
+pub struct PrInfo {
+    pub number: u64,
+    pub title: String,
+    pub body: String,
+    pub author: String,
+    pub state: String,
+    pub url: String,
+    pub base_branch: String,
+    pub reviews: Vec<Review>,
+    pub comments: Vec<Comment>,
+    pub file_threads: HashMap<String, Vec<ReviewThread>>,
+    /// Login of the authenticated user (for edit/delete of own comments)
+    pub viewer: String,
+    /// CI checks on the PR head
+    pub checks: Vec<CheckRun>,
+    pub draft: bool,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    /// Reviewers asked for a review who haven't given it yet
+    pub requested_reviewers: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Review {
+    pub id: u64,
+    pub author: String,
+    pub state: String,
+    pub body: String,
+    pub submitted_at: String,
+    /// PR head the review was given on (empty if the forge doesn't say)
+    pub commit_sha: String,
+}
+
+/// An entry in the PR conversation timeline
+#[derive(Debug, Clone, Copy)]
+pub enum ConversationItem<'a> {
+    Review(&'a Review),
+    Comment(&'a Comment),
+}
+
+impl ConversationItem<'_> {
+    pub fn timestamp(&self) -> &str {
+        match self {
+            Self::Review(r) => &r.submitted_at,
+            Self::Comment(c) => &c.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Comment {
+    pub id: u64,
+    pub in_reply_to_id: Option<u64>,
+    pub author: String,
+    pub body: String,
+    pub created_at: String,
+    pub line: Option<u32>,
+    pub original_line: Option<u32>,
+    pub side: Option<String>, // "LEFT" or "RIGHT"
+    /// Commit `line` refers to (latest PR head the comment still applies to)
+    pub commit_id: Option<String>,
+    /// Commit the comment was originally written against
+    pub original_commit_id: Option<String>,
+    /// Posted on the PR conversation (issues API) rather than as a review comment
+    pub conversation: bool,
+}
+
+impl Comment {
+    /// Commit and line the comment is anchored to: its current position when
+    /// GitHub could still place it, otherwise where it was originally written
+    pub fn anchor(&self) -> Option<(&str, u32)> {
+        match (self.commit_id.as_deref(), self.line) {
+            (Some(commit), Some(line)) => Some((commit, line)),
+            _ => Some((self.original_commit_id.as_deref()?, self.original_line?)),
+        }
+    }
+}
+
+/// A review conversation anchored to a line: root comment followed by replies
+#[derive(Debug, Clone, Default)]
+pub struct ReviewThread {
+    /// Root comment first, replies in chronological order
+    pub comments: Vec<Comment>,
+    /// GraphQL node ID (needed for resolve/unresolve), if known
+    pub node_id: Option<String>,
+    pub resolved: bool,
+}
+
+impl ReviewThread {
+    pub fn root(&self) -> &Comment {
+        &self.comments[0]
+    }
+
+    pub fn replies(&self) -> &[Comment] {
+        &self.comments[1..]
+    }
+
+    /// ID of the root comment (replies are posted against it)
+    pub fn id(&self) -> u64 {
+        self.root().id
+    }
+}
+
+/// Group flat review comments into threads.
+/// Replies are attached to their root via `in_reply_to_id`; orphans become roots.
+pub fn group_into_threads(comments: Vec<Comment>) -> Vec<ReviewThread> {
+    let ids: HashSet<u64> = comments.iter().map(|c| c.id).collect();
+    let mut threads: Vec<ReviewThread> = Vec::new();
+    let mut replies: Vec<Comment> = Vec::new();
+
+    for comment in comments {
+        match comment.in_reply_to_id {
+            Some(parent) if ids.contains(&parent) => replies.push(comment),
+            _ => threads.push(ReviewThread {
+                comments: vec![comment],
+                ..Default::default()
+            }),
+        }
+    }
+
+    // Oldest first, so a reply to a reply finds its parent already placed
+    replies.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    for reply in replies {
+        let parent = reply.in_reply_to_id.unwrap_or_default();
+        match threads
+            .iter_mut()
+            .find(|t| t.comments.iter().any(|c| c.id == parent))
+        {
+            Some(thread) => thread.comments.push(reply),
+            None => threads.push(ReviewThread {
+                comments: vec![reply],
+                ..Default::default()
+            }),
+        }
+    }
+
+    for thread in &mut threads {
+        // Keep root in place, sort replies by timestamp (ISO 8601 sorts lexically)
+        thread.comments[1..].sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    }
+
+    threads.sort_by(|a, b| a.root().created_at.cmp(&b.root().created_at));
+    threads
+}
+
+impl PrInfo {
+    /// Find a review thread by its root comment ID
+    pub fn find_thread(&self, root_id: u64) -> Option<&ReviewThread> {
+        self.file_threads
+            .values()
+            .flatten()
+            .find(|t| t.id() == root_id)
+    }
+
+    /// Find a review comment (inline or general) by ID
+    pub fn find_comment(&self, id: u64) -> Option<&Comment> {
+        self.file_threads
+            .values()
+            .flatten()
+            .flat_map(|t| t.comments.iter())
+            .chain(self.comments.iter())
+            .find(|c| c.id == id)
+    }
+
+    pub fn find_review(&self, id: u64) -> Option<&Review> {
+        self.reviews.iter().find(|r| r.id == id)
+    }
+
+    /// Conversation comments and review summaries in chronological order
+    pub fn conversation(&self) -> Vec<ConversationItem<'_>> {
+        let mut items: Vec<ConversationItem> = self
+            .reviews
+            .iter()
+            .map(ConversationItem::Review)
+            .chain(self.comments.iter().map(ConversationItem::Comment))
+            .collect();
+        // ISO 8601 sorts lexically; stable sort keeps fetch order for ties
+        items.sort_by(|a, b| a.timestamp().cmp(b.timestamp()));
+        items
+    }
+
+    /// Whether the authenticated user wrote this
+    pub fn is_own(&self, author: &str) -> bool {
+        !self.viewer.is_empty() && self.viewer == author
+    }
+
+    /// Commit the viewer's latest review was given on
+    pub fn viewer_review_commit(&self) -> Option<&str> {
+        self.reviews
+            .iter()
+            .filter(|r| self.is_own(&r.author) && !r.commit_sha.is_empty())
+            .max_by(|a, b| a.submitted_at.cmp(&b.submitted_at))
+            .map(|r| r.commit_sha.as_str())
+    }
+
+    /// Every markdown body in the PR: description, reviews and comments
+    pub fn bodies(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.body.as_str())
+            .chain(self.reviews.iter().map(|r| r.body.as_str()))
+            .chain(self.comments.iter().map(|c| c.body.as_str()))
+            .chain(
+                self.file_threads
+                    .values()
+                    .flatten()
+                    .flat_map(|t| t.comments.iter())
+                    .map(|c| c.body.as_str()),
+            )
+    }
+}
+
+/// Format an ISO 8601 timestamp as "YYYY-MM-DD HH:MM" for display
+pub fn short_timestamp(ts: &str) -> String {
+    ts.get(..16).unwrap_or(ts).replace('T', " ")
+}
+
+/// Status of a single CI check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckStatus {
+    Pending,
+    Success,
+    Failure,
+}
+
+/// A CI check on the PR head: a GitHub check run or a GitLab pipeline job
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: CheckStatus,
+    /// ISO 8601 timestamps, empty until the check starts/finishes
+    pub started_at: String,
+    pub completed_at: String,
+    pub url: String,
+    /// Job whose log can be fetched with `Forge::check_log` (None for external checks)
+    pub job_id: Option<u64>,
+}
+
+impl CheckRun {
+    /// Run time in seconds, once the check has finished
+    pub fn duration(&self) -> Option<i64> {
+        let secs = iso_seconds(&self.completed_at)? - iso_seconds(&self.started_at)?;
+        (secs >= 0).then_some(secs)
+    }
+}
+
+/// Seconds since the Unix epoch for a UTC ISO 8601 timestamp ("2024-01-02T03:04:05Z")
+fn iso_seconds(ts: &str) -> Option<i64> {
+    let num = |range: std::ops::Range<usize>| ts.get(range)?.parse::<i64>().ok();
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+
+    // Days from civil date (proleptic Gregorian calendar)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+    Some(days * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+/// Format a duration in seconds as "42s", "3m 05s" or "1h 02m"
+pub fn format_duration(secs: i64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m {:02}s", s / 60, s % 60),
+        s => format!("{}h {:02}m", s / 3600, s % 3600 / 60),
+    }
+}
+
+/// Most lines of a job log kept for display (the end, where failures are)
+pub const MAX_LOG_LINES: usize = 2000;
+
+/// Make a raw CI job log readable: drop ANSI escapes, per-line timestamps and
+/// `gh run view` "job<TAB>step<TAB>" prefixes, keeping the last `MAX_LOG_LINES`
+pub fn clean_log(raw: &str) -> Vec<String> {
+    let lines: Vec<String> = raw
+        .lines()
+        .map(|line| {
+            let line = strip_ansi(line);
+            let line = match line.splitn(3, '\t').collect::<Vec<_>>().as_slice() {
+                [_, _, rest] => rest.to_string(),
+                _ => line,
+            };
+            strip_log_timestamp(&line).trim_end().to_string()
+        })
+        .collect();
+    let skip = lines.len().saturating_sub(MAX_LOG_LINES);
+    lines.into_iter().skip(skip).collect()
+}
+
+/// Remove ANSI escape sequences; of text overwritten with carriage returns
+/// (progress bars, GitLab section markers) only the last segment is kept
+fn strip_ansi(line: &str) -> String {
+    let line = line.rsplit('\r').find(|seg| !seg.is_empty()).unwrap_or("");
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'[') {
+            chars.next();
+            // CSI: parameters up to a final byte in @..~
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Drop a leading "2024-01-02T03:04:05.1234567Z " timestamp (GitHub Actions logs)
+fn strip_log_timestamp(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    let looks_like_ts = bytes.len() > 20
+        && bytes[..4].iter().all(u8::is_ascii_digit)
+        && bytes[4] == b'-'
+        && bytes[10] == b'T';
+    if !looks_like_ts {
+        return line;
+    }
+    match line.split_once(' ') {
+        Some((ts, rest)) if ts.ends_with('Z') => rest,
+        _ => line,
+    }
+}
+
+/// Summary of a PR for listing
+#[derive(Debug, Clone)]
+pub struct PrSummary {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub branch: String,
+    pub base_branch: String,
+    /// Full ISO 8601 timestamps
+    pub updated_at: String,
+    pub created_at: String,
+    pub review_requested: bool, // true if current user is requested reviewer
+    pub authored_by_viewer: bool,
+    pub draft: bool,
+    pub labels: Vec<String>,
+    /// Changed lines (0 when the forge doesn't report them in listings)
+    pub additions: u64,
+    pub deletions: u64,
+    pub checks: Vec<CheckStatus>,
+    /// Head commit, to notice pushes between polls
+    pub head_sha: String,
+    /// Repository holding the head branch ("owner/repo"); empty when unknown
+    pub head_repo: String,
+    /// Submitted reviews and comments (0 when the forge doesn't count them)
+    pub review_count: u64,
+    pub comment_count: u64,
+}
+
+/// How a PR's commits land on the base branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeMethod {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergeMethod {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Merge => Self::Squash,
+            Self::Squash => Self::Rebase,
+            Self::Rebase => Self::Merge,
+        }
+    }
+
+    /// Lowercase name, also the GitHub API's `merge_method` value
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Merge => "merge",
+            Self::Squash => "squash",
+            Self::Rebase => "rebase",
+        }
+    }
+}
+
+/// Whether a PR can be merged, and what stands in the way
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeStatus {
+    /// Merges cleanly; None while the forge is still computing it
+    pub mergeable: Option<bool>,
+    /// APPROVED, CHANGES_REQUESTED or REVIEW_REQUIRED; None when no review is required
+    pub review_decision: Option<String>,
+    /// Checks branch protection requires, with their status
+    pub required_checks: Vec<(String, CheckStatus)>,
+    /// Unmet requirements; merging is refused while any remain
+    pub blockers: Vec<String>,
+}
+
+impl MergeStatus {
+    /// Lines describing the status for the merge confirmation
+    pub fn summary(&self) -> Vec<String> {
+        let mergeable = match self.mergeable {
+            Some(true) => "yes",
+            Some(false) => "no, has conflicts",
+            None => "still being checked",
+        };
+        let reviews = match self.review_decision.as_deref() {
+            Some("APPROVED") => "approved",
+            Some("CHANGES_REQUESTED") => "changes requested",
+            Some("REVIEW_REQUIRED") => "approval required",
+            Some(other) => other,
+            None => "not required",
+        };
+        let passed = self
+            .required_checks
+            .iter()
+            .filter(|(_, status)| *status == CheckStatus::Success)
+            .count();
+        let checks = if self.required_checks.is_empty() {
+            "none required".to_string()
+        } else {
+            format!("{}/{} passed", passed, self.required_checks.len())
+        };
+
+        let mut lines = vec![
+            format!("Mergeable: {}", mergeable),
+            format!("Reviews: {}", reviews),
+            format!("Required checks: {}", checks),
+        ];
+        lines.extend(self.blockers.iter().map(|b| format!("✗ {}", b)));
+        lines
+    }
+}
+
+/// Editable lists of people and labels on a PR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataKind {
+    Reviewers,
+    Assignees,
+    Labels,
+}
+
+impl MetadataKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Reviewers => "Reviewers",
+            Self::Assignees => "Assignees",
+            Self::Labels => "Labels",
+        }
+    }
+
+    /// The list on a PR
+    pub fn current(self, pr: &PrInfo) -> &[String] {
+        match self {
+            Self::Reviewers => &pr.requested_reviewers,
+            Self::Assignees => &pr.assignees,
+            Self::Labels => &pr.labels,
+        }
+    }
+}
+
+/// A head a PR had, e.g. before or after a force-push
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadRevision {
+    pub sha: String,
+    /// When the head was pushed, as the forge reports it
+    pub at: String,
+    /// What the revision is, e.g. "force-pushed" or "version 2"
+    pub note: String,
+}
+
+/// A PR to open from a pushed branch
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NewPr {
+    pub head: String,
+    pub base: String,
+    pub title: String,
+    pub body: String,
+    pub draft: bool,
+    /// Usernames; `org/team` names a team on GitHub
+    pub reviewers: Vec<String>,
+    /// Repository path holding `head` when it isn't the base repository (a fork)
+    pub head_repo: Option<String>,
+}
+
+/// Upper bound on PRs fetched for the list (pages of 50)
+pub const MAX_LISTED_PRS: usize = 500;
+
+/// A code hosting service with pull/merge requests.
+///
+/// Numbers are the PR number on GitHub and the MR iid on GitLab. Comment and
+/// thread ids are whatever the forge returned in `PrInfo`.
+pub trait Forge: Send {
+    /// Display name, e.g. for status messages
+    fn name(&self) -> &'static str;
+
+    /// Whether the forge can be reached (credentials or CLI present)
+    fn is_available(&mut self) -> bool;
+
+    /// Two short lines telling the user how to set up access when unavailable
+    fn setup_hint(&self) -> [&'static str; 2];
+
+    /// Open PRs for the current repository
+    fn list_open_prs(&mut self) -> Result<Vec<PrSummary>>;
+
+    /// Full PR details with reviews and comments
+    fn get_pr_by_number(&mut self, pr_number: u64) -> Result<Option<PrInfo>>;
+
+    /// Check out a PR branch, returns the PR's base branch name
+    fn checkout_pr(&self, pr_number: u64) -> Result<String>;
+
+    /// Ref on the remote holding the PR head, fetchable without checking it out
+    fn head_ref(&self, pr_number: u64) -> String;
+
+    fn open_pr_in_browser(&self, pr_number: u64) -> Result<()>;
+
+    /// Submit a verdict with a summary body and pending line comments.
+    /// `event` is APPROVE, REQUEST_CHANGES or COMMENT.
+    fn submit_review(
+        &self,
+        pr_number: u64,
+        event: &str,
+        body: &str,
+        comments: &[DraftComment],
+    ) -> Result<()>;
+
+    fn reply_to_thread(&self, pr_number: u64, thread: &ReviewThread, body: &str) -> Result<()>;
+
+    /// Resolve or unresolve a thread by its `node_id`
+    fn set_thread_resolved(&self, pr_number: u64, thread_id: &str, resolved: bool) -> Result<()>;
+
+    /// Replace the body of a review or conversation comment
+    fn edit_comment(
+        &self,
+        pr_number: u64,
+        comment_id: u64,
+        conversation: bool,
+        body: &str,
+    ) -> Result<()>;
+
+    fn delete_comment(&self, pr_number: u64, comment_id: u64, conversation: bool) -> Result<()>;
+
+    /// Post a comment on the PR conversation
+    fn add_conversation_comment(&self, pr_number: u64, body: &str) -> Result<()>;
+
+    /// Replace the summary body of a submitted review
+    fn edit_review(&self, pr_number: u64, review_id: u64, body: &str) -> Result<()>;
+
+    /// Log of a CI job (`CheckRun::job_id`), cleaned up for display
+    fn check_log(&self, job_id: u64) -> Result<Vec<String>>;
+
+    /// Mergeability, review decision and required checks
+    fn merge_status(&self, pr_number: u64) -> Result<MergeStatus>;
+
+    /// Merge the PR, optionally deleting its branch afterwards
+    fn merge_pr(&self, pr_number: u64, method: MergeMethod, delete_branch: bool) -> Result<()>;
+
+    /// Open a PR and request its reviewers, returns the new PR number
+    fn create_pr(&self, pr: &NewPr) -> Result<u64>;
+
+    /// Choices for a metadata list: the repository's collaborators or labels
+    fn metadata_options(&self, kind: MetadataKind) -> Result<Vec<String>>;
+
+    /// Add and remove entries of a metadata list (request or withdraw
+    /// reviews, assign, label)
+    fn update_metadata(
+        &self,
+        pr_number: u64,
+        kind: MetadataKind,
+        add: &[String],
+        remove: &[String],
+    ) -> Result<()>;
+
+    /// Convert to a draft, or mark ready for review
+    fn set_draft(&self, pr_number: u64, draft: bool) -> Result<()>;
+
+    /// Files the user marked as viewed on the forge
+    fn viewed_files(&self, pr_number: u64) -> Result<Vec<String>>;
+
+    /// Mark a file as viewed on the forge, or unmark it
+    fn set_file_viewed(&self, pr_number: u64, path: &str, viewed: bool) -> Result<()>;
+
+    /// Heads the PR had over time, newest first
+    fn head_revisions(&self, pr_number: u64) -> Result<Vec<HeadRevision>>;
+
+    fn approve_pr(&self, pr_number: u64) -> Result<()> {
+        self.submit_review(pr_number, "APPROVE", "", &[])
+            .context("Failed to approve PR")
+    }
+
+    fn request_changes(&self, pr_number: u64, body: &str) -> Result<()> {
+        self.submit_review(pr_number, "REQUEST_CHANGES", body, &[])
+            .context("Failed to request changes")
+    }
+
+    /// Add a general review comment
+    fn comment_pr(&self, pr_number: u64, body: &str) -> Result<()> {
+        self.submit_review(pr_number, "COMMENT", body, &[])
+            .context("Failed to comment")
+    }
+}
+
+/// Which forge hosts a remote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+}
+
+impl ForgeKind {
+    /// Pick the forge for a remote host. `TIMECOP_FORGE` wins; otherwise
+    /// hosts named like GitLab (or matching `TIMECOP_GITLAB_URL`) are GitLab
+    /// and everything else is treated as GitHub.
+    fn for_host(host: &str, forced: Option<&str>, gitlab_url: Option<&str>) -> Self {
+        match forced.map(|f| f.to_ascii_lowercase()).as_deref() {
+            Some("gitlab") => return ForgeKind::GitLab,
+            Some("github") => return ForgeKind::GitHub,
+            _ => {}
+        }
+        let gitlab_host = gitlab_url
+            .and_then(|url| url.split_once("://").map(|(_, rest)| rest).or(Some(url)))
+            .and_then(|rest| rest.split(['/', ':']).next());
+        if host.contains("gitlab") || (!host.is_empty() && gitlab_host == Some(host)) {
+            ForgeKind::GitLab
+        } else {
+            ForgeKind::GitHub
+        }
+    }
+}
+
+/// Forge client for the repository at `repo`
+pub fn detect(repo: &Path) -> Box<dyn Forge> {
+    let host = base_remote(repo).map(|r| r.host).unwrap_or_default();
+    let forced = std::env::var(FORGE_ENV).ok();
+    let gitlab_url = std::env::var(crate::gitlab::GITLAB_URL_ENV).ok();
+    match ForgeKind::for_host(&host, forced.as_deref(), gitlab_url.as_deref()) {
+        ForgeKind::GitHub => Box::new(GitHubClient::new(repo)),
+        ForgeKind::GitLab => Box::new(GitLabClient::new(repo)),
+    }
+}
+
+/// Host and repository path of a git remote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteUrl {
+    pub host: String,
+    /// Full repository path without `.git`, e.g. "group/subgroup/repo"
+    pub path: String,
+}
+
+impl RemoteUrl {
+    /// Parse an HTTPS, SSH or scp-style git remote URL
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (host, path) = if let Some((_, rest)) = url.split_once("://") {
+            // https://host/owner/repo, ssh://git@host:port/owner/repo
+            let (authority, path) = rest.split_once('/')?;
+            let host = authority
+                .rsplit_once('@')
+                .map(|(_, h)| h)
+                .unwrap_or(authority);
+            (host.split(':').next()?, path)
+        } else {
+            // git@host:owner/repo
+            let rest = url.rsplit_once('@').map(|(_, r)| r).unwrap_or(url);
+            rest.split_once(':')?
+        };
+
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        if host.is_empty() || !path.contains('/') {
+            return None;
+        }
+        Some(Self {
+            host: host.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    /// Last two path segments: (owner, repo)
+    pub fn owner_repo(&self) -> Option<(&str, &str)> {
+        let (rest, repo) = self.path.rsplit_once('/')?;
+        Some((rest.rsplit('/').next()?, repo))
+    }
+}
+
+/// The remote PRs live on, for the repository at `repo`: `upstream` in a
+/// fork workflow, otherwise `origin` (see `git::Remotes`)
+pub fn base_remote(repo: &Path) -> Option<RemoteUrl> {
+    remote_url(repo, |remotes| &remotes.base)
+}
+
+/// The remote branches are pushed to; the fork in a fork workflow
+pub fn push_remote(repo: &Path) -> Option<RemoteUrl> {
+    remote_url(repo, |remotes| &remotes.push)
+}
+
+fn remote_url(repo: &Path, pick: impl FnOnce(&Remotes) -> &String) -> Option<RemoteUrl> {
+    let repo = git2::Repository::discover(repo).ok()?;
+    let remotes = Remotes::resolve(&repo);
+    let remote = repo.find_remote(pick(&remotes)).ok()?;
+    RemoteUrl::parse(remote.url()?)
+}
+
+/// Open a URL with the platform's default handler
+pub fn open_url(url: &str) -> Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    std::process::Command::new(opener)
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .context("Failed to open browser")?;
+    Ok(())
+}
+
+/// Read a JSON API response, turning HTTP errors into "<service> API returned <status>: <message>"
+pub fn read_json_response(
+    response: std::result::Result<ureq::Response, ureq::Error>,
+    service: &str,
+) -> Result<serde_json::Value> {
+    let text = read_text_response(response, service)?;
+    if text.trim().is_empty() {
+        Ok(serde_json::Value::Null)
+    } else {
+        serde_json::from_str(&text).context("Failed to parse API response")
+    }
+}
+
+/// Read a plain text API response (e.g. a job log), with errors as in `read_json_response`
+pub fn read_text_response(
+    response: std::result::Result<ureq::Response, ureq::Error>,
+    service: &str,
+) -> Result<String> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .context("Failed to read API response"),
+        Err(ureq::Error::Status(code, response)) => {
+            let text = response.into_string().unwrap_or_default();
+            anyhow::bail!(
+                "{} API returned {}: {}",
+                service,
+                code,
+                error_message(&text)
+            )
+        }
+        Err(e) => anyhow::bail!("{} API request failed: {}", service, e),
+    }
+}
+
+/// The `message` (or `error`) of an API error response, or the raw body
+fn error_message(body: &str) -> String {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+        return body.trim().to_string();
+    };
+    match json.get("message").or_else(|| json.get("error")) {
+        Some(serde_json::Value::String(message)) => message.clone(),
+        Some(other) => other.to_string(),
+        None => body.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(id: u64, reply_to: Option<u64>, created_at: &str) -> Comment {
+        Comment {
+            id,
+            in_reply_to_id: reply_to,
+            author: format!("user{}", id),
+            body: format!("body {}", id),
+            created_at: created_at.to_string(),
+            line: Some(10),
+            ..Default::default()
+        }
+    }
+
+    // --- group_into_threads ---
+
+    #[test]
+    fn group_into_threads_attaches_replies_to_root() {
+        let threads = group_into_threads(vec![
+            comment(1, None, "2024-01-01T10:00:00Z"),
+            comment(2, Some(1), "2024-01-01T11:00:00Z"),
+            comment(3, None, "2024-01-01T12:00:00Z"),
+        ]);
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].id(), 1);
+        assert_eq!(threads[0].replies().len(), 1);
+        assert_eq!(threads[0].replies()[0].id, 2);
+        assert!(threads[1].replies().is_empty());
+    }
+
+    #[test]
+    fn group_into_threads_sorts_replies_chronologically() {
+        let threads = group_into_threads(vec![
+            comment(1, None, "2024-01-01T10:00:00Z"),
+            comment(3, Some(1), "2024-01-03T10:00:00Z"),
+            comment(2, Some(1), "2024-01-02T10:00:00Z"),
+        ]);
+        let ids: Vec<u64> = threads[0].comments.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn group_into_threads_orphan_reply_becomes_root() {
+        let threads = group_into_threads(vec![comment(5, Some(99), "2024-01-01T10:00:00Z")]);
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].id(), 5);
+    }
+
+    #[test]
+    fn group_into_threads_keeps_replies_to_replies() {
+        // Listed before the reply it answers
+        let threads = group_into_threads(vec![
+            comment(3, Some(2), "2024-01-03T10:00:00Z"),
+            comment(1, None, "2024-01-01T10:00:00Z"),
+            comment(2, Some(1), "2024-01-02T10:00:00Z"),
+        ]);
+        assert_eq!(threads.len(), 1);
+        let ids: Vec<u64> = threads[0].comments.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        // A reply whose parent never gets placed is kept as its own thread
+        let threads = group_into_threads(vec![
+            comment(1, Some(2), "2024-01-01T10:00:00Z"),
+            comment(2, Some(1), "2024-01-02T10:00:00Z"),
+        ]);
+        assert_eq!(threads.iter().map(|t| t.comments.len()).sum::<usize>(), 2);
+    }
+
+    // --- short_timestamp ---
+
+    #[test]
+    fn short_timestamp_formats_iso() {
+        assert_eq!(short_timestamp("2024-03-05T14:22:09Z"), "2024-03-05 14:22");
+    }
+
+    #[test]
+    fn short_timestamp_passes_through_short_input() {
+        assert_eq!(short_timestamp("2024-03-05"), "2024-03-05");
+    }
+
+    // --- Comment::anchor ---
+
+    #[test]
+    fn anchor_prefers_current_position() {
+        let c = Comment {
+            line: Some(20),
+            commit_id: Some("head".to_string()),
+            original_line: Some(12),
+            original_commit_id: Some("old".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(c.anchor(), Some(("head", 20)));
+    }
+
+    #[test]
+    fn anchor_falls_back_to_original_when_outdated() {
+        let c = Comment {
+            line: None,
+            commit_id: Some("head".to_string()),
+            original_line: Some(12),
+            original_commit_id: Some("old".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(c.anchor(), Some(("old", 12)));
+    }
+
+    // --- PrInfo lookups ---
+
+    #[test]
+    fn find_comment_searches_threads_and_general_comments() {
+        let mut pr = PrInfo {
+            viewer: "user2".to_string(),
+            comments: vec![comment(9, None, "2024-01-02T00:00:00Z")],
+            ..Default::default()
+        };
+        pr.file_threads.insert(
+            "src/main.rs".to_string(),
+            group_into_threads(vec![
+                comment(1, None, "2024-01-01T10:00:00Z"),
+                comment(2, Some(1), "2024-01-01T11:00:00Z"),
+            ]),
+        );
+
+        assert_eq!(pr.find_comment(2).map(|c| c.id), Some(2));
+        assert_eq!(pr.find_comment(9).map(|c| c.id), Some(9));
+        assert!(pr.find_comment(5).is_none());
+        assert!(pr.is_own("user2"));
+        assert!(!pr.is_own("user1"));
+    }
+
+    #[test]
+    fn is_own_requires_known_viewer() {
+        let pr = PrInfo::default();
+        assert!(!pr.is_own(""));
+    }
+
+    // --- PrInfo::conversation ---
+
+    #[test]
+    fn conversation_interleaves_reviews_and_comments_by_time() {
+        let pr = PrInfo {
+            reviews: vec![Review {
+                id: 7,
+                author: "reviewer".to_string(),
+                state: "APPROVED".to_string(),
+                body: String::new(),
+                submitted_at: "2024-01-01T12:00:00Z".to_string(),
+                commit_sha: String::new(),
+            }],
+            comments: vec![
+                comment(1, None, "2024-01-01T10:00:00Z"),
+                comment(2, None, "2024-01-01T14:00:00Z"),
+            ],
+            ..Default::default()
+        };
+
+        let order: Vec<String> = pr
+            .conversation()
+            .iter()
+            .map(|item| match item {
+                ConversationItem::Review(r) => format!("review {}", r.id),
+                ConversationItem::Comment(c) => format!("comment {}", c.id),
+            })
+            .collect();
+        assert_eq!(order, vec!["comment 1", "review 7", "comment 2"]);
+    }
+
+    #[test]
+    fn viewer_review_commit_is_latest_own_review() {
+        let review = |id, author: &str, at: &str, sha: &str| Review {
+            id,
+            author: author.to_string(),
+            state: "COMMENTED".to_string(),
+            body: String::new(),
+            submitted_at: at.to_string(),
+            commit_sha: sha.to_string(),
+        };
+        let pr = PrInfo {
+            viewer: "me".to_string(),
+            reviews: vec![
+                review(1, "me", "2024-01-02T00:00:00Z", "bbb"),
+                review(2, "me", "2024-01-01T00:00:00Z", "aaa"),
+                review(3, "other", "2024-01-03T00:00:00Z", "ccc"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(pr.viewer_review_commit(), Some("bbb"));
+        assert_eq!(PrInfo::default().viewer_review_commit(), None);
+    }
+
+    // --- RemoteUrl ---
+
+    #[test]
+    fn parse_remote_urls() {
+        let expected = Some(RemoteUrl {
+            host: "github.com".to_string(),
+            path: "octo/cat".to_string(),
+        });
+        assert_eq!(RemoteUrl::parse("git@github.com:octo/cat.git"), expected);
+        assert_eq!(RemoteUrl::parse("https://github.com/octo/cat"), expected);
+        assert_eq!(
+            RemoteUrl::parse("https://user@github.com/octo/cat.git/"),
+            expected
+        );
+        assert_eq!(
+            RemoteUrl::parse("ssh://git@github.com:22/octo/cat.git"),
+            expected
+        );
+        assert_eq!(RemoteUrl::parse("/local/path"), None);
+    }
+
+    #[test]
+    fn remote_url_keeps_nested_groups() {
+        let remote = RemoteUrl::parse("git@gitlab.corp:team/backend/api.git").unwrap();
+        assert_eq!(remote.path, "team/backend/api");
+        assert_eq!(remote.owner_repo(), Some(("backend", "api")));
+    }
+
+    // --- ForgeKind ---
+
+    #[test]
+    fn forge_kind_from_host() {
+        assert_eq!(
+            ForgeKind::for_host("github.com", None, None),
+            ForgeKind::GitHub
+        );
+        assert_eq!(
+            ForgeKind::for_host("gitlab.com", None, None),
+            ForgeKind::GitLab
+        );
+        assert_eq!(
+            ForgeKind::for_host("git.corp", None, Some("https://git.corp")),
+            ForgeKind::GitLab
+        );
+        assert_eq!(
+            ForgeKind::for_host("git.corp", Some("GitLab"), None),
+            ForgeKind::GitLab
+        );
+        assert_eq!(
+            ForgeKind::for_host("gitlab.com", Some("github"), None),
+            ForgeKind::GitHub
+        );
+    }
+
+    // --- CheckRun ---
+
+    fn check(started_at: &str, completed_at: &str) -> CheckRun {
+        CheckRun {
+            name: "build".to_string(),
+            status: CheckStatus::Success,
+            started_at: started_at.to_string(),
+            completed_at: completed_at.to_string(),
+            url: String::new(),
+            job_id: None,
+        }
+    }
+
+    #[test]
+    fn check_duration_spans_days_and_fractions() {
+        assert_eq!(
+            check("2024-02-28T23:59:30Z", "2024-03-01T00:00:10Z").duration(),
+            Some(86400 + 40)
+        );
+        assert_eq!(
+            check("2024-01-01T10:00:00.120Z", "2024-01-01T10:03:05.900Z").duration(),
+            Some(185)
+        );
+    }
+
+    #[test]
+    fn check_duration_needs_both_timestamps() {
+        assert_eq!(check("2024-01-01T10:00:00Z", "").duration(), None);
+        assert_eq!(check("", "").duration(), None);
+    }
+
+    #[test]
+    fn format_duration_picks_units() {
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(185), "3m 05s");
+        assert_eq!(format_duration(3720), "1h 02m");
+    }
+
+    // --- MergeStatus ---
+
+    #[test]
+    fn merge_status_summary_lists_requirements_and_blockers() {
+        let status = MergeStatus {
+            mergeable: Some(true),
+            review_decision: Some("REVIEW_REQUIRED".to_string()),
+            required_checks: vec![
+                ("test".to_string(), CheckStatus::Success),
+                ("lint".to_string(), CheckStatus::Failure),
+            ],
+            blockers: vec!["Approving review required".to_string()],
+        };
+        assert_eq!(
+            status.summary(),
+            vec![
+                "Mergeable: yes",
+                "Reviews: approval required",
+                "Required checks: 1/2 passed",
+                "✗ Approving review required",
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_method_cycles() {
+        let method = MergeMethod::default();
+        assert_eq!(method.label(), "merge");
+        assert_eq!(method.next().label(), "squash");
+        assert_eq!(method.next().next().next(), MergeMethod::Merge);
+    }
+
+    // --- clean_log ---
+
+    #[test]
+    fn clean_log_strips_timestamps_colors_and_gh_prefixes() {
+        let raw = "2024-01-01T10:00:00.1234567Z \u{1b}[31merror\u{1b}[0m: boom\n\
+                   test\tRun cargo test\t2024-01-01T10:00:01.0000000Z src/lib.rs:3:5\n\
+                   plain line";
+        assert_eq!(
+            clean_log(raw),
+            vec!["error: boom", "src/lib.rs:3:5", "plain line"]
+        );
+    }
+
+    #[test]
+    fn clean_log_keeps_last_segment_of_gitlab_sections() {
+        let raw = "\u{1b}[0Ksection_start:1700000000:build\r\u{1b}[0KBuilding\n\u{1b}[0Ksection_end:1700000001:build\r\u{1b}[0K";
+        assert_eq!(clean_log(raw), vec!["Building", ""]);
+    }
+
+    #[test]
+    fn clean_log_keeps_the_tail() {
+        let raw: String = (0..MAX_LOG_LINES + 5)
+            .map(|i| format!("line {}\n", i))
+            .collect();
+        let lines = clean_log(&raw);
+        assert_eq!(lines.len(), MAX_LOG_LINES);
+        assert_eq!(lines[0], "line 5");
+    }
+
+    // --- error_message ---
+
+    #[test]
+    fn error_message_reads_api_errors() {
+        assert_eq!(error_message(r#"{"message": "Not Found"}"#), "Not Found");
+        assert_eq!(
+            error_message(r#"{"error": "invalid_token"}"#),
+            "invalid_token"
+        );
+        assert_eq!(error_message("Bad Gateway\n"), "Bad Gateway");
+    }
+}
+
+/// Minimal HTTP server for exercising API clients in tests
+#[cfg(test)]
+pub mod test_server {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// Serve a single HTTP response; returns the base URL and a handle yielding the raw request
+    pub fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let response = response(status, "", body);
+        let handle = thread::spawn(move || respond(&listener, &response));
+        (url, handle)
+    }
+
+    /// Serve one response per request, in order, as (status, extra header lines, body);
+    /// returns the base URL and a handle yielding the raw requests
+    pub fn serve(responses: &[(&str, &str, &str)]) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let responses: Vec<String> = responses
+            .iter()
+            .map(|(status, headers, body)| response(status, headers, body))
+            .collect();
+        let handle =
+            thread::spawn(move || responses.iter().map(|r| respond(&listener, r)).collect());
+        (url, handle)
+    }
+
+    fn response(status: &str, headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            headers,
+            body
+        )
+    }
+
+    /// Answer one connection with `response`, returning the raw request
+    fn respond(listener: &TcpListener, response: &str) -> String {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request = String::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                content_length = len.trim().parse().unwrap();
+            }
+            request.push_str(&line);
+            if line == "\r\n" || line.is_empty() {
+                break;
+            }
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        request.push_str(&String::from_utf8_lossy(&body));
+        reader.get_mut().write_all(response.as_bytes()).unwrap();
+        request
+    }
+}
@@ -0,0 +1,134 @@
+//! Working-tree checkpoints
+//!
+//! Agents edit for a long time without committing, so the wip position only
+//! shows where they ended up. On each debounced change the working tree
+//! (tracked and untracked files, minus ignored ones) is written as a commit on
+//! top of HEAD and kept alive by `refs/timecop/checkpoints/<branch>/<n>`.
+//! Neither HEAD nor the index is touched: the tree is built in a private copy
+//! of the index that is never written back.
+//!
+//! Only checkpoints on top of the current HEAD are shown; those taken on an
+//! earlier HEAD (since committed or thrown away) are pruned, as are all but
+//! the newest `limit` of a branch.
+
+use anyhow::Result;
+use git2::{IndexAddOption, Oid, Repository};
+
+const REF_PREFIX: &str = "refs/timecop/checkpoints/";
+
+/// Checkpoints of `branch` taken on top of `head`, newest first
+pub fn list(repo: &Repository, branch: &str, head: Oid) -> Vec<Oid> {
+    branch_refs(repo, branch)
+        .into_iter()
+        .rev()
+        .map(|(_, oid)| oid)
+        .filter(|&oid| parent(repo, oid) == Some(head))
+        .collect()
+}
+
+/// Snapshot the working tree unless it matches HEAD or the latest
+/// checkpoint, then prune. Returns whether a checkpoint was taken.
+pub fn take(repo: &Repository, branch: &str, head: Oid, limit: usize) -> Result<bool> {
+    // A second handle has its own in-memory index to stage everything into
+    let scratch = Repository::open(repo.path())?;
+    let mut index = scratch.index()?;
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"], None)?;
+    let tree_id = index.write_tree()?;
+
+    let head_commit = repo.find_commit(head)?;
+    let latest_tree = match list(repo, branch, head).first() {
+        Some(&oid) => repo.find_commit(oid)?.tree_id(),
+        None => head_commit.tree_id(),
+    };
+    let taken = tree_id != latest_tree;
+
+    if taken {
+        let signature = repo
+            .signature()
+            .or_else(|_| git2::Signature::now("timecop", "timecop@localhost"))?;
+        let tree = repo.find_tree(tree_id)?;
+        let oid = repo.commit(None, &signature, &signature, "timecop checkpoint", &tree, &[&head_commit])?;
+        let seq = branch_refs(repo, branch).last().map_or(1, |(seq, _)| seq + 1);
+        repo.reference(&ref_name(branch, seq), oid, false, "timecop: checkpoint")?;
+    }
+
+    prune(repo, branch, head, limit.max(1));
+    Ok(taken)
+}
+
+/// Drop checkpoints taken on another HEAD and all but the newest `limit`
+fn prune(repo: &Repository, branch: &str, head: Oid, limit: usize) {
+    let mut kept = 0;
+    for (seq, oid) in branch_refs(repo, branch).into_iter().rev() {
+        if parent(repo, oid) == Some(head) && kept < limit {
+            kept += 1;
+            continue;
+        }
+        if let Ok(mut reference) = repo.find_reference(&ref_name(branch, seq)) {
+            let _ = reference.delete();
+        }
+    }
+}
+
+/// Checkpoint refs of `branch`, oldest first, as (sequence number, commit)
+fn branch_refs(repo: &Repository, branch: &str) -> Vec<(u64, Oid)> {
+    let prefix = format!("{}{}/", REF_PREFIX, branch);
+    let Ok(references) = repo.references_glob(&format!("{}*", prefix)) else {
+        return Vec::new();
+    };
+    // Names with more slashes belong to branches nested under this one
+    let mut refs: Vec<(u64, Oid)> = references
+        .flatten()
+        .filter_map(|r| {
+            let seq = r.name()?.strip_prefix(&prefix)?.parse().ok()?;
+            Some((seq, r.target()?))
+        })
+        .collect();
+    refs.sort();
+    refs
+}
+
+fn ref_name(branch: &str, seq: u64) -> String {
+    format!("{}{}/{}", REF_PREFIX, branch, seq)
+}
+
+fn parent(repo: &Repository, oid: Oid) -> Option<Oid> {
+    repo.find_commit(oid).ok()?.parent_id(0).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestRepo;
+
+    #[test]
+    fn takes_dedups_and_prunes_checkpoints() {
+        let test = TestRepo::new("checkpoints");
+        let (repo, dir) = (&test.repo, &test.dir);
+        let head = test.commit(None, "init", &[("a.txt", "a\n")], Some("refs/heads/main"));
+        test.checkout("refs/heads/main");
+
+        // A clean tree is no checkpoint
+        assert!(!take(repo, "main", head, 2).unwrap());
+
+        std::fs::write(dir.join("a.txt"), "a\nb\n").unwrap();
+        assert!(take(repo, "main", head, 2).unwrap());
+        assert!(!take(repo, "main", head, 2).unwrap());
+        std::fs::write(dir.join("new.txt"), "new\n").unwrap();
+        assert!(take(repo, "main", head, 2).unwrap());
+        std::fs::write(dir.join("a.txt"), "a\nb\nc\n").unwrap();
+        assert!(take(repo, "main", head, 2).unwrap());
+
+        // Capped at two, newest first, with the untracked file included
+        let checkpoints = list(repo, "main", head);
+        assert_eq!(checkpoints.len(), 2);
+        let newest = repo.find_commit(checkpoints[0]).unwrap().tree().unwrap();
+        assert!(newest.get_name("new.txt").is_some());
+        assert_eq!(branch_refs(repo, "main").len(), 2);
+
+        // The index and HEAD are untouched
+        assert_eq!(repo.index().unwrap().len(), 1);
+        assert_eq!(repo.head().unwrap().target(), Some(head));
+    }
+}
@@ -3,14 +3,26 @@ use git2::{DiffOptions, Repository, StatusOptions};
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+use super::checkpoints;
+use super::remote::Remotes;
 use super::types::*;
 
 /// Git client using libgit2 for native performance
 pub struct GitClient {
     repo: Repository,
     path: PathBuf,
+    remotes: Remotes,
     base_branch: Option<String>,
     cached_merge_base: Option<git2::Oid>,
+    /// PR head being reviewed in place: stands in for HEAD and the working
+    /// tree, which are left untouched
+    review_head: Option<git2::Oid>,
+    /// Head of the PR when the user last reviewed it, and whether that
+    /// commit is available locally
+    reviewed_commit: Option<git2::Oid>,
+    reviewed_available: bool,
+    /// Working-tree checkpoints on top of HEAD, newest first
+    checkpoints: Vec<git2::Oid>,
 }
 
 impl GitClient {
@@ -19,11 +31,17 @@ impl GitClient {
         let path = path.as_ref().to_path_buf();
         let repo = Repository::open(&path).context("Failed to open git repository")?;
 
+        let remotes = Remotes::resolve(&repo);
         let mut client = Self {
             repo,
             path,
+            remotes,
             base_branch: None,
             cached_merge_base: None,
+            review_head: None,
+            reviewed_commit: None,
+            reviewed_available: false,
+            checkpoints: Vec::new(),
         };
         client.base_branch = client.detect_base_branch();
         client.fetch_base_branch();
@@ -40,10 +58,78 @@ impl GitClient {
         Ok(branch)
     }
 
+    /// Branch name, or the commit id when HEAD is detached
+    pub fn head_name(&self) -> Result<String> {
+        let head = self.repo.head().context("Failed to get HEAD")?;
+        match head.shorthand() {
+            Some(name) if head.is_branch() => Ok(name.to_string()),
+            _ => Ok(head.peel_to_commit()?.id().to_string()),
+        }
+    }
+
+    /// Check out a local branch (or commit id, detached). Fails rather than
+    /// overwrite local changes.
+    pub fn switch_branch(&mut self, name: &str) -> Result<()> {
+        let branch_ref = format!("refs/heads/{}", name);
+        let target = self
+            .repo
+            .revparse_single(if self.repo.find_reference(&branch_ref).is_ok() { &branch_ref } else { name })
+            .with_context(|| format!("Failed to find {}", name))?;
+        self.repo
+            .checkout_tree(&target, Some(git2::build::CheckoutBuilder::new().safe()))
+            .with_context(|| format!("Failed to check out {}", name))?;
+        if self.repo.find_reference(&branch_ref).is_ok() {
+            self.repo.set_head(&branch_ref)?;
+        } else {
+            self.repo.set_head_detached(target.peel_to_commit()?.id())?;
+        }
+        self.cached_merge_base = None;
+        Ok(())
+    }
+
+    /// Stash staged, unstaged and untracked changes; returns the stash commit
+    pub fn stash_all(&mut self, message: &str) -> Result<git2::Oid> {
+        let signature = self
+            .repo
+            .signature()
+            .or_else(|_| git2::Signature::now("timecop", "timecop@localhost"))?;
+        self.repo
+            .stash_save(&signature, message, Some(git2::StashFlags::INCLUDE_UNTRACKED))
+            .context("Failed to stash changes")
+    }
+
+    /// Apply and drop the stash entry with the given commit, restoring the
+    /// index too. Returns false if the entry is gone. On conflicts the entry
+    /// is kept and an error returned.
+    pub fn pop_stash(&mut self, stash: git2::Oid) -> Result<bool> {
+        let mut index = None;
+        self.repo.stash_foreach(|i, _, oid| {
+            if *oid == stash {
+                index = Some(i);
+            }
+            index.is_none()
+        })?;
+        let Some(index) = index else {
+            return Ok(false);
+        };
+
+        let mut opts = git2::StashApplyOptions::new();
+        opts.reinstantiate_index();
+        self.repo
+            .stash_pop(index, Some(&mut opts))
+            .with_context(|| format!("Stash doesn't apply cleanly, kept as stash@{{{}}}", index))?;
+        Ok(true)
+    }
+
+    /// Remotes PRs and pushes go to
+    pub fn remotes(&self) -> &Remotes {
+        &self.remotes
+    }
+
     /// Set the base branch explicitly (e.g. from GitHub PR metadata)
     /// Fetches the remote ref to ensure it's up to date
     pub fn set_base_branch(&mut self, branch: &str) {
-        let remote_name = format!("origin/{}", branch);
+        let remote_name = format!("{}/{}", self.remotes.base, branch);
         if self.repo.find_reference(&format!("refs/remotes/{}", remote_name)).is_ok() {
             self.base_branch = Some(remote_name);
         } else {
@@ -60,6 +146,338 @@ impl GitClient {
         self.fetch_base_branch();
     }
 
+    /// Base branch as named on the forge, without the remote prefix
+    pub fn base_branch_name(&self) -> Option<&str> {
+        let base = self.base_branch.as_deref()?;
+        Some(self.strip_base_remote(base).unwrap_or(base))
+    }
+
+    /// Branch name of a `<base remote>/<branch>` ref name
+    fn strip_base_remote<'a>(&self, name: &'a str) -> Option<&'a str> {
+        name.strip_prefix(self.remotes.base.as_str())?.strip_prefix('/')
+    }
+
+    /// Branches on the base remote (as last fetched), e.g. to pick a PR base from
+    pub fn remote_branches(&self) -> Vec<String> {
+        let Ok(branches) = self.repo.branches(Some(git2::BranchType::Remote)) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = branches
+            .flatten()
+            .filter_map(|(branch, _)| branch.name().ok().flatten().map(str::to_string))
+            .filter_map(|name| self.strip_base_remote(&name).map(str::to_string))
+            .filter(|name| name != "HEAD")
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Whether a local branch tracks a remote branch
+    pub fn has_upstream(&self, branch: &str) -> bool {
+        self.repo
+            .find_branch(branch, git2::BranchType::Local)
+            .is_ok_and(|b| b.upstream().is_ok())
+    }
+
+    /// Push a branch to the push remote and track it. Runs `git push` so
+    /// credential helpers and hooks apply.
+    pub fn push_branch(&self, branch: &str) -> Result<()> {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(["push", "--set-upstream", &self.remotes.push, branch])
+            .output()
+            .context("Failed to run git push")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to push {}: {}", branch, stderr.trim());
+        }
+        Ok(())
+    }
+
+    /// Full messages of the commits since the base branch, oldest first
+    /// (first-parent, like the timeline)
+    pub fn commit_messages_since_base(&self) -> Result<Vec<String>> {
+        let base = match &self.base_branch {
+            Some(b) => b,
+            None => return Ok(Vec::new()),
+        };
+
+        let merge_base = self.merge_base_commit(base)?;
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.simplify_first_parent()?;
+        revwalk.push(self.head_commit()?.id())?;
+        revwalk.hide(merge_base.id())?;
+
+        let mut messages = revwalk
+            .map(|oid| {
+                let commit = self.repo.find_commit(oid?)?;
+                Ok(commit.message().unwrap_or_default().to_string())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        messages.reverse();
+        Ok(messages)
+    }
+
+    /// Fetch a PR head from the base remote into a hidden ref (`refs/timecop/pr/<n>`)
+    /// and review it in place of HEAD. Diffs are read from the object
+    /// database; the current branch and working tree are not touched.
+    pub fn review_pr_head(&mut self, remote_ref: &str, pr_number: u64) -> Result<()> {
+        let local_ref = format!("refs/timecop/pr/{}", pr_number);
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(["fetch", "--no-tags", &self.remotes.base, &format!("+{}:{}", remote_ref, local_ref)])
+            .output()
+            .context("Failed to run git fetch")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to fetch {}: {}", remote_ref, stderr.trim());
+        }
+
+        let oid = self.repo.refname_to_id(&local_ref).context("Fetched PR head not found")?;
+        self.set_review_head(Some(oid));
+        Ok(())
+    }
+
+    /// Review a commit in place of HEAD, or go back to HEAD and the working tree
+    pub fn set_review_head(&mut self, oid: Option<git2::Oid>) {
+        self.review_head = oid;
+        self.cached_merge_base = None;
+    }
+
+    /// The commit on screen: the PR head reviewed in place, else HEAD
+    pub fn head_oid(&self) -> Option<git2::Oid> {
+        self.head_commit().ok().map(|c| c.id())
+    }
+
+    /// Whether a PR head is being reviewed in place
+    pub fn is_reviewing(&self) -> bool {
+        self.review_head.is_some()
+    }
+
+    /// Set the commit the user last reviewed for the since-review position.
+    /// A commit missing locally (force-pushed away) is fetched from the base
+    /// remote by id; either way it's kept alive by `refs/timecop/reviewed/<n>`.
+    /// Returns whether the commit is available.
+    pub fn set_reviewed_commit(&mut self, pr_number: u64, sha: Option<&str>) -> bool {
+        let Some(oid) = sha.and_then(|sha| git2::Oid::from_str(sha).ok()) else {
+            self.reviewed_commit = None;
+            self.reviewed_available = false;
+            return false;
+        };
+        // Don't retry a failed fetch for the same commit
+        if self.reviewed_commit == Some(oid) {
+            return self.reviewed_available;
+        }
+
+        let available = self.ensure_commit(oid, &format!("refs/timecop/reviewed/{}", pr_number));
+        self.reviewed_commit = Some(oid);
+        self.reviewed_available = available;
+        available
+    }
+
+    /// Whether the since-review position can be shown
+    pub fn has_reviewed_commit(&self) -> bool {
+        self.reviewed_commit.is_some() && self.reviewed_available
+    }
+
+    /// Snapshot the working tree as a checkpoint on top of HEAD, keeping at
+    /// most `limit` per branch, and reload the list. Returns whether one was
+    /// taken (not while reviewing in place, nor when nothing changed).
+    pub fn take_checkpoint(&mut self, limit: usize) -> Result<bool> {
+        if self.is_reviewing() {
+            return Ok(false);
+        }
+        let head = self.head_commit()?.id();
+        let taken = checkpoints::take(&self.repo, &self.current_branch()?, head, limit)?;
+        self.refresh_checkpoints();
+        Ok(taken)
+    }
+
+    /// Reload the checkpoints on top of HEAD
+    pub fn refresh_checkpoints(&mut self) {
+        let head = self.head_commit().map(|c| c.id());
+        self.checkpoints = match (self.is_reviewing(), self.current_branch(), head) {
+            (false, Ok(branch), Ok(head)) => checkpoints::list(&self.repo, &branch, head),
+            _ => Vec::new(),
+        };
+    }
+
+    /// Number of checkpoints on the timeline
+    pub fn checkpoint_count(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// When checkpoint `n` (1 is the newest) was taken, in seconds since the epoch
+    pub fn checkpoint_time(&self, n: usize) -> Option<i64> {
+        let oid = self.checkpoints.get(n.checked_sub(1)?)?;
+        Some(self.repo.find_commit(*oid).ok()?.time().seconds())
+    }
+
+    /// Lines added and removed in a working-tree file since the latest
+    /// checkpoint (or HEAD), i.e. since the previous change the watcher saw;
+    /// None when the content is the same
+    pub fn change_stats(&self, path: &str) -> Option<(usize, usize)> {
+        let snapshot = match self.checkpoints.first() {
+            Some(oid) => self.repo.find_commit(*oid).ok()?,
+            None => self.repo.head().ok()?.peel_to_commit().ok()?,
+        };
+        let old = self.blob_content(&snapshot, path).unwrap_or_default();
+        let new = std::fs::read(self.path.join(path)).unwrap_or_default();
+        if old == new {
+            return None;
+        }
+        let patch = git2::Patch::from_buffers(&old, None, &new, None, None).ok()?;
+        let (_, added, removed) = patch.line_stats().ok()?;
+        Some((added, removed))
+    }
+
+    fn checkpoint_commit(&self, n: usize) -> Result<git2::Commit<'_>> {
+        let oid = n
+            .checked_sub(1)
+            .and_then(|i| self.checkpoints.get(i))
+            .context("No such checkpoint")?;
+        Ok(self.repo.find_commit(*oid)?)
+    }
+
+    /// Trees before and after checkpoint `n`: the checkpoint before it (or
+    /// HEAD, for the oldest) and the checkpoint itself
+    fn checkpoint_trees(&self, n: usize) -> Result<(git2::Tree<'_>, git2::Tree<'_>)> {
+        let new = self.checkpoint_commit(n)?;
+        let old = match self.checkpoint_commit(n + 1) {
+            Ok(older) => older.tree()?,
+            Err(_) => new.parent(0)?.tree()?,
+        };
+        Ok((old, new.tree()?))
+    }
+
+    /// Make a commit available locally, fetching it by id from the base remote
+    /// if needed, and point `local_ref` at it so it isn't garbage collected
+    fn ensure_commit(&self, oid: git2::Oid, local_ref: &str) -> bool {
+        let available = self.repo.find_commit(oid).is_ok() || self.fetch_commit(oid, local_ref);
+        if available {
+            let _ = self.repo.reference(local_ref, oid, true, "timecop: keep commit");
+        }
+        available
+    }
+
+    fn fetch_commit(&self, oid: git2::Oid, local_ref: &str) -> bool {
+        let fetched = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&self.path)
+            .args(["fetch", "--no-tags", &self.remotes.base, &format!("+{}:{}", oid, local_ref)])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        fetched && self.repo.find_commit(oid).is_ok()
+    }
+
+    /// Earlier tips of the current branch and its upstream from the reflogs,
+    /// newest first, as (commit id, description)
+    pub fn reflog_revisions(&self) -> Vec<(String, String)> {
+        if self.is_reviewing() {
+            return Vec::new();
+        }
+        let Ok(branch) = self.current_branch() else {
+            return Vec::new();
+        };
+        let refs = [
+            (format!("refs/heads/{}", branch), branch.clone()),
+            (
+                format!("refs/remotes/{}/{}", self.remotes.push, branch),
+                format!("{}/{}", self.remotes.push, branch),
+            ),
+        ];
+
+        let mut revisions = Vec::new();
+        for (name, short) in refs {
+            let Ok(reflog) = self.repo.reflog(&name) else {
+                continue;
+            };
+            for (i, entry) in reflog.iter().enumerate() {
+                let message = entry.message().unwrap_or_default().to_string();
+                revisions.push((entry.id_new().to_string(), format!("{}@{{{}}}: {}", short, i, message)));
+            }
+        }
+        revisions
+    }
+
+    /// First line of a commit's message, if the commit is local
+    pub fn commit_summary(&self, sha: &str) -> Option<String> {
+        let oid = git2::Oid::from_str(sha).ok()?;
+        let commit = self.repo.find_commit(oid).ok()?;
+        commit.summary().map(str::to_string)
+    }
+
+    /// Range-diff between two revisions of the branch, each taken from its
+    /// own merge base with the base branch. Missing revisions are fetched
+    /// from the base remote into `refs/timecop/revisions/<id>`.
+    pub fn range_diff(&self, old: &str, new: &str) -> Result<Vec<super::RangeEntry>> {
+        let base = self.base_branch.as_deref().context("No base branch found")?;
+        let base = self.resolve_commit(base)?.id();
+        let mut tips = [git2::Oid::zero(); 2];
+        for (tip, sha) in tips.iter_mut().zip([old, new]) {
+            *tip = git2::Oid::from_str(sha).context("Invalid commit id")?;
+            if !self.ensure_commit(*tip, &format!("refs/timecop/revisions/{}", sha)) {
+                anyhow::bail!("Commit {} isn't available locally or on {}", &sha[..sha.len().min(7)], self.remotes.base);
+            }
+        }
+        super::range_diff::range_diff(&self.repo, base, tips[0], tips[1])
+    }
+
+    /// Trees compared by the since-review position: the reviewed commit's
+    /// (carried onto the current merge base if the branch was rebased) and the head's
+    fn since_review_trees(&self) -> Result<(git2::Tree<'_>, git2::Tree<'_>)> {
+        let oid = self
+            .reviewed_commit
+            .filter(|_| self.reviewed_available)
+            .context("No previous review to compare with")?;
+        let reviewed = self.repo.find_commit(oid).context("Failed to find reviewed commit")?;
+        let head = self.head_commit()?;
+        let old_tree = match self.rebased_tree(&reviewed, &head) {
+            Some(tree) => tree,
+            None => reviewed.tree()?,
+        };
+        Ok((old_tree, head.tree()?))
+    }
+
+    /// The reviewed tree as if it had been based on the head's merge base, so
+    /// a force-push that rebased the branch only shows the author's changes
+    /// and not what landed on the base meanwhile. None when the head still
+    /// builds on the reviewed commit, the merge base didn't move, or the base
+    /// changes don't apply cleanly to the reviewed tree.
+    fn rebased_tree(&self, reviewed: &git2::Commit, head: &git2::Commit) -> Option<git2::Tree<'_>> {
+        if reviewed.id() == head.id() || self.repo.graph_descendant_of(head.id(), reviewed.id()).ok()? {
+            return None;
+        }
+        let base = self.base_branch.as_deref()?;
+        let new_base = self.merge_base_commit(base).ok()?;
+        let base_commit = self.resolve_commit(base).ok()?;
+        let old_base = self.repo.merge_base(reviewed.id(), base_commit.id()).ok()?;
+        if old_base == new_base.id() {
+            return None;
+        }
+
+        let old_base_tree = self.repo.find_commit(old_base).ok()?.tree().ok()?;
+        let mut index = self
+            .repo
+            .merge_trees(&old_base_tree, &reviewed.tree().ok()?, &new_base.tree().ok()?, None)
+            .ok()?;
+        if index.has_conflicts() {
+            return None;
+        }
+        let tree = index.write_tree_to(&self.repo).ok()?;
+        self.repo.find_tree(tree).ok()
+    }
+
+    /// The commit diffs are computed against: the reviewed PR head, else HEAD
+    fn head_commit(&self) -> Result<git2::Commit<'_>> {
+        match self.review_head {
+            Some(oid) => self.repo.find_commit(oid).context("Failed to find reviewed commit"),
+            None => Ok(self.repo.head()?.peel_to_commit()?),
+        }
+    }
+
     /// Compute and cache the merge base OID so downstream calls don't repeat the graph walk
     pub fn refresh_merge_base_cache(&mut self) {
         let base = match &self.base_branch {
@@ -68,7 +486,7 @@ impl GitClient {
         };
         let oid = (|| -> Result<git2::Oid> {
             let base_commit = self.resolve_commit(&base)?;
-            let head_commit = self.repo.head()?.peel_to_commit()?;
+            let head_commit = self.head_commit()?;
             self.repo
                 .merge_base(head_commit.id(), base_commit.id())
                 .context("Failed to find merge-base")
@@ -76,13 +494,12 @@ impl GitClient {
         self.cached_merge_base = oid.ok();
     }
 
-    /// Fetch the base branch from origin so diffs match GitHub's view
+    /// Fetch the base branch from the base remote so diffs match the forge's view
     pub fn fetch_base_branch(&self) {
-        let base = match &self.base_branch {
-            Some(b) if b.starts_with("origin/") => b.strip_prefix("origin/").unwrap(),
-            _ => return,
+        let Some(base) = self.base_branch.as_deref().and_then(|b| self.strip_base_remote(b)) else {
+            return;
         };
-        let mut remote = match self.repo.find_remote("origin") {
+        let mut remote = match self.repo.find_remote(&self.remotes.base) {
             Ok(r) => r,
             Err(_) => return,
         };
@@ -90,11 +507,11 @@ impl GitClient {
     }
 
     /// Detect the base branch (main, master, etc.)
-    /// Prefers origin/main over local main to match GitHub's behavior
+    /// Prefers upstream/main (or origin/main) over local main to match GitHub's behavior
     fn detect_base_branch(&self) -> Option<String> {
         // Prefer remote branches (matches GitHub PR behavior)
         for name in &["main", "master"] {
-            let remote_name = format!("origin/{}", name);
+            let remote_name = format!("{}/{}", self.remotes.base, name);
             if self.repo.find_reference(&format!("refs/remotes/{}", remote_name)).is_ok() {
                 return Some(remote_name);
             }
@@ -123,7 +540,7 @@ impl GitClient {
         };
 
         let merge_base = self.merge_base_commit(base)?;
-        let head_commit = self.repo.head()?.peel_to_commit()?;
+        let head_commit = self.head_commit()?;
 
         let base_tree = merge_base.tree()?;
         let head_tree = head_commit.tree()?;
@@ -183,6 +600,9 @@ impl GitClient {
 
     /// Get paths of uncommitted files
     fn get_uncommitted_paths(&self) -> Result<HashSet<String>> {
+        if self.is_reviewing() {
+            return Ok(HashSet::new());
+        }
         let mut opts = Self::status_opts();
         let statuses = self.repo.statuses(Some(&mut opts))?;
         let mut paths = HashSet::new();
@@ -198,6 +618,10 @@ impl GitClient {
 
     /// Get status for uncommitted-only files
     fn uncommitted_status(&self) -> Result<Vec<StatusEntry>> {
+        // A reviewed PR head has no working tree
+        if self.is_reviewing() {
+            return Ok(Vec::new());
+        }
         let mut opts = Self::status_opts();
         let statuses = self.repo.statuses(Some(&mut opts))?;
         let mut entries = Vec::new();
@@ -346,12 +770,26 @@ impl GitClient {
                 };
                 let merge_base = self.merge_base_commit(base)?;
                 let base_tree = merge_base.tree()?;
-                self.repo.diff_tree_to_workdir_with_index(Some(&base_tree), None)?
+                if self.is_reviewing() {
+                    let head_tree = self.head_commit()?.tree()?;
+                    self.repo.diff_tree_to_tree(Some(&base_tree), Some(&head_tree), None)?
+                } else {
+                    self.repo.diff_tree_to_workdir_with_index(Some(&base_tree), None)?
+                }
             }
+            TimelinePosition::Wip if self.is_reviewing() => return Ok(DiffStats::default()),
             TimelinePosition::Wip => {
                 let head_tree = self.repo.head()?.peel_to_tree()?;
                 self.repo.diff_tree_to_workdir(Some(&head_tree), None)?
             }
+            TimelinePosition::SinceReview => {
+                let (old_tree, new_tree) = self.since_review_trees()?;
+                self.repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?
+            }
+            TimelinePosition::Checkpoint(n) => {
+                let (old_tree, new_tree) = self.checkpoint_trees(n)?;
+                self.repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?
+            }
             TimelinePosition::CommitDiff(n) => {
                 let old_commit = self.commit_at_offset(n)?;
                 let new_commit = self.commit_at_offset(n - 1)?;
@@ -381,7 +819,7 @@ impl GitClient {
             oid
         } else {
             let base_commit = self.resolve_commit(base)?;
-            let head_commit = self.repo.head()?.peel_to_commit()?;
+            let head_commit = self.head_commit()?;
             self.repo
                 .merge_base(head_commit.id(), base_commit.id())
                 .context("Failed to find merge-base")?
@@ -397,10 +835,17 @@ impl GitClient {
         &self.path
     }
 
+    /// Get the `.git` directory (for app state that shouldn't touch the worktree)
+    pub fn git_dir(&self) -> &Path {
+        self.repo.path()
+    }
+
     /// Check if there are any uncommitted changes (staged or unstaged)
     pub fn has_uncommitted_changes(&self) -> bool {
-        self.get_uncommitted_paths()
-            .map(|paths| !paths.is_empty())
+        let mut opts = Self::status_opts();
+        self.repo
+            .statuses(Some(&mut opts))
+            .map(|statuses| !statuses.is_empty())
             .unwrap_or(false)
     }
 
@@ -413,7 +858,7 @@ impl GitClient {
         };
 
         let merge_base = self.merge_base_commit(base)?;
-        let head_commit = self.repo.head()?.peel_to_commit()?;
+        let head_commit = self.head_commit()?;
 
         let mut count = 0;
         let mut revwalk = self.repo.revwalk()?;
@@ -430,7 +875,7 @@ impl GitClient {
 
     /// Get commit at HEAD~n (first-parent only, matches GitHub PR behavior)
     fn commit_at_offset(&self, offset: usize) -> Result<git2::Commit<'_>> {
-        let head = self.repo.head()?.peel_to_commit()?;
+        let head = self.head_commit()?;
         if offset == 0 {
             return Ok(head);
         }
@@ -468,9 +913,23 @@ impl GitClient {
 
         // Browse mode: return file content, not diff
         if matches!(position, TimelinePosition::Browse) {
+            if self.is_reviewing() {
+                let content = self.blob_content(&self.head_commit()?, path)?;
+                return Ok(String::from_utf8_lossy(&content).into_owned());
+            }
             return self.read_file(path);
         }
 
+        let mut opts = DiffOptions::new();
+        opts.pathspec(path);
+
+        // Checkpoints don't depend on the base branch
+        if let TimelinePosition::Checkpoint(n) = position {
+            let (old_tree, new_tree) = self.checkpoint_trees(n)?;
+            let diff = self.repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), Some(&mut opts))?;
+            return self.diff_to_string(&diff);
+        }
+
         let base = match &self.base_branch {
             Some(b) => b,
             None => return self.working_diff(path),
@@ -479,11 +938,15 @@ impl GitClient {
         let merge_base = self.merge_base_commit(base)?;
         let base_tree = merge_base.tree()?;
 
-        let mut opts = DiffOptions::new();
-        opts.pathspec(path);
-
         match position {
-            TimelinePosition::Browse => unreachable!(), // Handled above
+            TimelinePosition::Browse | TimelinePosition::Checkpoint(_) => unreachable!(), // Handled above
+            TimelinePosition::FullDiff if self.is_reviewing() => {
+                // Base to the reviewed PR head
+                let head_tree = self.head_commit()?.tree()?;
+                let diff = self.repo.diff_tree_to_tree(Some(&base_tree), Some(&head_tree), Some(&mut opts))?;
+                self.diff_to_string(&diff)
+            }
+            TimelinePosition::Wip if self.is_reviewing() => Ok(String::new()),
             TimelinePosition::FullDiff => {
                 // Base to working tree (all changes: committed + uncommitted)
                 let diff = self.repo.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut opts))?;
@@ -503,6 +966,12 @@ impl GitClient {
                 }
                 Ok(result)
             }
+            TimelinePosition::SinceReview => {
+                // Last reviewed commit to HEAD (or the PR head reviewed in place)
+                let (old_tree, new_tree) = self.since_review_trees()?;
+                let diff = self.repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), Some(&mut opts))?;
+                self.diff_to_string(&diff)
+            }
             TimelinePosition::CommitDiff(n) => {
                 // Single commit: HEAD~n → HEAD~(n-1)
                 let old_commit = self.commit_at_offset(n)?;
@@ -516,6 +985,50 @@ impl GitClient {
         }
     }
 
+    /// Map lines of `path` as of `commit` onto the version shown at a timeline position.
+    /// Fails if the commit or file isn't available locally.
+    pub fn line_map(&self, path: &str, commit: &str, position: super::TimelinePosition) -> Result<LineMap> {
+        let oid = git2::Oid::from_str(commit).context("Invalid commit id")?;
+        let commit = self.repo.find_commit(oid).context("Commit not available locally")?;
+        let old = self.blob_content(&commit, path)?;
+        let new = self.displayed_content(path, position)?;
+
+        let mut opts = DiffOptions::new();
+        opts.context_lines(0);
+        let patch = git2::Patch::from_buffers(&old, None, &new, None, Some(&mut opts))?;
+
+        let mut hunks = Vec::with_capacity(patch.num_hunks());
+        for i in 0..patch.num_hunks() {
+            let (hunk, _) = patch.hunk(i)?;
+            hunks.push((hunk.old_start(), hunk.old_lines(), hunk.new_start(), hunk.new_lines()));
+        }
+        Ok(LineMap::new(hunks))
+    }
+
+    /// File content on the new (right) side of the diff at a timeline position
+    pub fn displayed_content(&self, path: &str, position: super::TimelinePosition) -> Result<Vec<u8>> {
+        use super::TimelinePosition;
+
+        match position {
+            TimelinePosition::CommitDiff(n) => {
+                let commit = self.commit_at_offset(n - 1)?;
+                self.blob_content(&commit, path)
+            }
+            TimelinePosition::SinceReview => self.blob_content(&self.head_commit()?, path),
+            TimelinePosition::Checkpoint(n) => self.blob_content(&self.checkpoint_commit(n)?, path),
+            _ if self.is_reviewing() => self.blob_content(&self.head_commit()?, path),
+            // Full diff, wip and browse all show the working tree
+            _ => std::fs::read(self.path.join(path))
+                .with_context(|| format!("Failed to read file: {}", path)),
+        }
+    }
+
+    fn blob_content(&self, commit: &git2::Commit, path: &str) -> Result<Vec<u8>> {
+        let entry = commit.tree()?.get_path(Path::new(path))?;
+        let blob = self.repo.find_blob(entry.id())?;
+        Ok(blob.content().to_vec())
+    }
+
     /// Get file status at a specific timeline position
     pub fn status_at_position(&self, position: super::TimelinePosition) -> Result<Vec<StatusEntry>> {
         use super::TimelinePosition;
@@ -535,6 +1048,18 @@ impl GitClient {
                 // Show ONLY uncommitted changes: HEAD → working tree
                 self.uncommitted_status()
             }
+            TimelinePosition::SinceReview => {
+                // Show changes since the last review: reviewed commit → HEAD
+                let (old_tree, new_tree) = self.since_review_trees()?;
+                let diff = self.repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?;
+                Ok(Self::diff_entries(&diff))
+            }
+            TimelinePosition::Checkpoint(n) => {
+                // Show what one checkpoint changed: previous checkpoint → checkpoint N
+                let (old_tree, new_tree) = self.checkpoint_trees(n)?;
+                let diff = self.repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?;
+                Ok(Self::diff_entries(&diff))
+            }
             TimelinePosition::CommitDiff(n) => {
                 // Show changes from single commit: HEAD~n → HEAD~(n-1)
                 log::debug!("Getting single commit diff: HEAD~{} → HEAD~{}", n, n - 1);
@@ -546,47 +1071,73 @@ impl GitClient {
                 let new_tree = new_commit.tree()?;
 
                 let diff = self.repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?;
+                Ok(Self::diff_entries(&diff))
+            }
+        }
+    }
 
-                let mut entries = Vec::new();
-                for delta in diff.deltas() {
-                    let path = delta
-                        .new_file()
-                        .path()
-                        .or_else(|| delta.old_file().path())
-                        .map(|p| p.to_string_lossy().to_string())
-                        .unwrap_or_default();
-
-                    let status = match delta.status() {
-                        git2::Delta::Added => FileStatus::Added,
-                        git2::Delta::Deleted => FileStatus::Deleted,
-                        git2::Delta::Modified => FileStatus::Modified,
-                        git2::Delta::Renamed => FileStatus::Renamed,
-                        _ => continue,
-                    };
+    /// Changed files of a tree-to-tree diff, sorted by path
+    fn diff_entries(diff: &git2::Diff) -> Vec<StatusEntry> {
+        let mut entries = Vec::new();
+        for delta in diff.deltas() {
+            let path = delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
 
-                    entries.push(StatusEntry {
-                        path,
-                        status,
-                        uncommitted: false,
-                        entry_type: EntryType::Tracked,
-                    });
-                }
+            let status = match delta.status() {
+                git2::Delta::Added => FileStatus::Added,
+                git2::Delta::Deleted => FileStatus::Deleted,
+                git2::Delta::Modified => FileStatus::Modified,
+                git2::Delta::Renamed => FileStatus::Renamed,
+                _ => continue,
+            };
 
-                entries.sort_by(|a, b| a.path.cmp(&b.path));
-                Ok(entries)
-            }
+            entries.push(StatusEntry {
+                path,
+                status,
+                uncommitted: false,
+                entry_type: EntryType::Tracked,
+            });
         }
+
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        entries
     }
 
     /// List all files in the repository directory (for browse/files mode)
     /// Walks filesystem and marks gitignored files
     fn list_all_files(&self) -> Result<Vec<StatusEntry>> {
+        if self.is_reviewing() {
+            return self.list_tree_files();
+        }
         let mut entries = Vec::new();
         self.walk_dir(&self.path, &mut entries, 0)?;
         entries.sort_by(|a, b| a.path.cmp(&b.path));
         Ok(entries)
     }
 
+    /// List all files in the reviewed PR head's tree
+    fn list_tree_files(&self) -> Result<Vec<StatusEntry>> {
+        let tree = self.head_commit()?.tree()?;
+        let mut entries = Vec::new();
+        tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() == Some(git2::ObjectType::Blob) {
+                entries.push(StatusEntry {
+                    path: format!("{}{}", dir, entry.name().unwrap_or_default()),
+                    status: FileStatus::Unchanged,
+                    uncommitted: false,
+                    entry_type: EntryType::Tracked,
+                });
+            }
+            git2::TreeWalkResult::Ok
+        })?;
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(entries)
+    }
+
     /// Walk an ignored directory and return its entries (for lazy expansion)
     pub fn list_ignored_dir(&self, rel_dir: &str) -> Result<Vec<StatusEntry>> {
         let abs_dir = self.path.join(rel_dir);
@@ -656,3 +1207,167 @@ impl GitClient {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestRepo;
+
+    /// Repo with one commit on `main` and a PR commit only reachable from
+    /// `refs/pull/7/head`; `origin` points at the repo itself
+    fn repo_with_pr(name: &str) -> TestRepo {
+        let test = TestRepo::new(&format!("git-{}", name));
+        let base = test.commit(None, "msg", &[("a.txt", "one\n")], Some("refs/heads/main"));
+        test.checkout("refs/heads/main");
+        test.commit(Some(base), "msg", &[("a.txt", "one\ntwo\n"), ("b.txt", "new\n")], Some("refs/pull/7/head"));
+        test.repo.remote("origin", test.dir.to_str().unwrap()).unwrap();
+        test
+    }
+
+    // --- stash and return ---
+
+    #[test]
+    fn stash_all_includes_untracked_and_pops_back() {
+        let dir = repo_with_pr("stash").dir;
+        let mut git = GitClient::open(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), "changed\n").unwrap();
+        std::fs::write(dir.join("c.txt"), "untracked\n").unwrap();
+
+        let stash = git.stash_all("test").unwrap();
+        assert!(!git.has_uncommitted_changes());
+        assert!(!dir.join("c.txt").exists());
+
+        assert!(git.pop_stash(stash).unwrap());
+        assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), "changed\n");
+        assert!(dir.join("c.txt").exists());
+        assert!(!git.pop_stash(stash).unwrap());
+    }
+
+    #[test]
+    fn pop_stash_keeps_entry_on_conflict() {
+        let dir = repo_with_pr("conflict").dir;
+        let mut git = GitClient::open(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), "stashed\n").unwrap();
+        let stash = git.stash_all("test").unwrap();
+
+        std::fs::write(dir.join("a.txt"), "other\n").unwrap();
+        assert!(git.pop_stash(stash).is_err());
+        assert_eq!(std::fs::read_to_string(dir.join("a.txt")).unwrap(), "other\n");
+
+        std::fs::write(dir.join("a.txt"), "one\n").unwrap();
+        assert!(git.pop_stash(stash).unwrap());
+    }
+
+    #[test]
+    fn switch_branch_and_head_name() {
+        let dir = repo_with_pr("switch").dir;
+        let mut git = GitClient::open(&dir).unwrap();
+        assert_eq!(git.head_name().unwrap(), "main");
+
+        let pr = git.repo.refname_to_id("refs/pull/7/head").unwrap().to_string();
+        git.switch_branch(&pr).unwrap();
+        assert_eq!(git.head_name().unwrap(), pr);
+        assert!(dir.join("b.txt").exists());
+
+        git.switch_branch("main").unwrap();
+        assert_eq!(git.head_name().unwrap(), "main");
+        assert!(!dir.join("b.txt").exists());
+    }
+
+    // --- opening a PR ---
+
+    #[test]
+    fn push_branch_sets_upstream() {
+        let dir = repo_with_pr("push").dir;
+        let git = GitClient::open(&dir).unwrap();
+        let pr = git.repo.find_commit(git.repo.refname_to_id("refs/pull/7/head").unwrap()).unwrap();
+        git.repo.branch("feature", &pr, false).unwrap();
+        assert!(!git.has_upstream("feature"));
+
+        git.push_branch("feature").unwrap();
+        assert!(git.has_upstream("feature"));
+        assert!(git.remote_branches().contains(&"feature".to_string()));
+    }
+
+    #[test]
+    fn commit_messages_since_base_oldest_first() {
+        let dir = repo_with_pr("messages").dir;
+        let mut git = GitClient::open(&dir).unwrap();
+        git.switch_branch(&git.repo.refname_to_id("refs/pull/7/head").unwrap().to_string()).unwrap();
+        git.set_base_branch("main");
+        assert_eq!(git.base_branch_name(), Some("main"));
+        assert_eq!(git.commit_messages_since_base().unwrap(), ["msg"]);
+    }
+
+    // --- review in place ---
+
+    #[test]
+    fn review_pr_head_diffs_without_touching_worktree() {
+        let dir = repo_with_pr("review").dir;
+        let mut git = GitClient::open(&dir).unwrap();
+        git.review_pr_head("refs/pull/7/head", 7).unwrap();
+        git.set_base_branch("main");
+        git.refresh_merge_base_cache();
+
+        let files = git.status_at_position(TimelinePosition::FullDiff).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["a.txt", "b.txt"]);
+        assert!(files.iter().all(|f| !f.uncommitted));
+        assert!(git.diff_at_position("a.txt", TimelinePosition::FullDiff).unwrap().contains("+two"));
+        assert_eq!(git.commit_count_since_base().unwrap(), 1);
+        assert!(git.status_at_position(TimelinePosition::Wip).unwrap().is_empty());
+
+        // Browse reads the PR tree, not the working tree
+        let browse = git.status_at_position(TimelinePosition::Browse).unwrap();
+        assert!(browse.iter().any(|f| f.path == "b.txt"));
+        assert_eq!(git.diff_at_position("b.txt", TimelinePosition::Browse).unwrap(), "new\n");
+
+        assert!(!dir.join("b.txt").exists());
+        assert_eq!(git.current_branch().unwrap(), "main");
+        assert!(!git.has_uncommitted_changes());
+
+        git.set_review_head(None);
+        git.refresh_merge_base_cache();
+        assert!(git.status_at_position(TimelinePosition::FullDiff).unwrap().is_empty());
+    }
+
+    // --- since review ---
+
+    #[test]
+    fn since_review_ignores_base_changes_after_rebase() {
+        let test = repo_with_pr("since-review");
+        let (repo, dir) = (&test.repo, &test.dir);
+
+        // Reviewed at refs/pull/7/head; then main moved on and the PR was
+        // rebased onto it with one more change
+        let reviewed = repo.refname_to_id("refs/pull/7/head").unwrap();
+        let main = repo.refname_to_id("refs/heads/main").unwrap();
+        let main = test.commit(Some(main), "msg", &[("c.txt", "base\n")], Some("refs/heads/main"));
+        let files = [("a.txt", "one\ntwo\nthree\n"), ("b.txt", "new\n")];
+        test.commit(Some(main), "msg", &files, Some("refs/pull/7/head"));
+
+        let mut git = GitClient::open(dir).unwrap();
+        git.review_pr_head("refs/pull/7/head", 7).unwrap();
+        git.set_base_branch("main");
+        git.refresh_merge_base_cache();
+        assert!(!git.has_reviewed_commit());
+        assert!(git.set_reviewed_commit(7, Some(&reviewed.to_string())));
+
+        let files = git.status_at_position(TimelinePosition::SinceReview).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["a.txt"]);
+        let diff = git.diff_at_position("a.txt", TimelinePosition::SinceReview).unwrap();
+        assert!(diff.contains("+three") && !diff.contains("+two"));
+
+        assert!(!git.set_reviewed_commit(7, Some(&"0".repeat(40))));
+        assert!(!git.has_reviewed_commit());
+    }
+
+    #[test]
+    fn review_pr_head_reports_missing_ref() {
+        let dir = repo_with_pr("missing").dir;
+        let mut git = GitClient::open(&dir).unwrap();
+        assert!(git.review_pr_head("refs/pull/99/head", 99).is_err());
+        assert!(!git.is_reviewing());
+    }
+}
@@ -1,5 +1,10 @@
+mod checkpoints;
 mod client;
+mod range_diff;
+mod remote;
 mod types;
 
 pub use client::GitClient;
+pub use range_diff::{RangeCommit, RangeEntry};
+pub use remote::{pin_remote, Remotes};
 pub use types::*;
@@ -0,0 +1,187 @@
+//! Range-diff between two revisions of a branch
+//!
+//! Like `git range-diff`: each revision's commits since its own merge base
+//! are paired up, first by patch-id (the same change, wherever it was
+//! applied) and then by subject. Paired commits whose patches differ get an
+//! interdiff, a diff of the two patches; the rest were added or dropped.
+
+use anyhow::{Context, Result};
+use git2::{Commit, DiffFormat, Oid, Repository};
+
+/// A commit of one revision
+#[derive(Debug, Clone)]
+pub struct RangeCommit {
+    /// 1-based position in its revision, oldest first
+    pub index: usize,
+    pub oid: Oid,
+    pub summary: String,
+    patch: String,
+    patch_id: Option<Oid>,
+}
+
+impl RangeCommit {
+    pub fn short_id(&self) -> String {
+        self.oid.to_string()[..7].to_string()
+    }
+}
+
+/// One line of a range-diff
+#[derive(Debug, Clone)]
+pub struct RangeEntry {
+    pub old: Option<RangeCommit>,
+    pub new: Option<RangeCommit>,
+    /// Diff between the two commits' patches; empty unless both exist and differ
+    pub interdiff: String,
+}
+
+impl RangeEntry {
+    /// `=` same patch, `!` changed, `<` dropped, `>` added
+    pub fn marker(&self) -> char {
+        match (&self.old, &self.new) {
+            (Some(_), Some(_)) if self.interdiff.is_empty() => '=',
+            (Some(_), Some(_)) => '!',
+            (Some(_), None) => '<',
+            _ => '>',
+        }
+    }
+
+    pub fn summary(&self) -> &str {
+        self.new.as_ref().or(self.old.as_ref()).map(|c| c.summary.as_str()).unwrap_or_default()
+    }
+}
+
+/// Range-diff of the commits `old` and `new` add on top of `base`
+pub fn range_diff(repo: &Repository, base: Oid, old: Oid, new: Oid) -> Result<Vec<RangeEntry>> {
+    let old_commits = revision_commits(repo, base, old)?;
+    let new_commits = revision_commits(repo, base, new)?;
+    Ok(pair_commits(old_commits, new_commits))
+}
+
+/// Commits from the merge base of `tip` and `base` up to `tip`, oldest first
+/// (first parent only, like the timeline)
+fn revision_commits(repo: &Repository, base: Oid, tip: Oid) -> Result<Vec<RangeCommit>> {
+    let merge_base = repo.merge_base(tip, base).context("Failed to find merge-base")?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.simplify_first_parent()?;
+    revwalk.push(tip)?;
+    revwalk.hide(merge_base)?;
+    let oids: Vec<Oid> = revwalk.collect::<std::result::Result<_, _>>()?;
+
+    oids.into_iter()
+        .rev()
+        .enumerate()
+        .map(|(i, oid)| {
+            let commit = repo.find_commit(oid)?;
+            let (patch, patch_id) = commit_patch(repo, &commit)?;
+            Ok(RangeCommit {
+                index: i + 1,
+                oid,
+                summary: commit.summary().unwrap_or("(no message)").to_string(),
+                patch,
+                patch_id,
+            })
+        })
+        .collect()
+}
+
+/// A commit's patch against its first parent, and its patch-id
+fn commit_patch(repo: &Repository, commit: &Commit) -> Result<(String, Option<Oid>)> {
+    let parent_tree = commit.parent(0).ok().map(|p| p.tree()).transpose()?;
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    let mut patch = String::new();
+    diff.print(DiffFormat::Patch, |_, _, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin());
+        }
+        patch.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })?;
+    Ok((patch, diff.patchid(None).ok()))
+}
+
+/// Pair old and new commits, in new order with each dropped commit right
+/// after the old commit it followed
+fn pair_commits(old: Vec<RangeCommit>, new: Vec<RangeCommit>) -> Vec<RangeEntry> {
+    let mut matched: Vec<Option<usize>> = vec![None; new.len()];
+    let mut used = vec![false; old.len()];
+
+    // Same change first, then the same subject
+    let passes: [fn(&RangeCommit, &RangeCommit) -> bool; 2] = [
+        |a, b| a.patch_id.is_some() && a.patch_id == b.patch_id,
+        |a, b| a.summary == b.summary,
+    ];
+    for same in passes {
+        for (n, new_commit) in new.iter().enumerate() {
+            if matched[n].is_some() {
+                continue;
+            }
+            if let Some(o) = (0..old.len()).find(|&o| !used[o] && same(&old[o], new_commit)) {
+                used[o] = true;
+                matched[n] = Some(o);
+            }
+        }
+    }
+
+    let dropped = |o: usize| RangeEntry { old: Some(old[o].clone()), new: None, interdiff: String::new() };
+    let mut entries: Vec<RangeEntry> = (0..old.len()).take_while(|&o| !used[o]).map(dropped).collect();
+    for (n, new_commit) in new.iter().enumerate() {
+        let Some(o) = matched[n] else {
+            entries.push(RangeEntry { old: None, new: Some(new_commit.clone()), interdiff: String::new() });
+            continue;
+        };
+        let interdiff = interdiff(&old[o], new_commit);
+        entries.push(RangeEntry { old: Some(old[o].clone()), new: Some(new_commit.clone()), interdiff });
+        entries.extend((o + 1..old.len()).take_while(|&o| !used[o]).map(dropped));
+    }
+    entries
+}
+
+/// Diff of two commits' patches, hunks only; empty for the same change
+fn interdiff(old: &RangeCommit, new: &RangeCommit) -> String {
+    if old.patch == new.patch || (old.patch_id.is_some() && old.patch_id == new.patch_id) {
+        return String::new();
+    }
+    let patch = git2::Patch::from_buffers(old.patch.as_bytes(), None, new.patch.as_bytes(), None, None)
+        .and_then(|mut patch| patch.to_buf());
+    match patch {
+        Ok(buf) => {
+            let text = String::from_utf8_lossy(&buf).into_owned();
+            // Drop the file header; the hunks are the interesting part
+            match text.find("@@") {
+                Some(start) => text[start..].to_string(),
+                None => text,
+            }
+        }
+        Err(_) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestRepo;
+
+    #[test]
+    fn pairs_rebased_commits_and_reports_changes() {
+        let test = TestRepo::new("range-diff");
+        let commit = |parent: Oid, message: &str, files: &[(&str, &str)]| test.commit(Some(parent), message, files, None);
+        let root = test.commit(None, "root", &[], None);
+
+        // Old: a, b, c on root
+        let a = commit(root, "add a", &[("a.txt", "a\n")]);
+        let b = commit(a, "add b", &[("b.txt", "b\n")]);
+        let old = commit(b, "add c", &[("c.txt", "c\n")]);
+
+        // Base moved on; new: a (same patch), b (reworked), d; c dropped
+        let base = commit(root, "base", &[("base.txt", "x\n")]);
+        let a2 = commit(base, "add a", &[("a.txt", "a\n")]);
+        let b2 = commit(a2, "add b", &[("b.txt", "b2\n")]);
+        let new = commit(b2, "add d", &[("d.txt", "d\n")]);
+
+        let entries = range_diff(&test.repo, base, old, new).unwrap();
+        let summary: Vec<(char, &str)> = entries.iter().map(|e| (e.marker(), e.summary())).collect();
+        assert_eq!(summary, [('=', "add a"), ('!', "add b"), ('<', "add c"), ('>', "add d")]);
+        assert!(entries[1].interdiff.contains("-+b") && entries[1].interdiff.contains("++b2"));
+        assert_eq!(entries[1].old.as_ref().map(|c| c.index), Some(2));
+    }
+}
@@ -0,0 +1,139 @@
+//! Which remotes PRs go through
+//!
+//! In a fork workflow PRs live on `upstream` while branches are pushed to
+//! `origin` (the fork). The base remote hosts the PRs and the base branches;
+//! the push remote receives the user's branches. Either can be the same.
+
+use anyhow::{Context, Result};
+use git2::Repository;
+use std::path::Path;
+
+/// git config key pinning the base remote for one repository
+/// (`git config timecop.remote upstream`)
+const PIN_KEY: &str = "timecop.remote";
+
+/// Pin the base remote of the repository at `path` in its git config
+pub fn pin_remote(path: &Path, name: &str) -> Result<()> {
+    let repo = Repository::open(path).context("Failed to open git repository")?;
+    repo.find_remote(name).with_context(|| format!("No remote named {}", name))?;
+    repo.config()?
+        .set_str(PIN_KEY, name)
+        .context("Failed to save pinned remote")
+}
+
+/// Remote names used for PRs and pushes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Remotes {
+    /// Where PRs are opened and base branches come from
+    pub base: String,
+    /// Where the user's branches are pushed
+    pub push: String,
+}
+
+impl Default for Remotes {
+    fn default() -> Self {
+        Self { base: "origin".to_string(), push: "origin".to_string() }
+    }
+}
+
+impl Remotes {
+    /// Remotes of a repository, from its remote names and URLs and its config
+    pub fn resolve(repo: &Repository) -> Self {
+        let names: Vec<String> = repo
+            .remotes()
+            .map(|names| names.iter().flatten().map(str::to_string).collect())
+            .unwrap_or_default();
+        let remotes: Vec<(String, String)> = names
+            .into_iter()
+            .map(|name| {
+                let url = repo.find_remote(&name).ok().and_then(|r| r.url().map(str::to_string));
+                (name, url.unwrap_or_default())
+            })
+            .collect();
+        let config = repo.config().ok();
+        let get = |key: &str| config.as_ref().and_then(|c| c.get_string(key).ok());
+        Self::choose(&remotes, get(PIN_KEY).as_deref(), get("remote.pushDefault").as_deref())
+    }
+
+    /// Pick remotes from (name, URL) pairs. A pinned remote wins, then
+    /// `upstream`, then `origin`, then the first remote. Branches are pushed
+    /// to `remote.pushDefault`, else to `origin` when it's a different
+    /// repository than the base (a fork), else to the base.
+    fn choose(remotes: &[(String, String)], pinned: Option<&str>, push_default: Option<&str>) -> Self {
+        let url = |name: &str| remotes.iter().find(|(n, _)| n == name).map(|(_, url)| url.as_str());
+        let base = pinned
+            .filter(|name| url(name).is_some())
+            .or_else(|| ["upstream", "origin"].into_iter().find(|name| url(name).is_some()))
+            .or_else(|| remotes.first().map(|(name, _)| name.as_str()))
+            .unwrap_or("origin")
+            .to_string();
+
+        let fork = url("origin").filter(|origin| Some(*origin) != url(&base)).map(|_| "origin");
+        let push = push_default
+            .filter(|name| url(name).is_some())
+            .or(fork)
+            .unwrap_or(&base)
+            .to_string();
+        Self { base, push }
+    }
+
+    /// Whether branches go to a different repository than the PRs (a fork)
+    pub fn is_fork(&self) -> bool {
+        self.base != self.push
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn remotes(list: &[(&str, &str)]) -> Vec<(String, String)> {
+        list.iter().map(|(n, u)| (n.to_string(), u.to_string())).collect()
+    }
+
+    #[test]
+    fn choose_prefers_upstream_and_pushes_to_fork() {
+        let list = remotes(&[("origin", "git@github.com:me/repo.git"), ("upstream", "git@github.com:org/repo.git")]);
+        let chosen = Remotes::choose(&list, None, None);
+        assert_eq!((chosen.base.as_str(), chosen.push.as_str()), ("upstream", "origin"));
+        assert!(chosen.is_fork());
+
+        // Same repository under two names isn't a fork
+        let list = remotes(&[("origin", "git@github.com:org/repo.git"), ("upstream", "git@github.com:org/repo.git")]);
+        assert_eq!(Remotes::choose(&list, None, None).push, "upstream");
+    }
+
+    #[test]
+    fn choose_honors_pin_and_push_default() {
+        let list = remotes(&[("origin", "a"), ("upstream", "b"), ("work", "c")]);
+        let chosen = Remotes::choose(&list, Some("work"), Some("upstream"));
+        assert_eq!((chosen.base.as_str(), chosen.push.as_str()), ("work", "upstream"));
+
+        // Unknown names are ignored
+        let chosen = Remotes::choose(&list, Some("gone"), Some("gone"));
+        assert_eq!((chosen.base.as_str(), chosen.push.as_str()), ("upstream", "origin"));
+    }
+
+    #[test]
+    fn pinned_remote_is_resolved() {
+        let dir = TempDir::new("remote");
+        let repo = Repository::init(&dir).unwrap();
+        repo.remote("origin", "https://github.com/me/repo").unwrap();
+        repo.remote("upstream", "https://github.com/org/repo").unwrap();
+        repo.remote("mirror", "https://ghe.corp/org/repo").unwrap();
+        assert_eq!(Remotes::resolve(&repo).base, "upstream");
+
+        assert!(pin_remote(&dir, "gone").is_err());
+        pin_remote(&dir, "mirror").unwrap();
+        let remotes = Remotes::resolve(&repo);
+        assert_eq!((remotes.base.as_str(), remotes.push.as_str()), ("mirror", "origin"));
+    }
+
+    #[test]
+    fn choose_falls_back_to_first_remote() {
+        let chosen = Remotes::choose(&remotes(&[("gitlab", "x")]), None, None);
+        assert_eq!(chosen, Remotes { base: "gitlab".to_string(), push: "gitlab".to_string() });
+        assert_eq!(Remotes::choose(&[], None, None), Remotes::default());
+    }
+}
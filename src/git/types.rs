@@ -69,15 +69,53 @@ pub struct DiffStats {
     pub removed: usize,
 }
 
+/// Changed regions between two versions of a file, used to carry line
+/// numbers (e.g. review comment anchors) from the old version to the new one
+#[derive(Debug, Clone, Default)]
+pub struct LineMap {
+    /// (old_start, old_lines, new_start, new_lines) per hunk, diffed without context
+    hunks: Vec<(u32, u32, u32, u32)>,
+}
+
+impl LineMap {
+    pub fn new(hunks: Vec<(u32, u32, u32, u32)>) -> Self {
+        Self { hunks }
+    }
+
+    /// Map an old line number onto the new version.
+    /// Returns None if the line itself was changed or removed.
+    pub fn map(&self, line: u32) -> Option<u32> {
+        let mut offset: i64 = 0;
+        for &(old_start, old_lines, _, new_lines) in &self.hunks {
+            // Pure insertions come *after* old_start
+            let before_hunk = if old_lines == 0 { line <= old_start } else { line < old_start };
+            if before_hunk {
+                break;
+            }
+            if line < old_start + old_lines {
+                return None;
+            }
+            offset += new_lines as i64 - old_lines as i64;
+        }
+        u32::try_from(line as i64 + offset).ok()
+    }
+}
+
 /// Timeline position for viewing PR history
-/// Order (older → newer): -16 → ... → -1 → Wip → FullDiff → Browse
+/// Order (older → newer): -16 → ... → -1 → ~N → ... → ~1 → Wip → SinceReview → FullDiff → Browse
 /// FullDiff is the default (primary code review view)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TimelinePosition {
     /// View changes from a single commit: HEAD~N → HEAD~(N-1)
     CommitDiff(usize),
+    /// View one working-tree checkpoint (1 is the newest): the checkpoint
+    /// before it (or HEAD) → checkpoint N
+    Checkpoint(usize),
     /// View only uncommitted changes: HEAD → working tree
     Wip,
+    /// View what changed since the user's last review: reviewed commit → HEAD
+    /// (only offered when that commit is known)
+    SinceReview,
     /// View all committed changes: base → HEAD (default)
     #[default]
     FullDiff,
@@ -86,27 +124,34 @@ pub enum TimelinePosition {
 }
 
 impl TimelinePosition {
-    /// Move to next position (towards newer: -16 → ... → -1 → Wip → FullDiff → Browse)
-    pub fn next(self) -> Self {
+    /// Move to next position (towards newer: -16 → ... → -1 → ~N → ... → ~1 → Wip → SinceReview → FullDiff → Browse)
+    pub fn next(self, checkpoints: usize) -> Self {
         match self {
+            Self::CommitDiff(1) if checkpoints > 0 => Self::Checkpoint(checkpoints),
             Self::CommitDiff(1) => Self::Wip,
             Self::CommitDiff(n) => Self::CommitDiff(n - 1),
-            Self::Wip => Self::FullDiff,
+            Self::Checkpoint(1) => Self::Wip,
+            Self::Checkpoint(n) => Self::Checkpoint(n - 1),
+            Self::Wip => Self::SinceReview,
+            Self::SinceReview => Self::FullDiff,
             Self::FullDiff => Self::Browse,
             Self::Browse => Self::Browse, // Can't go newer than browse
         }
     }
 
-    /// Move to previous position (towards older: Browse → FullDiff → Wip → -1 → ... → -16)
-    pub fn prev(self, max_commits: usize) -> Self {
+    /// Move to previous position (towards older: Browse → FullDiff → SinceReview → Wip → ~1 → ... → ~N → -1 → ... → -16)
+    pub fn prev(self, max_commits: usize, checkpoints: usize) -> Self {
         match self {
             Self::Browse => Self::FullDiff,
-            Self::FullDiff => Self::Wip,
-            Self::Wip => {
+            Self::FullDiff => Self::SinceReview,
+            Self::SinceReview => Self::Wip,
+            Self::Wip if checkpoints > 0 => Self::Checkpoint(1),
+            Self::Checkpoint(n) if n < checkpoints => Self::Checkpoint(n + 1),
+            Self::Wip | Self::Checkpoint(_) => {
                 if max_commits > 0 {
                     Self::CommitDiff(1)
                 } else {
-                    Self::Wip
+                    self
                 }
             }
             Self::CommitDiff(n) if n < max_commits && n < 16 => Self::CommitDiff(n + 1),
@@ -152,53 +197,93 @@ mod tests {
         assert!(EntryType::IgnoredDir.is_dir());
     }
 
+    // --- LineMap ---
+
+    #[test]
+    fn line_map_shifts_past_insertions() {
+        // Two lines inserted after line 3
+        let map = LineMap::new(vec![(3, 0, 4, 2)]);
+        assert_eq!(map.map(3), Some(3));
+        assert_eq!(map.map(4), Some(6));
+    }
+
+    #[test]
+    fn line_map_drops_changed_lines() {
+        // Lines 2-3 deleted, line 7 modified
+        let map = LineMap::new(vec![(2, 2, 1, 0), (7, 1, 5, 1)]);
+        assert_eq!(map.map(1), Some(1));
+        assert_eq!(map.map(2), None);
+        assert_eq!(map.map(3), None);
+        assert_eq!(map.map(4), Some(2));
+        assert_eq!(map.map(7), None);
+        assert_eq!(map.map(8), Some(6));
+    }
+
+    #[test]
+    fn line_map_identity_without_hunks() {
+        assert_eq!(LineMap::default().map(42), Some(42));
+    }
+
     // --- TimelinePosition::next ---
 
     #[test]
     fn timeline_next_full_traversal() {
         let pos = TimelinePosition::CommitDiff(3);
-        let pos = pos.next(); // CommitDiff(2)
+        let pos = pos.next(0); // CommitDiff(2)
         assert_eq!(pos, TimelinePosition::CommitDiff(2));
-        let pos = pos.next(); // CommitDiff(1)
+        let pos = pos.next(0); // CommitDiff(1)
         assert_eq!(pos, TimelinePosition::CommitDiff(1));
-        let pos = pos.next(); // Wip
+        let pos = pos.next(0); // Wip
         assert_eq!(pos, TimelinePosition::Wip);
-        let pos = pos.next(); // FullDiff
+        let pos = pos.next(0); // SinceReview
+        assert_eq!(pos, TimelinePosition::SinceReview);
+        let pos = pos.next(0); // FullDiff
         assert_eq!(pos, TimelinePosition::FullDiff);
-        let pos = pos.next(); // Browse
+        let pos = pos.next(0); // Browse
         assert_eq!(pos, TimelinePosition::Browse);
-        let pos = pos.next(); // stays Browse
+        let pos = pos.next(0); // stays Browse
         assert_eq!(pos, TimelinePosition::Browse);
     }
 
+    #[test]
+    fn timeline_next_steps_through_checkpoints() {
+        let pos = TimelinePosition::CommitDiff(1).next(2);
+        assert_eq!(pos, TimelinePosition::Checkpoint(2));
+        let pos = pos.next(2);
+        assert_eq!(pos, TimelinePosition::Checkpoint(1));
+        assert_eq!(pos.next(2), TimelinePosition::Wip);
+    }
+
     // --- TimelinePosition::prev ---
 
     #[test]
     fn timeline_prev_full_traversal() {
         let pos = TimelinePosition::Browse;
-        let pos = pos.prev(3);
+        let pos = pos.prev(3, 0);
         assert_eq!(pos, TimelinePosition::FullDiff);
-        let pos = pos.prev(3);
+        let pos = pos.prev(3, 0);
+        assert_eq!(pos, TimelinePosition::SinceReview);
+        let pos = pos.prev(3, 0);
         assert_eq!(pos, TimelinePosition::Wip);
-        let pos = pos.prev(3);
+        let pos = pos.prev(3, 0);
         assert_eq!(pos, TimelinePosition::CommitDiff(1));
-        let pos = pos.prev(3);
+        let pos = pos.prev(3, 0);
         assert_eq!(pos, TimelinePosition::CommitDiff(2));
-        let pos = pos.prev(3);
+        let pos = pos.prev(3, 0);
         assert_eq!(pos, TimelinePosition::CommitDiff(3));
-        let pos = pos.prev(3); // capped at max_commits
+        let pos = pos.prev(3, 0); // capped at max_commits
         assert_eq!(pos, TimelinePosition::CommitDiff(3));
     }
 
     #[test]
     fn timeline_prev_no_commits() {
-        let pos = TimelinePosition::Wip.prev(0);
+        let pos = TimelinePosition::Wip.prev(0, 0);
         assert_eq!(pos, TimelinePosition::Wip); // can't go further
     }
 
     #[test]
     fn timeline_prev_capped_at_16() {
-        let pos = TimelinePosition::CommitDiff(16).prev(100);
+        let pos = TimelinePosition::CommitDiff(16).prev(100, 0);
         assert_eq!(pos, TimelinePosition::CommitDiff(16)); // 16 is hard max
     }
 
@@ -206,4 +291,14 @@ mod tests {
     fn timeline_default_is_full_diff() {
         assert_eq!(TimelinePosition::default(), TimelinePosition::FullDiff);
     }
+
+    #[test]
+    fn timeline_prev_steps_through_checkpoints() {
+        let pos = TimelinePosition::Wip.prev(1, 2);
+        assert_eq!(pos, TimelinePosition::Checkpoint(1));
+        let pos = pos.prev(1, 2);
+        assert_eq!(pos, TimelinePosition::Checkpoint(2));
+        assert_eq!(pos.prev(1, 2), TimelinePosition::CommitDiff(1));
+        assert_eq!(pos.prev(0, 2), pos);
+    }
 }
@@ -0,0 +1,258 @@
+//! Native GitHub API client
+//!
+//! Talks to the REST and GraphQL APIs directly over HTTPS when a token is
+//! available, instead of spawning a `gh` process per request. The token comes
+//! from `GH_TOKEN`, `GITHUB_TOKEN` (`GH_ENTERPRISE_TOKEN` or
+//! `GITHUB_ENTERPRISE_TOKEN` for GitHub Enterprise hosts) or the gh CLI's
+//! hosts file.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::forge::{read_json_response, read_text_response, RemoteUrl};
+
+/// Environment variable overriding the REST API base URL (e.g. a local mock server)
+pub const API_URL_ENV: &str = "TIMECOP_GITHUB_API_URL";
+
+const DEFAULT_API_URL: &str = "https://api.github.com";
+pub const DEFAULT_HOST: &str = "github.com";
+
+/// HTTPS client for one repository
+pub struct ApiClient {
+    agent: ureq::Agent,
+    api_url: String,
+    token: String,
+    owner: String,
+    repo: String,
+}
+
+impl ApiClient {
+    pub fn new(api_url: &str, token: &str, owner: &str, repo: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(Duration::from_secs(30)).build(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        }
+    }
+
+    /// Client for a repository on github.com or a GitHub Enterprise host
+    /// (API at `https://<host>/api/v3`). None when no token is found.
+    pub fn for_remote(remote: &RemoteUrl) -> Option<Self> {
+        let configured_url = std::env::var(API_URL_ENV).ok().filter(|u| !u.is_empty());
+        let (owner, repo) = remote.owner_repo()?;
+        let token = find_token(&remote.host)?;
+        let api_url = configured_url.unwrap_or_else(|| api_url(&remote.host));
+        Some(Self::new(&api_url, &token, owner, repo))
+    }
+
+    /// Send a REST request; `{owner}` and `{repo}` in the endpoint are filled in
+    pub fn rest(&self, method: &str, endpoint: &str, body: Option<&Value>) -> Result<Value> {
+        let url = format!("{}/{}", self.api_url, self.expand(endpoint));
+        self.send(method, &url, body)
+    }
+
+    /// GET a plain text resource (job logs); redirects to blob storage are followed
+    pub fn rest_text(&self, endpoint: &str) -> Result<String> {
+        let url = format!("{}/{}", self.api_url, self.expand(endpoint));
+        read_text_response(self.request("GET", &url).call(), "GitHub")
+    }
+
+    /// Run a GraphQL query; `{owner}` and `{repo}` string variables are filled in
+    pub fn graphql(&self, query: &str, variables: &[(&str, Value)]) -> Result<Value> {
+        let variables: serde_json::Map<String, Value> = variables
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    Value::String(s) => Value::String(self.expand(s)),
+                    other => other.clone(),
+                };
+                (name.to_string(), value)
+            })
+            .collect();
+        let body = serde_json::json!({ "query": query, "variables": variables });
+        self.send("POST", &graphql_url(&self.api_url), Some(&body))
+    }
+
+    fn expand(&self, text: &str) -> String {
+        text.replace("{owner}", &self.owner).replace("{repo}", &self.repo)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent
+            .request(method, url)
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .set("Accept", "application/vnd.github+json")
+            .set("X-GitHub-Api-Version", "2022-11-28")
+            .set("User-Agent", "timecop")
+    }
+
+    fn send(&self, method: &str, url: &str, body: Option<&Value>) -> Result<Value> {
+        let request = self.request(method, url);
+        let response = match body {
+            Some(body) => request.send_json(body),
+            None => request.call(),
+        };
+
+        read_json_response(response, "GitHub")
+    }
+}
+
+/// REST API base URL for a host: api.github.com, or the Enterprise API path
+fn api_url(host: &str) -> String {
+    if host == DEFAULT_HOST {
+        DEFAULT_API_URL.to_string()
+    } else {
+        format!("https://{}/api/v3", host)
+    }
+}
+
+/// GraphQL endpoint for a REST base URL (GitHub Enterprise serves it from /api/graphql)
+fn graphql_url(api_url: &str) -> String {
+    match api_url.strip_suffix("/api/v3") {
+        Some(root) => format!("{}/api/graphql", root),
+        None => format!("{}/graphql", api_url),
+    }
+}
+
+/// Token from the environment, then from the gh CLI's hosts file. Like gh,
+/// Enterprise hosts use their own variables.
+fn find_token(host: &str) -> Option<String> {
+    let vars = if host == DEFAULT_HOST {
+        ["GH_TOKEN", "GITHUB_TOKEN"]
+    } else {
+        ["GH_ENTERPRISE_TOKEN", "GITHUB_ENTERPRISE_TOKEN"]
+    };
+    vars.iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|token| !token.is_empty())
+        .or_else(|| {
+            let content = std::fs::read_to_string(gh_config_dir()?.join("hosts.yml")).ok()?;
+            hosts_token(&content, host)
+        })
+}
+
+fn gh_config_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var("GH_CONFIG_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if let Ok(dir) = std::env::var("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(dir).join("gh"));
+    }
+    std::env::var("HOME").ok().map(|home| PathBuf::from(home).join(".config").join("gh"))
+}
+
+/// Read `oauth_token` for a host from gh's hosts.yml. Prefers the host-level
+/// token (the active account) over per-user entries. Tokens kept in the
+/// system keyring aren't in the file, in which case this returns None.
+fn hosts_token(content: &str, host: &str) -> Option<String> {
+    let mut in_host = false;
+    let mut best: Option<(usize, String)> = None;
+
+    for line in content.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            in_host = line.trim_end().trim_end_matches(':').trim_matches('"') == host;
+            continue;
+        }
+        if !in_host {
+            continue;
+        }
+        if let Some(token) = line.trim().strip_prefix("oauth_token:") {
+            let token = token.trim().trim_matches('"').trim_matches('\'').to_string();
+            if !token.is_empty() && best.as_ref().is_none_or(|(i, _)| indent < *i) {
+                best = Some((indent, token));
+            }
+        }
+    }
+
+    best.map(|(_, token)| token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::test_server::serve_once;
+
+    // --- requests against a mock server ---
+
+    #[test]
+    fn rest_expands_repo_and_sends_token() {
+        let (url, server) = serve_once("200 OK", r#"{"number": 7}"#);
+        let client = ApiClient::new(&url, "secret", "octo", "cat");
+
+        let json = client.rest("GET", "repos/{owner}/{repo}/pulls/7", None).unwrap();
+        assert_eq!(json["number"], 7);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /repos/octo/cat/pulls/7 HTTP/1.1"));
+        assert!(request.to_ascii_lowercase().contains("authorization: bearer secret"));
+    }
+
+    #[test]
+    fn rest_errors_carry_status_and_message() {
+        let (url, server) = serve_once("404 Not Found", r#"{"message": "Not Found"}"#);
+        let client = ApiClient::new(&url, "t", "o", "r");
+
+        let err = client.rest("GET", "repos/{owner}/{repo}/pulls/1", None).unwrap_err();
+        assert_eq!(err.to_string(), "GitHub API returned 404: Not Found");
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn graphql_posts_query_with_expanded_variables() {
+        let (url, server) = serve_once("200 OK", r#"{"data": {}}"#);
+        let client = ApiClient::new(&url, "t", "octo", "cat");
+
+        client.graphql("query { viewer { login } }", &[("owner", Value::from("{owner}"))]).unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /graphql HTTP/1.1"));
+        assert!(request.contains(r#""owner":"octo""#));
+    }
+
+    #[test]
+    fn rest_text_returns_raw_body() {
+        let (url, server) = serve_once("200 OK", "step 1\nerror: boom\n");
+        let client = ApiClient::new(&url, "t", "octo", "cat");
+
+        let log = client.rest_text("repos/{owner}/{repo}/actions/jobs/5/logs").unwrap();
+        assert_eq!(log, "step 1\nerror: boom\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /repos/octo/cat/actions/jobs/5/logs HTTP/1.1"));
+    }
+
+    // --- configuration ---
+
+    #[test]
+    fn graphql_url_for_enterprise_and_public_api() {
+        assert_eq!(api_url("github.com"), "https://api.github.com");
+        assert_eq!(api_url("ghe.corp"), "https://ghe.corp/api/v3");
+        assert_eq!(graphql_url("https://api.github.com"), "https://api.github.com/graphql");
+        assert_eq!(graphql_url("https://ghe.corp/api/v3"), "https://ghe.corp/api/graphql");
+    }
+
+    #[test]
+    fn hosts_token_prefers_active_account() {
+        let hosts = "\
+github.com:
+    users:
+        alt:
+            oauth_token: gho_alt
+    oauth_token: gho_active
+    user: me
+ghe.corp:
+    oauth_token: gho_other
+";
+        assert_eq!(hosts_token(hosts, "github.com").as_deref(), Some("gho_active"));
+        assert_eq!(hosts_token(hosts, "ghe.corp").as_deref(), Some("gho_other"));
+        assert_eq!(hosts_token("github.com:\n    user: me\n", "github.com"), None);
+    }
+}
@@ -1,51 +1,91 @@
+//! GitHub forge: native REST/GraphQL when a token is available, gh CLI otherwise
+//!
+//! The repository is the base remote's (`upstream` in a fork workflow, else
+//! `origin`), on github.com or a GitHub Enterprise host.
+
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
-use std::process::Command;
-
-/// PR information from GitHub
-#[derive(Debug, Clone, Default)]
-pub struct PrInfo {
-    pub number: u64,
-    pub title: String,
-    pub body: String,
-    pub author: String,
-    pub state: String,
-    pub url: String,
-    pub base_branch: String,
-    pub reviews: Vec<Review>,
-    pub comments: Vec<Comment>,
-    pub file_comments: HashMap<String, Vec<Comment>>,
-}
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
 
-#[derive(Debug, Clone)]
-pub struct Review {
-    pub author: String,
-    pub state: String,
-    pub body: String,
-}
+use crate::drafts::DraftComment;
+use crate::forge::{
+    base_remote, clean_log, group_into_threads, CheckRun, CheckStatus, Comment, Forge, HeadRevision, MergeMethod, MergeStatus,
+    MetadataKind, NewPr, PrInfo, PrSummary, RemoteUrl, Review, ReviewThread, MAX_LISTED_PRS,
+};
+
+mod api;
+
+use api::{ApiClient, DEFAULT_HOST};
+
+/// Items per page for paginated REST endpoints (GitHub's maximum)
+const PAGE_SIZE: usize = 100;
 
-#[derive(Debug, Clone)]
-pub struct Comment {
-    pub author: String,
-    pub body: String,
-    pub line: Option<u32>,
-    pub original_line: Option<u32>,
-    pub side: Option<String>, // "LEFT" or "RIGHT"
+/// Resolution state for a thread from GraphQL: (node ID, is resolved)
+type ThreadStates = HashMap<u64, (String, bool)>;
+
+/// Review comments for a PR: (comments without a path, threads grouped by file)
+type CommentsByFile = (Vec<Comment>, HashMap<String, Vec<ReviewThread>>);
+
+/// How API requests reach GitHub
+enum Transport {
+    /// Direct HTTPS requests with a token
+    Native(ApiClient),
+    /// `gh api` subprocesses, when no token is configured
+    Gh,
 }
 
-/// GitHub client using gh CLI
+/// GitHub client: native API when a token is available, gh CLI otherwise
 pub struct GitHubClient {
     available: Option<bool>,
+    transport: Transport,
+    /// Repository PRs are read from; None leaves gh to guess
+    remote: Option<RemoteUrl>,
 }
 
 impl GitHubClient {
-    pub fn new() -> Self {
+    pub fn new(repo: &Path) -> Self {
         // Lazy check - don't spawn process at startup
-        Self { available: None }
+        let remote = base_remote(repo);
+        let transport = match remote.as_ref().and_then(ApiClient::for_remote) {
+            Some(api) => Transport::Native(api),
+            None => Transport::Gh,
+        };
+        Self { available: None, transport, remote }
+    }
+
+    /// `gh api` aimed at the repository's host
+    fn gh_api(&self) -> Command {
+        let mut cmd = Command::new("gh");
+        cmd.arg("api");
+        if let Some(remote) = self.remote.as_ref().filter(|r| r.host != DEFAULT_HOST) {
+            cmd.args(["--hostname", &remote.host]);
+        }
+        cmd
+    }
+
+    /// `--repo HOST/OWNER/REPO` for gh subcommands, so gh doesn't pick a remote itself
+    fn gh_repo_args(&self) -> Vec<String> {
+        match &self.remote {
+            Some(remote) => vec!["--repo".to_string(), format!("{}/{}", remote.host, remote.path)],
+            None => Vec::new(),
+        }
     }
 
-    pub fn is_available(&mut self) -> bool {
+    /// Fill in `{owner}` and `{repo}` for gh (left to gh without a known remote)
+    fn expand(&self, text: &str) -> String {
+        match self.remote.as_ref().and_then(|r| r.owner_repo()) {
+            Some((owner, repo)) => text.replace("{owner}", owner).replace("{repo}", repo),
+            None => text.to_string(),
+        }
+    }
+
+    fn gh_available(&mut self) -> bool {
+        if matches!(self.transport, Transport::Native(_)) {
+            return true;
+        }
         if self.available.is_none() {
             let result = Command::new("gh")
                 .arg("--version")
@@ -57,72 +97,188 @@ impl GitHubClient {
         self.available.unwrap_or(false)
     }
 
-    fn get_reviews(&self, pr_number: u64) -> Result<Vec<Review>> {
-        let output = Command::new("gh")
-            .args([
-                "pr", "view",
-                &pr_number.to_string(),
-                "--json", "reviews",
-            ])
-            .output()?;
+    /// REST request; `{owner}` and `{repo}` in the endpoint refer to the current repository
+    fn rest(&self, method: &str, endpoint: &str, body: Option<&serde_json::Value>) -> Result<serde_json::Value> {
+        match &self.transport {
+            Transport::Native(api) => api.rest(method, endpoint, body),
+            Transport::Gh => {
+                let mut cmd = self.gh_api();
+                cmd.args(["--method", method, &self.expand(endpoint)]);
+                run_gh(cmd, body)
+            }
+        }
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            log::warn!("Failed to fetch reviews for PR #{}: {}", pr_number, stderr);
-            return Ok(Vec::new());
+    /// GET every page of a list endpoint, `PAGE_SIZE` items at a time, until
+    /// a short page shows there are no more
+    fn rest_pages(&self, endpoint: &str) -> Result<serde_json::Value> {
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        for page in 1.. {
+            let json = self.rest(
+                "GET",
+                &format!("{}{}per_page={}&page={}", endpoint, separator, PAGE_SIZE, page),
+                None,
+            )?;
+            let serde_json::Value::Array(page_items) = json else {
+                anyhow::bail!("Expected a list from {}", endpoint);
+            };
+            let last = page_items.len() < PAGE_SIZE;
+            items.extend(page_items);
+            if last {
+                break;
+            }
         }
+        Ok(serde_json::Value::Array(items))
+    }
 
-        #[derive(Deserialize)]
-        struct ReviewsResponse {
-            reviews: Vec<ReviewData>,
+    /// GET a plain text resource such as a job log
+    fn rest_text(&self, endpoint: &str) -> Result<String> {
+        match &self.transport {
+            Transport::Native(api) => api.rest_text(endpoint),
+            Transport::Gh => {
+                let mut cmd = self.gh_api();
+                cmd.arg(self.expand(endpoint));
+                let output = run_gh_output(cmd, None)?;
+                Ok(String::from_utf8_lossy(&output).into_owned())
+            }
+        }
+    }
+
+    /// GraphQL request; `{owner}` and `{repo}` string variables refer to the current repository
+    fn graphql(&self, query: &str, variables: &[(&str, serde_json::Value)]) -> Result<serde_json::Value> {
+        let json = match &self.transport {
+            Transport::Native(api) => api.graphql(query, variables)?,
+            Transport::Gh => {
+                let mut cmd = self.gh_api();
+                cmd.arg("graphql");
+                for (name, value) in variables {
+                    match value {
+                        // -f sends raw strings; -F also fills in {owner}/{repo}
+                        serde_json::Value::String(s) if s != "{owner}" && s != "{repo}" => {
+                            cmd.args(["-f", &format!("{}={}", name, s)])
+                        }
+                        serde_json::Value::String(s) if self.remote.is_some() => {
+                            cmd.args(["-f", &format!("{}={}", name, self.expand(s))])
+                        }
+                        serde_json::Value::String(s) => cmd.args(["-F", &format!("{}={}", name, s)]),
+                        other => cmd.args(["-F", &format!("{}={}", name, other)]),
+                    };
+                }
+                cmd.args(["-f", &format!("query={}", query)]);
+                run_gh(cmd, None)?
+            }
+        };
+
+        if let Some(message) = json.pointer("/errors/0/message").and_then(|m| m.as_str()) {
+            anyhow::bail!("GitHub GraphQL error: {}", message);
         }
+        Ok(json)
+    }
+
+    /// GraphQL node ID of a PR, for mutations
+    fn pr_node_id(&self, pr_number: u64) -> Result<String> {
+        let pr = self
+            .rest("GET", &format!("repos/{{owner}}/{{repo}}/pulls/{}", pr_number), None)
+            .context("Failed to fetch PR")?;
+        pr["node_id"].as_str().map(str::to_string).context("No node id for PR")
+    }
+
+    /// Get current GitHub user login
+    fn get_current_user(&self) -> Option<String> {
+        let json = self.rest("GET", "user", None).ok()?;
+        json.get("login").and_then(|l| l.as_str()).map(str::to_string)
+    }
+
+    fn get_reviews(&self, pr_number: u64) -> Result<Vec<Review>> {
+        let json = self.rest_pages(&format!("repos/{{owner}}/{{repo}}/pulls/{}/reviews", pr_number))?;
 
         #[derive(Deserialize)]
         struct ReviewData {
-            author: AuthorData,
+            id: u64,
+            user: UserData,
             state: String,
+            #[serde(default)]
             body: String,
+            #[serde(default)]
+            submitted_at: Option<String>,
+            #[serde(default)]
+            commit_id: Option<String>,
         }
 
         #[derive(Deserialize)]
-        struct AuthorData {
+        struct UserData {
             login: String,
         }
 
-        let resp: ReviewsResponse = serde_json::from_slice(&output.stdout)?;
-        Ok(resp.reviews
+        let reviews: Vec<ReviewData> = serde_json::from_value(json).context("Failed to parse reviews")?;
+        Ok(reviews
             .into_iter()
+            // Pending reviews aren't visible to anyone else yet
+            .filter(|r| r.state != "PENDING")
             .filter(|r| !r.state.is_empty() || !r.body.is_empty())
             .map(|r| Review {
-                author: r.author.login,
+                id: r.id,
+                author: r.user.login,
                 state: r.state,
                 body: r.body,
+                submitted_at: r.submitted_at.unwrap_or_default(),
+                commit_sha: r.commit_id.unwrap_or_default(),
             })
             .collect())
     }
 
-    fn get_comments(&self, pr_number: u64) -> Result<(Vec<Comment>, HashMap<String, Vec<Comment>>)> {
-        let output = Command::new("gh")
-            .args([
-                "api",
-                &format!("repos/{{owner}}/{{repo}}/pulls/{}/comments", pr_number),
-            ])
-            .output()?;
+    /// Fetch comments from the main PR conversation (issues API)
+    fn get_conversation_comments(&self, pr_number: u64) -> Result<Vec<Comment>> {
+        let json = self.rest_pages(&format!("repos/{{owner}}/{{repo}}/issues/{}/comments", pr_number))?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            log::warn!("Failed to fetch comments for PR #{}: {}", pr_number, stderr);
-            return Ok((Vec::new(), HashMap::new()));
+        #[derive(Deserialize)]
+        struct IssueCommentData {
+            id: u64,
+            user: UserData,
+            #[serde(default)]
+            body: String,
+            #[serde(default)]
+            created_at: String,
         }
 
+        #[derive(Deserialize)]
+        struct UserData {
+            login: String,
+        }
+
+        let comments: Vec<IssueCommentData> =
+            serde_json::from_value(json).context("Failed to parse conversation comments")?;
+        Ok(comments
+            .into_iter()
+            .map(|c| Comment {
+                id: c.id,
+                author: c.user.login,
+                body: c.body,
+                created_at: c.created_at,
+                conversation: true,
+                ..Default::default()
+            })
+            .collect())
+    }
+
+    fn get_comments(&self, pr_number: u64) -> Result<CommentsByFile> {
+        let json = self.rest_pages(&format!("repos/{{owner}}/{{repo}}/pulls/{}/comments", pr_number))?;
+
         #[derive(Deserialize)]
         struct CommentData {
+            id: u64,
+            in_reply_to_id: Option<u64>,
             user: UserData,
             body: String,
+            #[serde(default)]
+            created_at: String,
             path: Option<String>,
             line: Option<u32>,
             original_line: Option<u32>,
             side: Option<String>,
+            commit_id: Option<String>,
+            original_commit_id: Option<String>,
         }
 
         #[derive(Deserialize)]
@@ -130,19 +286,25 @@ impl GitHubClient {
             login: String,
         }
 
-        let comments: Vec<CommentData> = serde_json::from_slice(&output.stdout)
-            .unwrap_or_default();
+        let comments: Vec<CommentData> =
+            serde_json::from_value(json).context("Failed to parse review comments")?;
 
         let mut general_comments = Vec::new();
         let mut file_comments: HashMap<String, Vec<Comment>> = HashMap::new();
 
         for c in comments {
             let comment = Comment {
+                id: c.id,
+                in_reply_to_id: c.in_reply_to_id,
                 author: c.user.login,
                 body: c.body,
+                created_at: c.created_at,
                 line: c.line,
                 original_line: c.original_line,
                 side: c.side,
+                commit_id: c.commit_id,
+                original_commit_id: c.original_commit_id,
+                conversation: false,
             };
 
             if let Some(path) = c.path {
@@ -152,152 +314,436 @@ impl GitHubClient {
             }
         }
 
-        Ok((general_comments, file_comments))
+        let file_threads = file_comments
+            .into_iter()
+            .map(|(path, comments)| (path, group_into_threads(comments)))
+            .collect();
+
+        Ok((general_comments, file_threads))
+    }
+
+    /// Check runs on a commit (GitHub Actions jobs and third-party checks)
+    fn get_check_runs(&self, sha: &str) -> Result<Vec<CheckRun>> {
+        let json = self.rest(
+            "GET",
+            &format!("repos/{{owner}}/{{repo}}/commits/{}/check-runs?per_page=100", sha),
+            None,
+        )?;
+        Ok(parse_check_runs(&json))
+    }
+
+    /// Fetch thread resolution state via GraphQL, keyed by root comment database ID
+    fn get_thread_states(&self, pr_number: u64) -> Result<ThreadStates> {
+        const QUERY: &str = "query($owner: String!, $name: String!, $number: Int!, $cursor: String) {
+            repository(owner: $owner, name: $name) {
+                pullRequest(number: $number) {
+                    reviewThreads(first: 100, after: $cursor) {
+                        pageInfo { hasNextPage endCursor }
+                        nodes { id isResolved comments(first: 1) { nodes { databaseId } } }
+                    }
+                }
+            }
+        }";
+
+        let mut states = ThreadStates::new();
+        let mut cursor = serde_json::Value::Null;
+        loop {
+            let json = self.graphql(
+                QUERY,
+                &[
+                    ("owner", "{owner}".into()),
+                    ("name", "{repo}".into()),
+                    ("number", pr_number.into()),
+                    ("cursor", cursor),
+                ],
+            )?;
+            states.extend(parse_thread_states(&json));
+
+            let page_info = json.pointer("/data/repository/pullRequest/reviewThreads/pageInfo");
+            let has_next = page_info
+                .and_then(|p| p.get("hasNextPage"))
+                .and_then(|h| h.as_bool())
+                .unwrap_or(false);
+            match page_info.and_then(|p| p.get("endCursor")).and_then(|c| c.as_str()) {
+                Some(end) if has_next => cursor = end.into(),
+                _ => break,
+            }
+        }
+        Ok(states)
     }
 }
 
-/// Status of a single CI check
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum CheckStatus {
-    Pending,
-    Success,
-    Failure,
+/// Run a `gh` command, sending `body` as JSON on stdin, and parse its JSON output
+fn run_gh(cmd: Command, body: Option<&serde_json::Value>) -> Result<serde_json::Value> {
+    let stdout = run_gh_output(cmd, body)?;
+    if stdout.iter().all(|b| b.is_ascii_whitespace()) {
+        return Ok(serde_json::Value::Null);
+    }
+    serde_json::from_slice(&stdout).context("Failed to parse gh output")
 }
 
-/// Summary of a PR for listing
-#[derive(Debug, Clone)]
-pub struct PrSummary {
-    pub number: u64,
-    pub title: String,
-    pub author: String,
-    pub branch: String,
-    pub base_branch: String,
-    pub updated_at: String,
-    pub review_requested: bool, // true if current user is requested reviewer
-    pub checks: Vec<CheckStatus>,
+/// Run a `gh` command, sending `body` as JSON on stdin, and return its raw output
+fn run_gh_output(mut cmd: Command, body: Option<&serde_json::Value>) -> Result<Vec<u8>> {
+    if body.is_some() {
+        cmd.args(["--input", "-"]);
+    }
+
+    let mut child = cmd
+        .stdin(if body.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run gh")?;
+
+    if let (Some(body), Some(mut stdin)) = (body, child.stdin.take()) {
+        stdin
+            .write_all(body.to_string().as_bytes())
+            .context("Failed to send request to gh")?;
+    }
+
+    let output = child.wait_with_output().context("Failed to run gh")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{}", stderr.trim());
+    }
+    Ok(output.stdout)
 }
 
-impl GitHubClient {
-    /// Get PR info by number
-    pub fn get_pr_by_number(&mut self, pr_number: u64) -> Result<Option<PrInfo>> {
-        if !self.is_available() {
-            return Ok(None);
+/// Extract thread states from a reviewThreads GraphQL response
+fn parse_thread_states(json: &serde_json::Value) -> ThreadStates {
+    let nodes = json
+        .pointer("/data/repository/pullRequest/reviewThreads/nodes")
+        .and_then(|n| n.as_array());
+
+    let mut states = HashMap::new();
+    for node in nodes.into_iter().flatten() {
+        let root_id = node
+            .pointer("/comments/nodes/0/databaseId")
+            .and_then(|id| id.as_u64());
+        let node_id = node.get("id").and_then(|id| id.as_str());
+        let resolved = node.get("isResolved").and_then(|r| r.as_bool()).unwrap_or(false);
+        if let (Some(root_id), Some(node_id)) = (root_id, node_id) {
+            states.insert(root_id, (node_id.to_string(), resolved));
         }
+    }
+    states
+}
 
-        let output = Command::new("gh")
-            .args([
-                "pr",
-                "view",
-                &pr_number.to_string(),
-                "--json",
-                "number,title,body,author,state,url,baseRefName",
-            ])
-            .output()
-            .context("Failed to run gh pr view")?;
+/// Map a CheckRun conclusion or StatusContext state
+fn check_status(status: &str) -> CheckStatus {
+    match status.to_uppercase().as_str() {
+        "FAILURE" | "TIMED_OUT" | "CANCELLED" | "ERROR" => CheckStatus::Failure,
+        "SUCCESS" | "NEUTRAL" | "SKIPPED" => CheckStatus::Success,
+        _ => CheckStatus::Pending,
+    }
+}
 
-        if !output.status.success() {
-            return Ok(None);
-        }
+/// Parse a check-runs REST response. Only GitHub Actions jobs have logs the API serves
+/// (their check run id is the job id).
+fn parse_check_runs(json: &serde_json::Value) -> Vec<CheckRun> {
+    let runs = json.get("check_runs").and_then(|r| r.as_array());
+    let str_at = |run: &serde_json::Value, key: &str| {
+        run.get(key).and_then(|v| v.as_str()).unwrap_or("").to_string()
+    };
+
+    runs.into_iter()
+        .flatten()
+        .map(|run| {
+            let status = if str_at(run, "status") == "completed" {
+                check_status(&str_at(run, "conclusion"))
+            } else {
+                CheckStatus::Pending
+            };
+            let actions = run.pointer("/app/slug").and_then(|s| s.as_str()) == Some("github-actions");
+            CheckRun {
+                name: str_at(run, "name"),
+                status,
+                started_at: str_at(run, "started_at"),
+                completed_at: str_at(run, "completed_at"),
+                url: str_at(run, "html_url"),
+                job_id: run.get("id").and_then(|id| id.as_u64()).filter(|_| actions),
+            }
+        })
+        .collect()
+}
 
-        #[derive(Deserialize)]
-        struct PrBasic {
-            number: u64,
-            title: String,
-            body: String,
-            author: Author,
-            state: String,
-            url: String,
-            #[serde(rename = "baseRefName", default)]
-            base_ref_name: String,
+/// Mergeability, review decision and required checks of one PR
+const MERGE_STATUS_QUERY: &str = "query($owner: String!, $name: String!, $number: Int!) {
+    repository(owner: $owner, name: $name) {
+        pullRequest(number: $number) {
+            state isDraft mergeable mergeStateStatus reviewDecision
+            commits(last: 1) { nodes { commit { statusCheckRollup { contexts(first: 100) { nodes {
+                ... on CheckRun { name status conclusion isRequired(pullRequestNumber: $number) }
+                ... on StatusContext { context state isRequired(pullRequestNumber: $number) }
+            } } } } } }
         }
-
-        #[derive(Deserialize)]
-        struct Author {
-            login: String,
+    }
+}";
+
+/// Parse the merge status GraphQL response into requirements and blockers
+fn parse_merge_status(json: &serde_json::Value) -> Option<MergeStatus> {
+    let pr = json.pointer("/data/repository/pullRequest")?;
+    let str_at = |key: &str| pr.get(key).and_then(|v| v.as_str()).unwrap_or("");
+
+    let required_checks: Vec<(String, CheckStatus)> = pr
+        .pointer("/commits/nodes/0/commit/statusCheckRollup/contexts/nodes")
+        .and_then(|n| n.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|c| c.get("isRequired").and_then(|r| r.as_bool()).unwrap_or(false))
+        .map(|c| {
+            let name = c.get("name").or_else(|| c.get("context")).and_then(|n| n.as_str()).unwrap_or("");
+            let status = c.get("conclusion").or_else(|| c.get("state")).and_then(|s| s.as_str()).unwrap_or("");
+            (name.to_string(), check_status(status))
+        })
+        .collect();
+
+    let mergeable = match str_at("mergeable") {
+        "MERGEABLE" => Some(true),
+        "CONFLICTING" => Some(false),
+        _ => None,
+    };
+    let review_decision = Some(str_at("reviewDecision")).filter(|d| !d.is_empty()).map(str::to_string);
+
+    let mut blockers = Vec::new();
+    if str_at("state") != "OPEN" {
+        blockers.push(format!("PR is {}", str_at("state").to_lowercase()));
+    }
+    if pr.get("isDraft").and_then(|d| d.as_bool()).unwrap_or(false) {
+        blockers.push("PR is a draft".to_string());
+    }
+    if mergeable == Some(false) {
+        blockers.push("Merge conflicts with the base branch".to_string());
+    }
+    match review_decision.as_deref() {
+        Some("CHANGES_REQUESTED") => blockers.push("Changes requested".to_string()),
+        Some("REVIEW_REQUIRED") => blockers.push("Approving review required".to_string()),
+        _ => {}
+    }
+    for (name, status) in &required_checks {
+        match status {
+            CheckStatus::Failure => blockers.push(format!("Required check failed: {}", name)),
+            CheckStatus::Pending => blockers.push(format!("Required check pending: {}", name)),
+            CheckStatus::Success => {}
         }
+    }
+    match str_at("mergeStateStatus") {
+        "BEHIND" => blockers.push("Branch is behind the base branch".to_string()),
+        // Other branch protection rules (signed commits, merge queue, ...)
+        "BLOCKED" if blockers.is_empty() => blockers.push("Blocked by branch protection".to_string()),
+        _ => {}
+    }
 
-        let basic: PrBasic =
-            serde_json::from_slice(&output.stdout).context("Failed to parse PR JSON")?;
-
-        let mut pr_info = PrInfo {
-            number: basic.number,
-            title: basic.title,
-            body: basic.body,
-            author: basic.author.login,
-            state: basic.state,
-            url: basic.url,
-            base_branch: basic.base_ref_name,
-            ..Default::default()
-        };
+    Some(MergeStatus { mergeable, review_decision, required_checks, blockers })
+}
 
-        // Get reviews
-        if let Ok(reviews) = self.get_reviews(basic.number) {
-            pr_info.reviews = reviews;
+/// Open PRs with review requests and the latest commit's checks
+const PR_LIST_QUERY: &str = "query($owner: String!, $name: String!, $cursor: String) {
+    viewer { login }
+    repository(owner: $owner, name: $name) {
+        pullRequests(states: OPEN, first: 50, after: $cursor, orderBy: {field: UPDATED_AT, direction: DESC}) {
+            pageInfo { hasNextPage endCursor }
+            nodes {
+                number title headRefName baseRefName headRefOid updatedAt createdAt isDraft additions deletions
+                author { login }
+                headRepository { nameWithOwner }
+                reviews { totalCount }
+                comments { totalCount }
+                reviewThreads(first: 100) { totalCount nodes { comments { totalCount } } }
+                labels(first: 20) { nodes { name } }
+                reviewRequests(first: 20) { nodes { requestedReviewer { ... on User { login } } } }
+                commits(last: 1) { nodes { commit { statusCheckRollup { contexts(first: 100) {
+                    nodes { ... on CheckRun { conclusion } ... on StatusContext { state } }
+                } } } } }
+            }
         }
-
-        // Get comments
-        if let Ok((comments, file_comments)) = self.get_comments(basic.number) {
-            pr_info.comments = comments;
-            pr_info.file_comments = file_comments;
+    }
+}";
+
+const VIEWED_FILES_QUERY: &str = "query($owner: String!, $name: String!, $number: Int!, $cursor: String) {
+    repository(owner: $owner, name: $name) {
+        pullRequest(number: $number) {
+            files(first: 100, after: $cursor) {
+                pageInfo { hasNextPage endCursor }
+                nodes { path viewerViewedState }
+            }
+        }
+    }
+}";
+
+const FORCE_PUSHES_QUERY: &str = "query($owner: String!, $name: String!, $number: Int!) {
+    repository(owner: $owner, name: $name) {
+        pullRequest(number: $number) {
+            timelineItems(last: 50, itemTypes: [HEAD_REF_FORCE_PUSHED_EVENT]) {
+                nodes {
+                    ... on HeadRefForcePushedEvent { createdAt beforeCommit { oid } afterCommit { oid } }
+                }
+            }
         }
+    }
+}";
+
+/// Heads before and after each force-push, newest first
+fn parse_force_pushes(json: &serde_json::Value) -> Vec<HeadRevision> {
+    let events = json
+        .pointer("/data/repository/pullRequest/timelineItems/nodes")
+        .and_then(|n| n.as_array());
+    let mut revisions: Vec<HeadRevision> = Vec::new();
+    for event in events.into_iter().flatten().rev() {
+        let at = event.get("createdAt").and_then(|a| a.as_str()).unwrap_or_default();
+        for (pointer, note) in [("/afterCommit/oid", "force-pushed"), ("/beforeCommit/oid", "before force-push")] {
+            let Some(sha) = event.pointer(pointer).and_then(|o| o.as_str()) else {
+                continue;
+            };
+            if revisions.iter().all(|r| r.sha != sha) {
+                revisions.push(HeadRevision { sha: sha.to_string(), at: at.to_string(), note: note.to_string() });
+            }
+        }
+    }
+    revisions
+}
 
-        Ok(Some(pr_info))
+/// Paths marked VIEWED in a page of PR files (DISMISSED ones changed since)
+fn parse_viewed_files(files: &serde_json::Value) -> Vec<String> {
+    files
+        .pointer("/nodes")
+        .and_then(|n| n.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|f| f.get("viewerViewedState").and_then(|s| s.as_str()) == Some("VIEWED"))
+        .filter_map(|f| f.get("path").and_then(|p| p.as_str()).map(str::to_string))
+        .collect()
+}
+
+/// Parse the PR list GraphQL response
+fn parse_pr_list(json: &serde_json::Value) -> Vec<PrSummary> {
+    let viewer = json.pointer("/data/viewer/login").and_then(|l| l.as_str());
+    let nodes = json
+        .pointer("/data/repository/pullRequests/nodes")
+        .and_then(|n| n.as_array());
+    let str_at = |node: &serde_json::Value, path: &str| {
+        node.pointer(path).and_then(|v| v.as_str()).unwrap_or("").to_string()
+    };
+
+    nodes
+        .into_iter()
+        .flatten()
+        .filter_map(|p| {
+            let review_requested = p
+                .pointer("/reviewRequests/nodes")
+                .and_then(|n| n.as_array())
+                .into_iter()
+                .flatten()
+                .any(|r| viewer.is_some() && r.pointer("/requestedReviewer/login").and_then(|l| l.as_str()) == viewer);
+
+            let contexts = p
+                .pointer("/commits/nodes/0/commit/statusCheckRollup/contexts/nodes")
+                .and_then(|n| n.as_array());
+            let checks = contexts
+                .into_iter()
+                .flatten()
+                .map(|c| {
+                    // Use conclusion (CheckRun) or fall back to state (StatusContext)
+                    let status = c
+                        .get("conclusion")
+                        .or_else(|| c.get("state"))
+                        .and_then(|s| s.as_str())
+                        .unwrap_or("");
+                    check_status(status)
+                })
+                .collect();
+
+            let count = |path: &str| p.pointer(path).and_then(|n| n.as_u64()).unwrap_or(0);
+            let updated_at = str_at(p, "/updatedAt");
+            let author = str_at(p, "/author/login");
+            let labels = p
+                .pointer("/labels/nodes")
+                .and_then(|n| n.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|l| l.get("name").and_then(|n| n.as_str()).map(str::to_string))
+                .collect();
+            Some(PrSummary {
+                number: p.get("number")?.as_u64()?,
+                title: str_at(p, "/title"),
+                authored_by_viewer: viewer == Some(author.as_str()),
+                author,
+                branch: str_at(p, "/headRefName"),
+                base_branch: str_at(p, "/baseRefName"),
+                updated_at,
+                created_at: str_at(p, "/createdAt"),
+                review_requested,
+                draft: p.get("isDraft").and_then(|d| d.as_bool()).unwrap_or(false),
+                labels,
+                additions: p.get("additions").and_then(|n| n.as_u64()).unwrap_or(0),
+                deletions: p.get("deletions").and_then(|n| n.as_u64()).unwrap_or(0),
+                checks,
+                head_sha: str_at(p, "/headRefOid"),
+                head_repo: str_at(p, "/headRepository/nameWithOwner"),
+                review_count: count("/reviews/totalCount"),
+                comment_count: count("/comments/totalCount") + review_comment_count(p),
+            })
+        })
+        .collect()
+}
+
+/// Inline review comments on a PR, replies included. Threads past the
+/// first page count as one comment each.
+fn review_comment_count(pr: &serde_json::Value) -> u64 {
+    let threads = &pr["reviewThreads"];
+    let nodes = threads["nodes"].as_array().map(Vec::as_slice).unwrap_or_default();
+    let listed: u64 = nodes.iter().map(|t| t["comments"]["totalCount"].as_u64().unwrap_or(1)).sum();
+    let unlisted = threads["totalCount"].as_u64().unwrap_or(0).saturating_sub(nodes.len() as u64);
+    listed + unlisted
+}
+
+impl Forge for GitHubClient {
+    fn name(&self) -> &'static str {
+        "GitHub"
     }
 
-    /// Get current GitHub user login
-    fn get_current_user(&self) -> Option<String> {
-        let output = Command::new("gh")
-            .args(["api", "user", "--jq", ".login"])
-            .output()
-            .ok()?;
+    fn is_available(&mut self) -> bool {
+        self.gh_available()
+    }
 
-        if output.status.success() {
-            Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
-        } else {
-            None
-        }
+    fn setup_hint(&self) -> [&'static str; 2] {
+        ["gh CLI not found", "cli.github.com"]
     }
 
-    /// List open PRs for the current repo
-    pub fn list_open_prs(&mut self) -> Result<Vec<PrSummary>> {
+    /// Get PR info by number
+    fn get_pr_by_number(&mut self, pr_number: u64) -> Result<Option<PrInfo>> {
         if !self.is_available() {
-            return Ok(Vec::new());
+            return Ok(None);
         }
 
-        // Get current user for review request check
-        let current_user = self.get_current_user();
-
-        let output = Command::new("gh")
-            .args([
-                "pr", "list",
-                "--state", "open",
-                "--json", "number,title,author,headRefName,baseRefName,updatedAt,reviewRequests,statusCheckRollup",
-                "--limit", "50",
-            ])
-            .output()
-            .context("Failed to run gh pr list")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            log::warn!("Failed to list PRs: {}", stderr);
-            return Ok(Vec::new());
-        }
+        let json = self
+            .rest("GET", &format!("repos/{{owner}}/{{repo}}/pulls/{}", pr_number), None)
+            .context("Failed to fetch PR")?;
 
         #[derive(Deserialize)]
-        struct PrData {
+        struct PrBasic {
             number: u64,
             title: String,
-            author: Author,
-            #[serde(rename = "headRefName")]
-            head_ref_name: String,
-            #[serde(rename = "baseRefName", default)]
-            base_ref_name: String,
-            #[serde(rename = "updatedAt")]
-            updated_at: String,
-            #[serde(rename = "reviewRequests", default)]
-            review_requests: Vec<ReviewRequest>,
-            #[serde(rename = "statusCheckRollup", default)]
-            status_check_rollup: Vec<CheckRunData>,
+            #[serde(default)]
+            body: Option<String>,
+            user: Author,
+            state: String,
+            #[serde(default)]
+            merged_at: Option<String>,
+            html_url: String,
+            base: BaseRef,
+            head: HeadRef,
+            #[serde(default)]
+            draft: bool,
+            #[serde(default)]
+            labels: Vec<Label>,
+            #[serde(default)]
+            assignees: Vec<Author>,
+        }
+
+        #[derive(Deserialize)]
+        struct Label {
+            name: String,
         }
 
         #[derive(Deserialize)]
@@ -306,61 +752,112 @@ impl GitHubClient {
         }
 
         #[derive(Deserialize)]
-        struct ReviewRequest {
-            login: Option<String>,
+        struct BaseRef {
+            #[serde(rename = "ref")]
+            name: String,
         }
 
-        // CheckRun has `conclusion`, StatusContext has `state`
         #[derive(Deserialize)]
-        struct CheckRunData {
-            #[serde(default)]
-            conclusion: String,
-            #[serde(default)]
-            state: String,
+        struct HeadRef {
+            sha: String,
         }
 
-        let prs: Vec<PrData> = serde_json::from_slice(&output.stdout)
-            .unwrap_or_default();
+        let requested_reviewers = parse_requested_reviewers(&json);
+        let basic: PrBasic = serde_json::from_value(json).context("Failed to parse PR JSON")?;
 
-        Ok(prs.into_iter().map(|p| {
-            let review_requested = current_user.as_ref().map(|user| {
-                p.review_requests.iter().any(|r| {
-                    r.login.as_ref() == Some(user)
-                })
-            }).unwrap_or(false);
-
-            let checks: Vec<CheckStatus> = p.status_check_rollup.iter().map(|c| {
-                // Use conclusion (CheckRun) or fall back to state (StatusContext)
-                let status = if c.conclusion.is_empty() { &c.state } else { &c.conclusion };
-                let status = status.to_uppercase();
-                if status == "FAILURE" || status == "TIMED_OUT"
-                    || status == "CANCELLED" || status == "ERROR" {
-                    CheckStatus::Failure
-                } else if status == "SUCCESS" || status == "NEUTRAL"
-                    || status == "SKIPPED" {
-                    CheckStatus::Success
-                } else {
-                    CheckStatus::Pending
+        // Same states gh reports: OPEN, CLOSED or MERGED
+        let state = if basic.merged_at.is_some() {
+            "MERGED".to_string()
+        } else {
+            basic.state.to_uppercase()
+        };
+
+        let mut pr_info = PrInfo {
+            number: basic.number,
+            title: basic.title,
+            body: basic.body.unwrap_or_default(),
+            author: basic.user.login,
+            state,
+            url: basic.html_url,
+            base_branch: basic.base.name,
+            draft: basic.draft,
+            labels: basic.labels.into_iter().map(|l| l.name).collect(),
+            assignees: basic.assignees.into_iter().map(|a| a.login).collect(),
+            requested_reviewers,
+            ..Default::default()
+        };
+
+        pr_info.viewer = self.get_current_user().unwrap_or_default();
+        pr_info.reviews = self.get_reviews(basic.number).context("Failed to fetch reviews")?;
+
+        let (comments, mut file_threads) =
+            self.get_comments(basic.number).context("Failed to fetch review comments")?;
+        // Attach resolution state to each thread
+        match self.get_thread_states(basic.number) {
+            Ok(states) => {
+                for thread in file_threads.values_mut().flatten() {
+                    if let Some((node_id, resolved)) = states.get(&thread.id()) {
+                        thread.node_id = Some(node_id.clone());
+                        thread.resolved = *resolved;
+                    }
                 }
-            }).collect();
-
-            PrSummary {
-                number: p.number,
-                title: p.title,
-                author: p.author.login,
-                branch: p.head_ref_name,
-                base_branch: p.base_ref_name,
-                updated_at: p.updated_at.split('T').next().unwrap_or("").to_string(),
-                review_requested,
-                checks,
             }
-        }).collect())
+            Err(e) => log::warn!("Failed to fetch review threads for PR #{}: {:#}", pr_number, e),
+        }
+        pr_info.comments = comments;
+        pr_info.file_threads = file_threads;
+
+        // Conversation comments (merged with any path-less review comments)
+        let conversation = self
+            .get_conversation_comments(basic.number)
+            .context("Failed to fetch conversation")?;
+        pr_info.comments.extend(conversation);
+
+        pr_info.checks = self.get_check_runs(&basic.head.sha).unwrap_or_else(|e| {
+            log::warn!("Failed to fetch checks for PR #{}: {:#}", pr_number, e);
+            Vec::new()
+        });
+
+        Ok(Some(pr_info))
+    }
+
+    /// List open PRs for the current repo
+    fn list_open_prs(&mut self) -> Result<Vec<PrSummary>> {
+        if !self.is_available() {
+            return Ok(Vec::new());
+        }
+
+        // Follow the cursor page by page, up to MAX_LISTED_PRS
+        let mut prs = Vec::new();
+        let mut cursor = serde_json::Value::Null;
+        loop {
+            let json = self
+                .graphql(
+                    PR_LIST_QUERY,
+                    &[("owner", "{owner}".into()), ("name", "{repo}".into()), ("cursor", cursor)],
+                )
+                .context("Failed to list PRs")?;
+            prs.extend(parse_pr_list(&json));
+
+            let page_info = json.pointer("/data/repository/pullRequests/pageInfo");
+            let has_next = page_info
+                .and_then(|p| p.get("hasNextPage"))
+                .and_then(|h| h.as_bool())
+                .unwrap_or(false);
+            match page_info.and_then(|p| p.get("endCursor")).and_then(|c| c.as_str()) {
+                Some(end) if has_next && prs.len() < MAX_LISTED_PRS => cursor = end.into(),
+                _ => break,
+            }
+        }
+        prs.truncate(MAX_LISTED_PRS);
+        Ok(prs)
     }
 
     /// Checkout a PR branch, returns the PR's base branch name
-    pub fn checkout_pr(&self, pr_number: u64) -> Result<String> {
+    fn checkout_pr(&self, pr_number: u64) -> Result<String> {
         let output = Command::new("gh")
             .args(["pr", "checkout", &pr_number.to_string()])
+            .args(self.gh_repo_args())
             .output()
             .context("Failed to checkout PR")?;
 
@@ -370,134 +867,666 @@ impl GitHubClient {
         }
 
         // Get the PR's base branch from GitHub
-        let base_output = Command::new("gh")
-            .args(["pr", "view", &pr_number.to_string(), "--json", "baseRefName", "-q", ".baseRefName"])
-            .output()
-            .context("Failed to get PR base branch")?;
-
-        let base_branch = String::from_utf8_lossy(&base_output.stdout).trim().to_string();
+        let base_branch = self
+            .rest("GET", &format!("repos/{{owner}}/{{repo}}/pulls/{}", pr_number), None)
+            .ok()
+            .and_then(|pr| pr.pointer("/base/ref").and_then(|r| r.as_str()).map(str::to_string))
+            .unwrap_or_default();
         Ok(base_branch)
     }
 
+    fn head_ref(&self, pr_number: u64) -> String {
+        format!("refs/pull/{}/head", pr_number)
+    }
+
     /// Open PR in browser
-    pub fn open_pr_in_browser(&self, pr_number: u64) -> Result<()> {
+    fn open_pr_in_browser(&self, pr_number: u64) -> Result<()> {
         Command::new("gh")
             .args(["pr", "view", &pr_number.to_string(), "--web"])
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::null())
+            .args(self.gh_repo_args())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
             .spawn()
             .context("Failed to open PR in browser")?;
         Ok(())
     }
 
-    /// Approve a PR
-    pub fn approve_pr(&self, pr_number: u64) -> Result<()> {
-        let output = Command::new("gh")
-            .args(["pr", "review", &pr_number.to_string(), "--approve"])
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::piped())
-            .output()
-            .context("Failed to approve PR")?;
+    /// Replace the body of a review or conversation comment
+    fn edit_comment(&self, _pr_number: u64, comment_id: u64, conversation: bool, body: &str) -> Result<()> {
+        let payload = serde_json::json!({ "body": body });
+        self.rest("PATCH", &comment_endpoint(comment_id, conversation), Some(&payload))
+            .context("Failed to edit comment")?;
+        Ok(())
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Failed to approve PR: {}", stderr);
-        }
+    /// Delete a review or conversation comment
+    fn delete_comment(&self, _pr_number: u64, comment_id: u64, conversation: bool) -> Result<()> {
+        self.rest("DELETE", &comment_endpoint(comment_id, conversation), None)
+            .context("Failed to delete comment")?;
         Ok(())
     }
 
-    /// Request changes on a PR
-    pub fn request_changes(&self, pr_number: u64, body: &str) -> Result<()> {
-        let output = Command::new("gh")
-            .args(["pr", "review", &pr_number.to_string(), "--request-changes", "-b", body])
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::piped())
-            .output()
-            .context("Failed to request changes")?;
+    /// Post a comment on the PR conversation
+    fn add_conversation_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        let payload = serde_json::json!({ "body": body });
+        self.rest(
+            "POST",
+            &format!("repos/{{owner}}/{{repo}}/issues/{}/comments", pr_number),
+            Some(&payload),
+        )
+        .context("Failed to comment")?;
+        Ok(())
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Failed to request changes: {}", stderr);
-        }
+    /// Replace the summary body of a submitted review
+    fn edit_review(&self, pr_number: u64, review_id: u64, body: &str) -> Result<()> {
+        let payload = serde_json::json!({ "body": body });
+        self.rest(
+            "PUT",
+            &format!("repos/{{owner}}/{{repo}}/pulls/{}/reviews/{}", pr_number, review_id),
+            Some(&payload),
+        )
+        .context("Failed to edit review")?;
         Ok(())
     }
 
-    /// Add a comment to a PR (general review comment)
-    pub fn comment_pr(&self, pr_number: u64, body: &str) -> Result<()> {
-        let output = Command::new("gh")
-            .args(["pr", "review", &pr_number.to_string(), "--comment", "-b", body])
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::piped())
-            .output()
-            .context("Failed to comment on PR")?;
+    /// Submit a review with a verdict, summary body and all pending line comments
+    /// in a single review creation call. `event` is APPROVE, REQUEST_CHANGES or COMMENT.
+    fn submit_review(&self, pr_number: u64, event: &str, body: &str, comments: &[DraftComment]) -> Result<()> {
+        let payload = review_payload(event, body, comments);
+        self.rest(
+            "POST",
+            &format!("repos/{{owner}}/{{repo}}/pulls/{}/reviews", pr_number),
+            Some(&payload),
+        )
+        .context("Failed to submit review")?;
+        Ok(())
+    }
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Failed to comment: {}", stderr);
+    /// Reply to a review thread (posted against the thread's root comment)
+    fn reply_to_thread(&self, pr_number: u64, thread: &ReviewThread, body: &str) -> Result<()> {
+        let payload = serde_json::json!({ "body": body });
+        self.rest(
+            "POST",
+            &format!("repos/{{owner}}/{{repo}}/pulls/{}/comments/{}/replies", pr_number, thread.id()),
+            Some(&payload),
+        )
+        .context("Failed to reply")?;
+        Ok(())
+    }
+
+    /// Log of a GitHub Actions job
+    fn check_log(&self, job_id: u64) -> Result<Vec<String>> {
+        let log = self
+            .rest_text(&format!("repos/{{owner}}/{{repo}}/actions/jobs/{}/logs", job_id))
+            .context("Failed to fetch job log")?;
+        Ok(clean_log(&log))
+    }
+
+    fn merge_status(&self, pr_number: u64) -> Result<MergeStatus> {
+        let json = self
+            .graphql(
+                MERGE_STATUS_QUERY,
+                &[
+                    ("owner", "{owner}".into()),
+                    ("name", "{repo}".into()),
+                    ("number", pr_number.into()),
+                ],
+            )
+            .context("Failed to fetch merge status")?;
+        parse_merge_status(&json).context("PR not found")
+    }
+
+    /// Merge via the REST API; the branch is only deleted when it lives in
+    /// this repository (not a fork)
+    fn merge_pr(&self, pr_number: u64, method: MergeMethod, delete_branch: bool) -> Result<()> {
+        let pr = self
+            .rest("GET", &format!("repos/{{owner}}/{{repo}}/pulls/{}", pr_number), None)
+            .context("Failed to fetch PR")?;
+
+        let payload = serde_json::json!({ "merge_method": method.label() });
+        self.rest(
+            "PUT",
+            &format!("repos/{{owner}}/{{repo}}/pulls/{}/merge", pr_number),
+            Some(&payload),
+        )
+        .context("Failed to merge PR")?;
+
+        let same_repo = pr.pointer("/head/repo/full_name") == pr.pointer("/base/repo/full_name");
+        if let (true, true, Some(branch)) = (delete_branch, same_repo, pr.pointer("/head/ref").and_then(|r| r.as_str())) {
+            self.rest("DELETE", &format!("repos/{{owner}}/{{repo}}/git/refs/heads/{}", branch), None)
+                .context("Merged, but failed to delete the branch")?;
         }
         Ok(())
     }
 
-    /// Add a line comment to a PR
-    pub fn add_line_comment(&self, pr_number: u64, path: &str, line: u32, body: &str) -> Result<()> {
-        // Use gh api to create a review comment on a specific line
-        let output = Command::new("gh")
-            .args([
-                "api",
-                &format!("repos/{{owner}}/{{repo}}/pulls/{}/comments", pr_number),
-                "-f", &format!("body={}", body),
-                "-f", &format!("path={}", path),
-                "-f", "commit_id=$(gh pr view --json headRefOid -q .headRefOid)",
-                "-F", &format!("line={}", line),
-                "-f", "side=RIGHT",
-            ])
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::null())
-            .stderr(std::process::Stdio::piped())
-            .output()
-            .context("Failed to add line comment")?;
+    /// Create the PR, then request reviews (a failed review request names
+    /// the already created PR)
+    fn create_pr(&self, pr: &NewPr) -> Result<u64> {
+        let payload = serde_json::json!({
+            "title": pr.title,
+            "head": head_label(pr),
+            "base": pr.base,
+            "body": pr.body,
+            "draft": pr.draft,
+        });
+        let json = self
+            .rest("POST", "repos/{owner}/{repo}/pulls", Some(&payload))
+            .context("Failed to create PR")?;
+        let number = json["number"].as_u64().context("No PR number in response")?;
+
+        if !pr.reviewers.is_empty() {
+            self.rest(
+                "POST",
+                &format!("repos/{{owner}}/{{repo}}/pulls/{}/requested_reviewers", number),
+                Some(&reviewers_payload(&pr.reviewers)),
+            )
+            .with_context(|| format!("PR #{} created, but requesting reviews failed", number))?;
+        }
+        Ok(number)
+    }
 
-        if !output.status.success() {
-            // Try alternative: get commit SHA first, then post comment
-            let sha_output = Command::new("gh")
-                .args(["pr", "view", &pr_number.to_string(), "--json", "headRefOid", "-q", ".headRefOid"])
-                .output()
-                .context("Failed to get PR head SHA")?;
-
-            let commit_sha = String::from_utf8_lossy(&sha_output.stdout).trim().to_string();
-
-            let output2 = Command::new("gh")
-                .args([
-                    "api",
-                    "--method", "POST",
-                    &format!("repos/{{owner}}/{{repo}}/pulls/{}/comments", pr_number),
-                    "-f", &format!("body={}", body),
-                    "-f", &format!("path={}", path),
-                    "-f", &format!("commit_id={}", commit_sha),
-                    "-F", &format!("line={}", line),
-                    "-f", "side=RIGHT",
-                ])
-                .stdin(std::process::Stdio::null())
-                .stdout(std::process::Stdio::null())
-                .stderr(std::process::Stdio::piped())
-                .output()
-                .context("Failed to add line comment")?;
+    /// Collaborators for people, the label set for labels
+    fn metadata_options(&self, kind: MetadataKind) -> Result<Vec<String>> {
+        let (endpoint, key) = match kind {
+            MetadataKind::Reviewers | MetadataKind::Assignees => ("repos/{owner}/{repo}/collaborators?per_page=100", "login"),
+            MetadataKind::Labels => ("repos/{owner}/{repo}/labels?per_page=100", "name"),
+        };
+        let json = self
+            .rest("GET", endpoint, None)
+            .with_context(|| format!("Failed to list {}", kind.label().to_lowercase()))?;
+        Ok(names(&json, key))
+    }
 
-            if !output2.status.success() {
-                let stderr = String::from_utf8_lossy(&output2.stderr);
-                anyhow::bail!("Failed to add line comment: {}", stderr);
+    /// Reviewers are requested on the pull, assignees and labels live on the issue
+    fn update_metadata(&self, pr_number: u64, kind: MetadataKind, add: &[String], remove: &[String]) -> Result<()> {
+        let issue = format!("repos/{{owner}}/{{repo}}/issues/{}", pr_number);
+        match kind {
+            MetadataKind::Reviewers => {
+                let endpoint = format!("repos/{{owner}}/{{repo}}/pulls/{}/requested_reviewers", pr_number);
+                if !add.is_empty() {
+                    self.rest("POST", &endpoint, Some(&reviewers_payload(add)))?;
+                }
+                if !remove.is_empty() {
+                    self.rest("DELETE", &endpoint, Some(&reviewers_payload(remove)))?;
+                }
+            }
+            MetadataKind::Assignees => {
+                let endpoint = format!("{}/assignees", issue);
+                if !add.is_empty() {
+                    self.rest("POST", &endpoint, Some(&serde_json::json!({ "assignees": add })))?;
+                }
+                if !remove.is_empty() {
+                    self.rest("DELETE", &endpoint, Some(&serde_json::json!({ "assignees": remove })))?;
+                }
+            }
+            MetadataKind::Labels => {
+                if !add.is_empty() {
+                    self.rest("POST", &format!("{}/labels", issue), Some(&serde_json::json!({ "labels": add })))?;
+                }
+                for label in remove {
+                    self.rest("DELETE", &format!("{}/labels/{}", issue, encode_path_segment(label)), None)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Draft state only changes through GraphQL mutations on the PR node
+    fn set_draft(&self, pr_number: u64, draft: bool) -> Result<()> {
+        let node_id = self.pr_node_id(pr_number)?;
+        let mutation = if draft { "convertPullRequestToDraft" } else { "markPullRequestReadyForReview" };
+        let query = format!(
+            "mutation($id: ID!) {{ {}(input: {{pullRequestId: $id}}) {{ pullRequest {{ isDraft }} }} }}",
+            mutation
+        );
+        self.graphql(&query, &[("id", node_id.into())])
+            .context("Failed to change draft state")?;
+        Ok(())
+    }
+
+    fn viewed_files(&self, pr_number: u64) -> Result<Vec<String>> {
+        let mut paths = Vec::new();
+        let mut cursor = serde_json::Value::Null;
+        loop {
+            let json = self
+                .graphql(
+                    VIEWED_FILES_QUERY,
+                    &[
+                        ("owner", "{owner}".into()),
+                        ("name", "{repo}".into()),
+                        ("number", pr_number.into()),
+                        ("cursor", cursor),
+                    ],
+                )
+                .context("Failed to fetch viewed files")?;
+            let files = json.pointer("/data/repository/pullRequest/files");
+            paths.extend(parse_viewed_files(files.unwrap_or(&serde_json::Value::Null)));
+            let page_info = files.and_then(|f| f.get("pageInfo"));
+            let has_next = page_info
+                .and_then(|p| p.get("hasNextPage"))
+                .and_then(|h| h.as_bool())
+                .unwrap_or(false);
+            match page_info.and_then(|p| p.get("endCursor")).and_then(|c| c.as_str()) {
+                Some(end) if has_next => cursor = end.into(),
+                _ => break,
             }
         }
+        Ok(paths)
+    }
+
+    fn head_revisions(&self, pr_number: u64) -> Result<Vec<HeadRevision>> {
+        let json = self
+            .graphql(
+                FORCE_PUSHES_QUERY,
+                &[("owner", "{owner}".into()), ("name", "{repo}".into()), ("number", pr_number.into())],
+            )
+            .context("Failed to fetch force-pushes")?;
+        Ok(parse_force_pushes(&json))
+    }
+
+    fn set_file_viewed(&self, pr_number: u64, path: &str, viewed: bool) -> Result<()> {
+        let node_id = self.pr_node_id(pr_number)?;
+        let mutation = if viewed { "markFileAsViewed" } else { "unmarkFileAsViewed" };
+        let query = format!(
+            "mutation($id: ID!, $path: String!) {{ {}(input: {{pullRequestId: $id, path: $path}}) {{ clientMutationId }} }}",
+            mutation
+        );
+        self.graphql(&query, &[("id", node_id.into()), ("path", path.into())])
+            .context("Failed to update viewed state on GitHub")?;
         Ok(())
     }
+
+    /// Resolve or unresolve a review thread by its GraphQL node ID
+    fn set_thread_resolved(&self, _pr_number: u64, thread_id: &str, resolved: bool) -> Result<()> {
+        let mutation = if resolved { "resolveReviewThread" } else { "unresolveReviewThread" };
+        let query = format!(
+            "mutation($id: ID!) {{ {}(input: {{threadId: $id}}) {{ thread {{ isResolved }} }} }}",
+            mutation
+        );
+
+        self.graphql(&query, &[("id", thread_id.into())])
+            .context("Failed to update thread")?;
+        Ok(())
+    }
+}
+
+/// String field `key` of each object in a JSON array
+fn names(json: &serde_json::Value, key: &str) -> Vec<String> {
+    json.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item[key].as_str().map(str::to_string))
+        .collect()
+}
+
+/// Requested users by login and teams as `org/slug` (the form review
+/// requests take)
+fn parse_requested_reviewers(pr: &serde_json::Value) -> Vec<String> {
+    let org = pr.pointer("/base/repo/owner/login").and_then(|o| o.as_str()).unwrap_or_default();
+    let mut reviewers = names(&pr["requested_reviewers"], "login");
+    reviewers.extend(names(&pr["requested_teams"], "slug").into_iter().map(|slug| format!("{}/{}", org, slug)));
+    reviewers
+}
+
+/// Percent-encode a value used as one URL path segment (e.g. a label name)
+fn encode_path_segment(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// PR head as GitHub expects it: `owner:branch` when pushed to a fork
+fn head_label(pr: &NewPr) -> String {
+    let fork_owner = pr.head_repo.as_deref().and_then(|path| path.rsplit('/').nth(1));
+    match fork_owner {
+        Some(owner) => format!("{}:{}", owner, pr.head),
+        None => pr.head.clone(),
+    }
 }
 
-impl Default for GitHubClient {
-    fn default() -> Self {
-        Self::new()
+/// Review request body: `org/team` entries go to `team_reviewers` by slug
+fn reviewers_payload(reviewers: &[String]) -> serde_json::Value {
+    let (teams, users): (Vec<&String>, Vec<&String>) = reviewers.iter().partition(|r| r.contains('/'));
+    let teams: Vec<&str> = teams.iter().filter_map(|t| t.rsplit('/').next()).collect();
+    serde_json::json!({ "reviewers": users, "team_reviewers": teams })
+}
+
+/// REST path for a single comment; conversation comments live under issues
+fn comment_endpoint(comment_id: u64, conversation: bool) -> String {
+    if conversation {
+        format!("repos/{{owner}}/{{repo}}/issues/comments/{}", comment_id)
+    } else {
+        format!("repos/{{owner}}/{{repo}}/pulls/comments/{}", comment_id)
+    }
+}
+
+/// Request body for creating a review with inline comments
+fn review_payload(event: &str, body: &str, comments: &[DraftComment]) -> serde_json::Value {
+    let comments: Vec<serde_json::Value> = comments
+        .iter()
+        .map(|c| {
+            serde_json::json!({
+                "path": c.path,
+                "line": c.line,
+                "side": c.side,
+                "body": c.body,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "event": event,
+        "body": body,
+        "comments": comments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::test_server::serve;
+
+    // --- requests against a mock server ---
+
+    #[test]
+    fn rest_pages_reads_until_a_short_page() {
+        let full = serde_json::to_string(&vec![serde_json::json!({ "id": 1 }); PAGE_SIZE]).unwrap();
+        let (url, server) = serve(&[("200 OK", "", &full), ("200 OK", "", r#"[{ "id": 2 }]"#)]);
+        let client = GitHubClient {
+            available: None,
+            transport: Transport::Native(ApiClient::new(&url, "t", "octo", "cat")),
+            remote: None,
+        };
+
+        let items = client.rest_pages("repos/{owner}/{repo}/pulls/7/comments").unwrap();
+        assert_eq!(items.as_array().map(Vec::len), Some(PAGE_SIZE + 1));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /repos/octo/cat/pulls/7/comments?per_page=100&page=1 "));
+        assert!(requests[1].starts_with("GET /repos/octo/cat/pulls/7/comments?per_page=100&page=2 "));
+    }
+
+    // --- parse_thread_states ---
+
+    #[test]
+    fn parse_thread_states_maps_root_comment_to_state() {
+        let json = serde_json::json!({
+            "data": { "repository": { "pullRequest": { "reviewThreads": { "nodes": [
+                { "id": "PRRT_a", "isResolved": true, "comments": { "nodes": [{ "databaseId": 11 }] } },
+                { "id": "PRRT_b", "isResolved": false, "comments": { "nodes": [{ "databaseId": 12 }] } },
+                { "id": "PRRT_c", "isResolved": false, "comments": { "nodes": [] } }
+            ] } } } }
+        });
+        let states = parse_thread_states(&json);
+        assert_eq!(states.len(), 2);
+        assert_eq!(states.get(&11), Some(&("PRRT_a".to_string(), true)));
+        assert_eq!(states.get(&12), Some(&("PRRT_b".to_string(), false)));
+    }
+
+    #[test]
+    fn parse_thread_states_handles_missing_data() {
+        assert!(parse_thread_states(&serde_json::json!({ "errors": [] })).is_empty());
+    }
+
+    // --- review_payload ---
+
+    #[test]
+    fn review_payload_includes_verdict_and_comments() {
+        let drafts = vec![DraftComment {
+            path: "src/lib.rs".to_string(),
+            line: 12,
+            side: "RIGHT".to_string(),
+            body: "nit".to_string(),
+        }];
+        let payload = review_payload("REQUEST_CHANGES", "Needs work", &drafts);
+        assert_eq!(payload["event"], "REQUEST_CHANGES");
+        assert_eq!(payload["body"], "Needs work");
+        assert_eq!(payload["comments"][0]["path"], "src/lib.rs");
+        assert_eq!(payload["comments"][0]["line"], 12);
+        assert_eq!(payload["comments"][0]["side"], "RIGHT");
+    }
+
+    // --- comment_endpoint ---
+
+    #[test]
+    fn comment_endpoint_depends_on_kind() {
+        assert!(comment_endpoint(5, true).ends_with("issues/comments/5"));
+        assert!(comment_endpoint(5, false).ends_with("pulls/comments/5"));
+    }
+
+    // --- encode_path_segment ---
+
+    #[test]
+    fn encode_path_segment_escapes_spaces_and_unicode() {
+        assert_eq!(encode_path_segment("good first issue"), "good%20first%20issue");
+        assert_eq!(encode_path_segment("type: bug/ü"), "type%3A%20bug%2F%C3%BC");
+        assert_eq!(encode_path_segment("v1.2-rc_1~"), "v1.2-rc_1~");
+    }
+
+    // --- reviewers ---
+
+    #[test]
+    fn parse_requested_reviewers_includes_teams() {
+        let pr = serde_json::json!({
+            "base": { "repo": { "owner": { "login": "acme" } } },
+            "requested_reviewers": [{ "login": "ann" }],
+            "requested_teams": [{ "slug": "core" }],
+        });
+        assert_eq!(parse_requested_reviewers(&pr), ["ann", "acme/core"]);
+        assert!(parse_requested_reviewers(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn reviewers_payload_splits_users_and_teams() {
+        let reviewers = vec!["ann".to_string(), "acme/core".to_string()];
+        assert_eq!(
+            reviewers_payload(&reviewers),
+            serde_json::json!({ "reviewers": ["ann"], "team_reviewers": ["core"] })
+        );
+    }
+
+    // --- parse_viewed_files ---
+
+    #[test]
+    fn parse_viewed_files_keeps_viewed_only() {
+        let files = serde_json::json!({ "nodes": [
+            { "path": "a.rs", "viewerViewedState": "VIEWED" },
+            { "path": "b.rs", "viewerViewedState": "DISMISSED" },
+            { "path": "c.rs", "viewerViewedState": "UNVIEWED" },
+        ] });
+        assert_eq!(parse_viewed_files(&files), ["a.rs"]);
+    }
+
+    // --- parse_force_pushes ---
+
+    #[test]
+    fn parse_force_pushes_newest_first_without_duplicates() {
+        let json = serde_json::json!({ "data": { "repository": { "pullRequest": { "timelineItems": { "nodes": [
+            { "createdAt": "2024-01-01T00:00:00Z", "beforeCommit": { "oid": "a" }, "afterCommit": { "oid": "b" } },
+            { "createdAt": "2024-01-02T00:00:00Z", "beforeCommit": { "oid": "b" }, "afterCommit": { "oid": "c" } },
+        ] } } } } });
+        let revisions = parse_force_pushes(&json);
+        let shas: Vec<(&str, &str)> = revisions.iter().map(|r| (r.sha.as_str(), r.note.as_str())).collect();
+        assert_eq!(shas, [("c", "force-pushed"), ("b", "before force-push"), ("a", "before force-push")]);
+        assert_eq!(revisions[0].at, "2024-01-02T00:00:00Z");
+    }
+
+    // --- head_label ---
+
+    #[test]
+    fn head_label_names_fork_owner() {
+        let mut pr = NewPr { head: "feature".to_string(), ..NewPr::default() };
+        assert_eq!(head_label(&pr), "feature");
+        pr.head_repo = Some("me/repo".to_string());
+        assert_eq!(head_label(&pr), "me:feature");
+    }
+
+    // --- parse_pr_list ---
+
+    #[test]
+    fn parse_pr_list_reads_checks_and_review_requests() {
+        let json = serde_json::json!({ "data": {
+            "viewer": { "login": "me" },
+            "repository": { "pullRequests": { "nodes": [{
+                "number": 3,
+                "title": "Fix it",
+                "headRefName": "fix",
+                "baseRefName": "main",
+                "headRefOid": "c0ffee",
+                "updatedAt": "2024-05-01T10:00:00Z",
+                "createdAt": "2024-04-20T08:00:00Z",
+                "isDraft": true,
+                "additions": 10,
+                "deletions": 4,
+                "author": { "login": "octo" },
+                "headRepository": { "nameWithOwner": "octo/fork" },
+                "reviews": { "totalCount": 2 },
+                "comments": { "totalCount": 3 },
+                "reviewThreads": { "totalCount": 1, "nodes": [{ "comments": { "totalCount": 2 } }] },
+                "labels": { "nodes": [{ "name": "bug" }] },
+                "reviewRequests": { "nodes": [{ "requestedReviewer": { "login": "me" } }] },
+                "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "contexts": { "nodes": [
+                    { "conclusion": "SUCCESS" },
+                    { "conclusion": null },
+                    { "state": "FAILURE" }
+                ] } } } }] }
+            }] } }
+        } });
+
+        let prs = parse_pr_list(&json);
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].number, 3);
+        assert_eq!(prs[0].author, "octo");
+        assert_eq!(prs[0].updated_at, "2024-05-01T10:00:00Z");
+        assert_eq!(prs[0].created_at, "2024-04-20T08:00:00Z");
+        assert!(prs[0].review_requested);
+        assert!(!prs[0].authored_by_viewer);
+        assert!(prs[0].draft);
+        assert_eq!(prs[0].labels, vec!["bug"]);
+        assert_eq!(prs[0].additions + prs[0].deletions, 14);
+        assert_eq!(prs[0].head_sha, "c0ffee");
+        assert_eq!(prs[0].head_repo, "octo/fork");
+        assert_eq!((prs[0].review_count, prs[0].comment_count), (2, 5));
+        assert_eq!(
+            prs[0].checks,
+            vec![CheckStatus::Success, CheckStatus::Pending, CheckStatus::Failure]
+        );
+    }
+
+    #[test]
+    fn review_comment_count_includes_replies() {
+        let pr = |replies: u64| {
+            serde_json::json!({ "reviewThreads": { "totalCount": 2, "nodes": [
+                { "comments": { "totalCount": 1 + replies } },
+                { "comments": { "totalCount": 1 } },
+            ] } })
+        };
+        assert_eq!(review_comment_count(&pr(0)), 2);
+        assert_eq!(review_comment_count(&pr(1)), 3);
+
+        // Threads past the first page still count
+        let more = serde_json::json!({ "reviewThreads": { "totalCount": 150, "nodes": [] } });
+        assert_eq!(review_comment_count(&more), 150);
+    }
+
+    // --- parse_check_runs ---
+
+    #[test]
+    fn parse_check_runs_maps_status_and_job_ids() {
+        let json = serde_json::json!({ "total_count": 2, "check_runs": [
+            {
+                "id": 101,
+                "name": "test",
+                "status": "completed",
+                "conclusion": "failure",
+                "started_at": "2024-05-01T10:00:00Z",
+                "completed_at": "2024-05-01T10:02:30Z",
+                "html_url": "https://github.com/o/r/actions/runs/1/job/101",
+                "app": { "slug": "github-actions" }
+            },
+            {
+                "id": 202,
+                "name": "codecov",
+                "status": "in_progress",
+                "conclusion": null,
+                "started_at": "2024-05-01T10:00:00Z",
+                "completed_at": null,
+                "html_url": "https://codecov.io/x",
+                "app": { "slug": "codecov" }
+            }
+        ] });
+
+        let checks = parse_check_runs(&json);
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].name, "test");
+        assert_eq!(checks[0].status, CheckStatus::Failure);
+        assert_eq!(checks[0].duration(), Some(150));
+        assert_eq!(checks[0].job_id, Some(101));
+        assert_eq!(checks[1].status, CheckStatus::Pending);
+        assert_eq!(checks[1].completed_at, "");
+        assert_eq!(checks[1].job_id, None);
+    }
+
+    // --- parse_merge_status ---
+
+    fn merge_json(pr: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({ "data": { "repository": { "pullRequest": pr } } })
+    }
+
+    #[test]
+    fn parse_merge_status_clean_pr_has_no_blockers() {
+        let json = merge_json(serde_json::json!({
+            "state": "OPEN", "isDraft": false, "mergeable": "MERGEABLE",
+            "mergeStateStatus": "CLEAN", "reviewDecision": "APPROVED",
+            "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "contexts": { "nodes": [
+                { "name": "test", "status": "COMPLETED", "conclusion": "SUCCESS", "isRequired": true },
+                { "name": "coverage", "status": "COMPLETED", "conclusion": "FAILURE", "isRequired": false }
+            ] } } } }] }
+        }));
+        let status = parse_merge_status(&json).unwrap();
+        assert_eq!(status.mergeable, Some(true));
+        assert_eq!(status.review_decision.as_deref(), Some("APPROVED"));
+        assert_eq!(status.required_checks, vec![("test".to_string(), CheckStatus::Success)]);
+        assert!(status.blockers.is_empty());
+    }
+
+    #[test]
+    fn parse_merge_status_collects_blockers() {
+        let json = merge_json(serde_json::json!({
+            "state": "OPEN", "isDraft": true, "mergeable": "CONFLICTING",
+            "mergeStateStatus": "BLOCKED", "reviewDecision": "REVIEW_REQUIRED",
+            "commits": { "nodes": [{ "commit": { "statusCheckRollup": { "contexts": { "nodes": [
+                { "context": "ci/build", "state": "PENDING", "isRequired": true }
+            ] } } } }] }
+        }));
+        let status = parse_merge_status(&json).unwrap();
+        assert_eq!(
+            status.blockers,
+            vec![
+                "PR is a draft",
+                "Merge conflicts with the base branch",
+                "Approving review required",
+                "Required check pending: ci/build",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_merge_status_reports_protection_without_other_reasons() {
+        let json = merge_json(serde_json::json!({
+            "state": "OPEN", "isDraft": false, "mergeable": "UNKNOWN", "mergeStateStatus": "BLOCKED",
+            "reviewDecision": null
+        }));
+        let status = parse_merge_status(&json).unwrap();
+        assert_eq!(status.mergeable, None);
+        assert_eq!(status.review_decision, None);
+        assert_eq!(status.blockers, vec!["Blocked by branch protection"]);
+        assert!(parse_merge_status(&serde_json::json!({ "data": null })).is_none());
     }
 }
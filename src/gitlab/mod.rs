@@ -0,0 +1,1203 @@
+//! GitLab forge: merge requests through the REST API (v4)
+//!
+//! Authenticates with a personal access token from `GITLAB_TOKEN` or
+//! `GL_TOKEN`. The instance URL is taken from `TIMECOP_GITLAB_URL`, or
+//! derived from the base remote's host (`upstream` in a fork workflow, else
+//! `origin`).
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::drafts::DraftComment;
+use crate::forge::{
+    base_remote, clean_log, open_url, read_json_response, read_text_response, CheckRun,
+    CheckStatus, Comment, Forge, HeadRevision, MergeMethod, MergeStatus, MetadataKind, NewPr,
+    PrInfo, PrSummary, Review, ReviewThread, MAX_LISTED_PRS,
+};
+use crate::git::Remotes;
+
+const PAGE_SIZE: usize = 50;
+
+/// Largest page GitLab serves, for lists that are read in full
+const MAX_PAGE_SIZE: usize = 100;
+
+/// Environment variable with the GitLab instance URL, e.g. https://gitlab.example.com
+pub const GITLAB_URL_ENV: &str = "TIMECOP_GITLAB_URL";
+
+type FileThreads = HashMap<String, Vec<ReviewThread>>;
+
+/// GitLab REST client for the project of a local repository
+pub struct GitLabClient {
+    agent: ureq::Agent,
+    api_url: String,
+    token: Option<String>,
+    /// URL-encoded project path ("group%2Fsub%2Frepo"), None outside a GitLab checkout
+    project: Option<String>,
+    /// Local repository MRs are checked out into
+    repo: PathBuf,
+    /// Remote MR refs are fetched from (see `git::Remotes`)
+    remote: String,
+}
+
+impl GitLabClient {
+    pub fn new(repo: &Path) -> Self {
+        let remote = base_remote(repo);
+        let base_url = std::env::var(GITLAB_URL_ENV)
+            .ok()
+            .filter(|u| !u.is_empty())
+            .or_else(|| remote.as_ref().map(|r| format!("https://{}", r.host)))
+            .unwrap_or_else(|| "https://gitlab.com".to_string());
+        let token = ["GITLAB_TOKEN", "GL_TOKEN"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|token| !token.is_empty());
+        let project = remote.map(|r| r.path);
+        let remotes = git2::Repository::discover(repo)
+            .map(|r| Remotes::resolve(&r))
+            .unwrap_or_default();
+
+        Self {
+            repo: repo.to_path_buf(),
+            remote: remotes.base,
+            ..Self::with(
+                &format!("{}/api/v4", base_url.trim_end_matches('/')),
+                token.as_deref(),
+                project.as_deref(),
+            )
+        }
+    }
+
+    fn with(api_url: &str, token: Option<&str>, project_path: Option<&str>) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: token.map(str::to_string),
+            project: project_path.map(|p| p.replace('/', "%2F")),
+            repo: PathBuf::from("."),
+            remote: Remotes::default().base,
+        }
+    }
+
+    /// Send a request to an endpoint below `projects/:id/`
+    fn project_request(&self, method: &str, endpoint: &str, body: Option<&Value>) -> Result<Value> {
+        let project = self.project.as_deref().context("Not a GitLab project")?;
+        self.request(method, &format!("projects/{}/{}", project, endpoint), body)
+    }
+
+    /// GET every page of a list below `projects/:id/`, following `X-Next-Page`
+    fn project_pages(&self, endpoint: &str) -> Result<Value> {
+        let project = self.project.as_deref().context("Not a GitLab project")?;
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        let mut page = "1".to_string();
+        loop {
+            let url = format!(
+                "projects/{}/{}{}per_page={}&page={}",
+                project, endpoint, separator, MAX_PAGE_SIZE, page
+            );
+            let response = self.authorized("GET", &url)?.call();
+            let next = response
+                .as_ref()
+                .ok()
+                .and_then(|r| r.header("X-Next-Page"))
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+            match read_json_response(response, "GitLab")? {
+                Value::Array(batch) => items.extend(batch),
+                _ => anyhow::bail!("Expected a list from {}", endpoint),
+            }
+            if next.is_empty() {
+                break;
+            }
+            page = next;
+        }
+        Ok(Value::Array(items))
+    }
+
+    fn request(&self, method: &str, endpoint: &str, body: Option<&Value>) -> Result<Value> {
+        let request = self.authorized(method, endpoint)?;
+        let response = match body {
+            Some(body) => request.send_json(body),
+            None => request.call(),
+        };
+        read_json_response(response, "GitLab")
+    }
+
+    fn authorized(&self, method: &str, endpoint: &str) -> Result<ureq::Request> {
+        let token = self
+            .token
+            .as_deref()
+            .context("No GitLab token (set GITLAB_TOKEN)")?;
+        Ok(self
+            .agent
+            .request(method, &format!("{}/{}", self.api_url, endpoint))
+            .set("PRIVATE-TOKEN", token)
+            .set("User-Agent", "timecop"))
+    }
+
+    fn get_current_user(&self) -> Result<String> {
+        let json = self.request("GET", "user", None)?;
+        Ok(str_field(&json, "username"))
+    }
+
+    /// Latest pipeline status per commit SHA
+    fn get_pipeline_states(&self) -> Result<HashMap<String, CheckStatus>> {
+        let json = self.project_request("GET", "pipelines?per_page=100", None)?;
+        Ok(parse_pipelines(&json))
+    }
+
+    /// Jobs of a pipeline, in stage order
+    fn get_pipeline_jobs(&self, pipeline_id: u64) -> Result<Vec<CheckRun>> {
+        let json = self.project_pages(&format!("pipelines/{}/jobs", pipeline_id))?;
+        Ok(parse_jobs(&json))
+    }
+
+    /// User id for a username; reviewers are assigned by id
+    fn get_user_id(&self, username: &str) -> Result<u64> {
+        let json = self.request("GET", &format!("users?username={}", username), None)?;
+        json[0]["id"]
+            .as_u64()
+            .with_context(|| format!("No GitLab user @{}", username))
+    }
+
+    fn get_approvals(&self, mr_iid: u64) -> Result<Vec<Review>> {
+        let json =
+            self.project_request("GET", &format!("merge_requests/{}/approvals", mr_iid), None)?;
+        Ok(parse_approvals(&json))
+    }
+
+    fn get_discussions(&self, mr_iid: u64) -> Result<(Vec<Comment>, FileThreads)> {
+        let json = self.project_pages(&format!("merge_requests/{}/discussions", mr_iid))?;
+        Ok(parse_discussions(&json))
+    }
+
+    /// Post a note; `endpoint` is relative to the merge request
+    fn post_note(&self, mr_iid: u64, endpoint: &str, payload: &Value) -> Result<()> {
+        self.project_request(
+            "POST",
+            &format!("merge_requests/{}/{}", mr_iid, endpoint),
+            Some(payload),
+        )?;
+        Ok(())
+    }
+}
+
+impl Forge for GitLabClient {
+    fn name(&self) -> &'static str {
+        "GitLab"
+    }
+
+    fn is_available(&mut self) -> bool {
+        self.token.is_some() && self.project.is_some()
+    }
+
+    fn setup_hint(&self) -> [&'static str; 2] {
+        ["No GitLab token", "set GITLAB_TOKEN"]
+    }
+
+    fn list_open_prs(&mut self) -> Result<Vec<PrSummary>> {
+        if !self.is_available() {
+            return Ok(Vec::new());
+        }
+
+        let viewer = self.get_current_user().unwrap_or_default();
+        let pipelines = self.get_pipeline_states().unwrap_or_else(|e| {
+            log::warn!("Failed to fetch pipelines: {:#}", e);
+            HashMap::new()
+        });
+
+        let mut prs = Vec::new();
+        for page in 1..=MAX_LISTED_PRS / PAGE_SIZE {
+            let json = self
+                .project_request(
+                    "GET",
+                    &format!(
+                        "merge_requests?state=opened&per_page={}&page={}&order_by=updated_at",
+                        PAGE_SIZE, page
+                    ),
+                    None,
+                )
+                .context("Failed to list merge requests")?;
+            let batch = parse_mr_list(&json, &viewer, &pipelines);
+            let last_page = batch.len() < PAGE_SIZE;
+            prs.extend(batch);
+            if last_page {
+                break;
+            }
+        }
+        Ok(prs)
+    }
+
+    fn get_pr_by_number(&mut self, pr_number: u64) -> Result<Option<PrInfo>> {
+        if !self.is_available() {
+            return Ok(None);
+        }
+
+        let json = self
+            .project_request("GET", &format!("merge_requests/{}", pr_number), None)
+            .context("Failed to fetch merge request")?;
+        let mut pr_info = parse_mr(&json);
+
+        pr_info.viewer = self.get_current_user().unwrap_or_default();
+        pr_info.reviews = self
+            .get_approvals(pr_number)
+            .context("Failed to fetch approvals")?;
+        let (comments, file_threads) = self
+            .get_discussions(pr_number)
+            .context("Failed to fetch discussions")?;
+        pr_info.comments = comments;
+        pr_info.file_threads = file_threads;
+
+        if let Some(pipeline_id) = json.pointer("/head_pipeline/id").and_then(|id| id.as_u64()) {
+            pr_info.checks = self.get_pipeline_jobs(pipeline_id).unwrap_or_else(|e| {
+                log::warn!(
+                    "Failed to fetch pipeline jobs for MR !{}: {:#}",
+                    pr_number,
+                    e
+                );
+                Vec::new()
+            });
+        }
+
+        Ok(Some(pr_info))
+    }
+
+    /// Fetch the MR head into a local `mr-<iid>` branch and check it out
+    fn checkout_pr(&self, pr_number: u64) -> Result<String> {
+        let refspec = self.head_ref(pr_number);
+        let branch = format!("mr-{}", pr_number);
+        for args in [
+            vec!["fetch", self.remote.as_str(), refspec.as_str()],
+            vec!["checkout", "-B", branch.as_str(), "FETCH_HEAD"],
+        ] {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(&self.repo)
+                .args(&args)
+                .output()
+                .context("Failed to checkout merge request")?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("Failed to checkout merge request: {}", stderr);
+            }
+        }
+
+        let target_branch = self
+            .project_request("GET", &format!("merge_requests/{}", pr_number), None)
+            .map(|mr| str_field(&mr, "target_branch"))
+            .unwrap_or_default();
+        Ok(target_branch)
+    }
+
+    fn head_ref(&self, pr_number: u64) -> String {
+        format!("refs/merge-requests/{}/head", pr_number)
+    }
+
+    fn open_pr_in_browser(&self, pr_number: u64) -> Result<()> {
+        let mr = self.project_request("GET", &format!("merge_requests/{}", pr_number), None)?;
+        open_url(&str_field(&mr, "web_url"))
+    }
+
+    /// GitLab has no review objects: line comments become diff discussions,
+    /// the body a note, and APPROVE an approval. Requesting changes posts the
+    /// body only.
+    fn submit_review(
+        &self,
+        pr_number: u64,
+        event: &str,
+        body: &str,
+        comments: &[DraftComment],
+    ) -> Result<()> {
+        if !comments.is_empty() {
+            let mr = self
+                .project_request("GET", &format!("merge_requests/{}", pr_number), None)
+                .context("Failed to fetch merge request")?;
+            for comment in comments {
+                let payload = discussion_payload(&mr["diff_refs"], comment);
+                self.post_note(pr_number, "discussions", &payload)
+                    .with_context(|| {
+                        format!("Failed to comment on {}:{}", comment.path, comment.line)
+                    })?;
+            }
+        }
+
+        if !body.is_empty() {
+            self.post_note(pr_number, "notes", &serde_json::json!({ "body": body }))
+                .context("Failed to submit review")?;
+        }
+
+        if event == "APPROVE" {
+            self.post_note(pr_number, "approve", &serde_json::json!({}))
+                .context("Failed to approve merge request")?;
+        }
+        Ok(())
+    }
+
+    fn reply_to_thread(&self, pr_number: u64, thread: &ReviewThread, body: &str) -> Result<()> {
+        let discussion = thread
+            .node_id
+            .as_deref()
+            .context("Thread has no discussion id")?;
+        self.post_note(
+            pr_number,
+            &format!("discussions/{}/notes", discussion),
+            &serde_json::json!({ "body": body }),
+        )
+        .context("Failed to reply")
+    }
+
+    fn set_thread_resolved(&self, pr_number: u64, thread_id: &str, resolved: bool) -> Result<()> {
+        self.project_request(
+            "PUT",
+            &format!(
+                "merge_requests/{}/discussions/{}?resolved={}",
+                pr_number, thread_id, resolved
+            ),
+            None,
+        )
+        .context("Failed to update thread")?;
+        Ok(())
+    }
+
+    /// Diff and conversation comments are both notes on GitLab
+    fn edit_comment(
+        &self,
+        pr_number: u64,
+        comment_id: u64,
+        _conversation: bool,
+        body: &str,
+    ) -> Result<()> {
+        self.project_request(
+            "PUT",
+            &format!("merge_requests/{}/notes/{}", pr_number, comment_id),
+            Some(&serde_json::json!({ "body": body })),
+        )
+        .context("Failed to edit comment")?;
+        Ok(())
+    }
+
+    fn delete_comment(&self, pr_number: u64, comment_id: u64, _conversation: bool) -> Result<()> {
+        self.project_request(
+            "DELETE",
+            &format!("merge_requests/{}/notes/{}", pr_number, comment_id),
+            None,
+        )
+        .context("Failed to delete comment")?;
+        Ok(())
+    }
+
+    fn add_conversation_comment(&self, pr_number: u64, body: &str) -> Result<()> {
+        self.post_note(pr_number, "notes", &serde_json::json!({ "body": body }))
+            .context("Failed to add comment")
+    }
+
+    fn edit_review(&self, _pr_number: u64, _review_id: u64, _body: &str) -> Result<()> {
+        anyhow::bail!("GitLab approvals have no body to edit")
+    }
+
+    fn merge_status(&self, pr_number: u64) -> Result<MergeStatus> {
+        let mr = self
+            .project_request("GET", &format!("merge_requests/{}", pr_number), None)
+            .context("Failed to fetch merge request")?;
+        let approvals = self
+            .project_request(
+                "GET",
+                &format!("merge_requests/{}/approvals", pr_number),
+                None,
+            )
+            .context("Failed to fetch approvals")?;
+        Ok(parse_merge_status(&mr, &approvals))
+    }
+
+    /// Merge with `squash` as requested; GitLab sets merge commits vs
+    /// fast-forward per project, so rebase isn't a per-merge choice
+    fn merge_pr(&self, pr_number: u64, method: MergeMethod, delete_branch: bool) -> Result<()> {
+        if method == MergeMethod::Rebase {
+            anyhow::bail!("GitLab merge methods are a project setting; choose merge or squash");
+        }
+        let payload = serde_json::json!({
+            "squash": method == MergeMethod::Squash,
+            "should_remove_source_branch": delete_branch,
+        });
+        self.project_request(
+            "PUT",
+            &format!("merge_requests/{}/merge", pr_number),
+            Some(&payload),
+        )
+        .context("Failed to merge")?;
+        Ok(())
+    }
+
+    /// Reviewers are looked up before creating, so unknown names fail early
+    fn create_pr(&self, pr: &NewPr) -> Result<u64> {
+        let reviewer_ids = pr
+            .reviewers
+            .iter()
+            .map(|name| self.get_user_id(name))
+            .collect::<Result<Vec<_>>>()?;
+        let mut payload = mr_payload(pr, &reviewer_ids);
+        let json = match &pr.head_repo {
+            // From a fork: opened on the source project, targeting this one
+            Some(head_repo) => {
+                let project = self.project.as_deref().context("Not a GitLab project")?;
+                let target = self
+                    .request("GET", &format!("projects/{}", project), None)
+                    .context("Failed to look up project")?;
+                payload["target_project_id"] = target["id"].clone();
+                let endpoint = format!("projects/{}/merge_requests", head_repo.replace('/', "%2F"));
+                self.request("POST", &endpoint, Some(&payload))
+            }
+            None => self.project_request("POST", "merge_requests", Some(&payload)),
+        }
+        .context("Failed to create merge request")?;
+        json["iid"]
+            .as_u64()
+            .context("No merge request iid in response")
+    }
+
+    /// Project members (including inherited ones) or the project's labels
+    fn metadata_options(&self, kind: MetadataKind) -> Result<Vec<String>> {
+        let (endpoint, key) = match kind {
+            MetadataKind::Reviewers | MetadataKind::Assignees => {
+                ("members/all?per_page=100", "username")
+            }
+            MetadataKind::Labels => ("labels?per_page=100", "name"),
+        };
+        let json = self
+            .project_request("GET", endpoint, None)
+            .with_context(|| format!("Failed to list {}", kind.label().to_lowercase()))?;
+        Ok(json
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|item| str_field(item, key))
+            .collect())
+    }
+
+    /// Labels change by name; reviewers and assignees are replaced as a whole
+    /// list of user ids
+    fn update_metadata(
+        &self,
+        pr_number: u64,
+        kind: MetadataKind,
+        add: &[String],
+        remove: &[String],
+    ) -> Result<()> {
+        let endpoint = format!("merge_requests/{}", pr_number);
+        let payload = match kind {
+            MetadataKind::Labels => serde_json::json!({
+                "add_labels": add.join(","),
+                "remove_labels": remove.join(","),
+            }),
+            MetadataKind::Reviewers | MetadataKind::Assignees => {
+                let (list, field) = match kind {
+                    MetadataKind::Reviewers => ("reviewers", "reviewer_ids"),
+                    _ => ("assignees", "assignee_ids"),
+                };
+                let mr = self
+                    .project_request("GET", &endpoint, None)
+                    .context("Failed to fetch merge request")?;
+                let added = add
+                    .iter()
+                    .map(|name| self.get_user_id(name))
+                    .collect::<Result<Vec<_>>>()?;
+                serde_json::json!({ field: updated_user_ids(&mr[list], &added, remove) })
+            }
+        };
+        self.project_request("PUT", &endpoint, Some(&payload))
+            .with_context(|| format!("Failed to update {}", kind.label().to_lowercase()))?;
+        Ok(())
+    }
+
+    /// GitLab tracks drafts by a title prefix
+    fn set_draft(&self, pr_number: u64, draft: bool) -> Result<()> {
+        let endpoint = format!("merge_requests/{}", pr_number);
+        let mr = self
+            .project_request("GET", &endpoint, None)
+            .context("Failed to fetch merge request")?;
+        let title = draft_title(&str_field(&mr, "title"), draft);
+        self.project_request(
+            "PUT",
+            &endpoint,
+            Some(&serde_json::json!({ "title": title })),
+        )
+        .context("Failed to change draft state")?;
+        Ok(())
+    }
+
+    /// GitLab keeps viewed files in the browser only
+    fn viewed_files(&self, _pr_number: u64) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn set_file_viewed(&self, _pr_number: u64, _path: &str, _viewed: bool) -> Result<()> {
+        Ok(())
+    }
+
+    fn head_revisions(&self, pr_number: u64) -> Result<Vec<HeadRevision>> {
+        let json = self
+            .project_request(
+                "GET",
+                &format!("merge_requests/{}/versions", pr_number),
+                None,
+            )
+            .context("Failed to fetch merge request versions")?;
+        Ok(parse_versions(&json))
+    }
+
+    fn check_log(&self, job_id: u64) -> Result<Vec<String>> {
+        let project = self.project.as_deref().context("Not a GitLab project")?;
+        let request = self.authorized(
+            "GET",
+            &format!("projects/{}/jobs/{}/trace", project, job_id),
+        )?;
+        let trace =
+            read_text_response(request.call(), "GitLab").context("Failed to fetch job log")?;
+        Ok(clean_log(&trace))
+    }
+}
+
+fn str_field(json: &Value, key: &str) -> String {
+    json[key].as_str().unwrap_or_default().to_string()
+}
+
+/// Same states the GitHub side reports: OPEN, CLOSED or MERGED
+fn mr_state(state: &str) -> String {
+    match state {
+        "opened" => "OPEN".to_string(),
+        other => other.to_uppercase(),
+    }
+}
+
+/// Usernames of a list of users
+fn usernames(users: &Value) -> Vec<String> {
+    users
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|user| str_field(user, "username"))
+        .collect()
+}
+
+/// Ids of `users` without the removed usernames, plus the added ids
+fn updated_user_ids(users: &Value, added: &[u64], removed: &[String]) -> Vec<u64> {
+    let mut ids: Vec<u64> = users
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|user| !removed.iter().any(|r| user["username"].as_str() == Some(r)))
+        .filter_map(|user| user["id"].as_u64())
+        .collect();
+    for id in added {
+        if !ids.contains(id) {
+            ids.push(*id);
+        }
+    }
+    ids
+}
+
+/// Title with GitLab's draft prefix added, or any draft prefix removed
+fn draft_title(title: &str, draft: bool) -> String {
+    let lower = title.to_lowercase();
+    let bare = ["draft:", "[draft]", "(draft)"]
+        .iter()
+        .find(|prefix| lower.starts_with(*prefix))
+        .map(|prefix| title[prefix.len()..].trim_start())
+        .unwrap_or(title);
+    if draft {
+        format!("Draft: {}", bare)
+    } else {
+        bare.to_string()
+    }
+}
+
+fn parse_mr(json: &Value) -> PrInfo {
+    PrInfo {
+        number: json["iid"].as_u64().unwrap_or_default(),
+        title: str_field(json, "title"),
+        body: str_field(json, "description"),
+        author: str_field(&json["author"], "username"),
+        state: mr_state(json["state"].as_str().unwrap_or_default()),
+        url: str_field(json, "web_url"),
+        base_branch: str_field(json, "target_branch"),
+        draft: json["draft"].as_bool().unwrap_or(false),
+        labels: json["labels"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|l| l.as_str().map(str::to_string))
+            .collect(),
+        assignees: usernames(&json["assignees"]),
+        requested_reviewers: usernames(&json["reviewers"]),
+        ..Default::default()
+    }
+}
+
+/// Map a pipeline or job status
+fn check_status(status: &str) -> CheckStatus {
+    match status {
+        "success" | "skipped" => CheckStatus::Success,
+        "failed" | "canceled" => CheckStatus::Failure,
+        _ => CheckStatus::Pending,
+    }
+}
+
+fn parse_pipelines(json: &Value) -> HashMap<String, CheckStatus> {
+    let mut states = HashMap::new();
+    // Newest first, so the first pipeline seen for a SHA wins
+    for pipeline in json.as_array().into_iter().flatten() {
+        let status = check_status(pipeline["status"].as_str().unwrap_or_default());
+        states.entry(str_field(pipeline, "sha")).or_insert(status);
+    }
+    states
+}
+
+/// Pipeline jobs as checks, in creation order (roughly stage order)
+fn parse_jobs(json: &Value) -> Vec<CheckRun> {
+    let mut jobs: Vec<CheckRun> = json
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|job| CheckRun {
+            name: format!("{} / {}", str_field(job, "stage"), str_field(job, "name")),
+            status: check_status(job["status"].as_str().unwrap_or_default()),
+            started_at: str_field(job, "started_at"),
+            completed_at: str_field(job, "finished_at"),
+            url: str_field(job, "web_url"),
+            job_id: job["id"].as_u64(),
+        })
+        .collect();
+    jobs.sort_by_key(|job| job.job_id);
+    jobs
+}
+
+fn parse_mr_list(
+    json: &Value,
+    viewer: &str,
+    pipelines: &HashMap<String, CheckStatus>,
+) -> Vec<PrSummary> {
+    json.as_array()
+        .into_iter()
+        .flatten()
+        .map(|mr| PrSummary {
+            number: mr["iid"].as_u64().unwrap_or_default(),
+            title: str_field(mr, "title"),
+            author: str_field(&mr["author"], "username"),
+            authored_by_viewer: !viewer.is_empty()
+                && mr["author"]["username"].as_str() == Some(viewer),
+            branch: str_field(mr, "source_branch"),
+            base_branch: str_field(mr, "target_branch"),
+            updated_at: str_field(mr, "updated_at"),
+            created_at: str_field(mr, "created_at"),
+            draft: mr["draft"]
+                .as_bool()
+                .or(mr["work_in_progress"].as_bool())
+                .unwrap_or(false),
+            labels: mr["labels"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|l| l.as_str().map(str::to_string))
+                .collect(),
+            // Not part of GitLab's listing
+            additions: 0,
+            deletions: 0,
+            review_requested: !viewer.is_empty()
+                && mr["reviewers"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .any(|r| r["username"].as_str() == Some(viewer)),
+            checks: pipelines
+                .get(&str_field(mr, "sha"))
+                .copied()
+                .into_iter()
+                .collect(),
+            head_sha: str_field(mr, "sha"),
+            // Only the source project's id is listed
+            head_repo: String::new(),
+            // Approvals aren't part of the listing; notes are
+            review_count: 0,
+            comment_count: mr["user_notes_count"].as_u64().unwrap_or(0),
+        })
+        .collect()
+}
+
+/// New merge request; drafts are marked by the title prefix
+fn mr_payload(pr: &NewPr, reviewer_ids: &[u64]) -> Value {
+    let title = if pr.draft {
+        format!("Draft: {}", pr.title)
+    } else {
+        pr.title.clone()
+    };
+    serde_json::json!({
+        "source_branch": pr.head,
+        "target_branch": pr.base,
+        "title": title,
+        "description": pr.body,
+        "reviewer_ids": reviewer_ids,
+    })
+}
+
+/// Every push to a merge request makes a version; listed newest first
+fn parse_versions(json: &Value) -> Vec<HeadRevision> {
+    let versions = json.as_array().map(Vec::as_slice).unwrap_or_default();
+    versions
+        .iter()
+        .enumerate()
+        .map(|(i, version)| HeadRevision {
+            sha: str_field(version, "head_commit_sha"),
+            at: str_field(version, "created_at"),
+            note: format!("version {}", versions.len() - i),
+        })
+        .filter(|revision| !revision.sha.is_empty())
+        .collect()
+}
+
+/// Approvals shown as APPROVED reviews (GitLab doesn't report when they were given)
+fn parse_approvals(json: &Value) -> Vec<Review> {
+    json["approved_by"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|entry| Review {
+            id: entry["user"]["id"].as_u64().unwrap_or_default(),
+            author: str_field(&entry["user"], "username"),
+            state: "APPROVED".to_string(),
+            body: String::new(),
+            submitted_at: String::new(),
+            commit_sha: String::new(),
+        })
+        .collect()
+}
+
+/// Merge status from a merge request and its approvals. `detailed_merge_status`
+/// names the first unmet requirement; anything but "mergeable" blocks.
+fn parse_merge_status(mr: &Value, approvals: &Value) -> MergeStatus {
+    let detailed = mr["detailed_merge_status"]
+        .as_str()
+        .or(mr["merge_status"].as_str())
+        .unwrap_or_default();
+    let mergeable = match detailed {
+        "checking" | "unchecked" | "preparing" => None,
+        _ => Some(!mr["has_conflicts"].as_bool().unwrap_or(false)),
+    };
+
+    let review_decision = if approvals["approved"].as_bool().unwrap_or(false) {
+        Some("APPROVED".to_string())
+    } else if approvals["approvals_left"].as_u64().unwrap_or(0) > 0 {
+        Some("REVIEW_REQUIRED".to_string())
+    } else {
+        None
+    };
+
+    let required_checks = match mr["head_pipeline"]["status"].as_str() {
+        Some(status) => vec![("pipeline".to_string(), check_status(status))],
+        None => Vec::new(),
+    };
+
+    let blocker = match detailed {
+        "mergeable" | "can_be_merged" | "checking" | "unchecked" | "preparing" => None,
+        "not_open" => Some("Merge request is not open".to_string()),
+        "draft_status" => Some("Merge request is a draft".to_string()),
+        "conflict" | "cannot_be_merged" | "broken_status" => {
+            Some("Merge conflicts with the target branch".to_string())
+        }
+        "not_approved" => Some("Approval required".to_string()),
+        "discussions_not_resolved" => Some("Unresolved discussions".to_string()),
+        "ci_must_pass" => Some("Pipeline must succeed".to_string()),
+        "ci_still_running" => Some("Pipeline still running".to_string()),
+        "need_rebase" => Some("Source branch needs a rebase".to_string()),
+        other => Some(format!("Not mergeable ({})", other.replace('_', " "))),
+    };
+
+    MergeStatus {
+        mergeable,
+        review_decision,
+        required_checks,
+        blockers: blocker.into_iter().collect(),
+    }
+}
+
+/// Split MR discussions into conversation comments and per-file diff threads.
+/// System notes (pushes, label changes, ...) are skipped.
+fn parse_discussions(json: &Value) -> (Vec<Comment>, FileThreads) {
+    let mut comments = Vec::new();
+    let mut file_threads: FileThreads = HashMap::new();
+
+    for discussion in json.as_array().into_iter().flatten() {
+        let notes: Vec<&Value> = discussion["notes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|n| !n["system"].as_bool().unwrap_or(false))
+            .collect();
+        let Some(first) = notes.first() else { continue };
+
+        let position = &first["position"];
+        if position.is_null() {
+            comments.extend(notes.iter().map(|note| Comment {
+                conversation: true,
+                ..note_comment(note)
+            }));
+            continue;
+        }
+
+        let (line, side) = match position["new_line"].as_u64() {
+            Some(line) => (Some(line as u32), "RIGHT"),
+            None => (position["old_line"].as_u64().map(|l| l as u32), "LEFT"),
+        };
+        let path = match side {
+            "RIGHT" => str_field(position, "new_path"),
+            _ => str_field(position, "old_path"),
+        };
+        let head_sha = position["head_sha"].as_str().map(str::to_string);
+        let root_id = first["id"].as_u64().unwrap_or_default();
+
+        let thread_comments = notes
+            .iter()
+            .enumerate()
+            .map(|(i, note)| Comment {
+                in_reply_to_id: (i > 0).then_some(root_id),
+                line,
+                original_line: line,
+                side: Some(side.to_string()),
+                commit_id: head_sha.clone(),
+                original_commit_id: head_sha.clone(),
+                ..note_comment(note)
+            })
+            .collect();
+
+        file_threads.entry(path).or_default().push(ReviewThread {
+            comments: thread_comments,
+            node_id: discussion["id"].as_str().map(str::to_string),
+            resolved: first["resolved"].as_bool().unwrap_or(false),
+        });
+    }
+
+    (comments, file_threads)
+}
+
+fn note_comment(note: &Value) -> Comment {
+    Comment {
+        id: note["id"].as_u64().unwrap_or_default(),
+        author: str_field(&note["author"], "username"),
+        body: str_field(note, "body"),
+        created_at: str_field(note, "created_at"),
+        ..Default::default()
+    }
+}
+
+/// New diff discussion for a draft line comment, positioned on the MR's latest diff
+fn discussion_payload(diff_refs: &Value, comment: &DraftComment) -> Value {
+    let mut position = serde_json::json!({
+        "position_type": "text",
+        "base_sha": diff_refs["base_sha"],
+        "start_sha": diff_refs["start_sha"],
+        "head_sha": diff_refs["head_sha"],
+        "old_path": comment.path,
+        "new_path": comment.path,
+    });
+    let line_key = if comment.side == "LEFT" {
+        "old_line"
+    } else {
+        "new_line"
+    };
+    position[line_key] = comment.line.into();
+    serde_json::json!({ "body": comment.body, "position": position })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forge::test_server::{serve, serve_once};
+    use serde_json::json;
+
+    // --- requests against a mock server ---
+
+    #[test]
+    fn requests_send_token_and_encoded_project() {
+        let (url, server) =
+            serve_once("200 OK", r#"{"iid": 4, "title": "Fix", "state": "opened"}"#);
+        let client = GitLabClient::with(&url, Some("glpat"), Some("group/sub/repo"));
+
+        let json = client
+            .project_request("GET", "merge_requests/4", None)
+            .unwrap();
+        assert_eq!(parse_mr(&json).state, "OPEN");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /projects/group%2Fsub%2Frepo/merge_requests/4 HTTP/1.1"));
+        assert!(request
+            .to_ascii_lowercase()
+            .contains("private-token: glpat"));
+    }
+
+    #[test]
+    fn project_pages_follows_next_page_header() {
+        let (url, server) = serve(&[
+            ("200 OK", "X-Next-Page: 2\r\n", r#"[{ "id": "a" }]"#),
+            ("200 OK", "X-Next-Page: \r\n", r#"[{ "id": "b" }]"#),
+        ]);
+        let client = GitLabClient::with(&url, Some("glpat"), Some("group/repo"));
+
+        let json = client
+            .project_pages("merge_requests/4/discussions")
+            .unwrap();
+        assert_eq!(json, json!([{ "id": "a" }, { "id": "b" }]));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with(
+            "GET /projects/group%2Frepo/merge_requests/4/discussions?per_page=100&page=1 "
+        ));
+        assert!(requests[1].contains("&page=2 "));
+    }
+
+    #[test]
+    fn unavailable_without_token_or_project() {
+        assert!(!GitLabClient::with("http://x", None, Some("a/b")).is_available());
+        assert!(!GitLabClient::with("http://x", Some("t"), None).is_available());
+        assert!(GitLabClient::with("http://x", Some("t"), Some("a/b")).is_available());
+    }
+
+    // --- parsing ---
+
+    #[test]
+    fn parse_versions_numbers_pushes() {
+        let json = json!([
+            { "head_commit_sha": "c", "created_at": "2024-01-03T00:00:00Z" },
+            { "head_commit_sha": "b", "created_at": "2024-01-02T00:00:00Z" },
+            { "head_commit_sha": "a", "created_at": "2024-01-01T00:00:00Z" },
+        ]);
+        let versions = parse_versions(&json);
+        assert_eq!(
+            versions[0],
+            HeadRevision {
+                sha: "c".to_string(),
+                at: "2024-01-03T00:00:00Z".to_string(),
+                note: "version 3".to_string(),
+            }
+        );
+        assert_eq!(versions[2].note, "version 1");
+    }
+
+    #[test]
+    fn parse_mr_list_reads_reviewers_and_pipelines() {
+        let json = json!([{
+            "iid": 9,
+            "title": "Add thing",
+            "author": { "username": "ann" },
+            "source_branch": "thing",
+            "target_branch": "main",
+            "updated_at": "2024-05-01T10:00:00Z",
+            "created_at": "2024-04-01T10:00:00Z",
+            "draft": true,
+            "labels": ["bug"],
+            "sha": "abc",
+            "user_notes_count": 5,
+            "reviewers": [{ "username": "me" }],
+        }]);
+        let pipelines = parse_pipelines(&json!([
+            { "sha": "abc", "status": "failed" },
+            { "sha": "abc", "status": "success" },
+        ]));
+
+        let prs = parse_mr_list(&json, "me", &pipelines);
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].number, 9);
+        assert_eq!(prs[0].branch, "thing");
+        assert!(prs[0].review_requested);
+        assert!(prs[0].draft);
+        assert_eq!(prs[0].labels, vec!["bug"]);
+        assert_eq!(prs[0].updated_at, "2024-05-01T10:00:00Z");
+        assert_eq!(prs[0].checks, vec![CheckStatus::Failure]);
+        assert_eq!((prs[0].head_sha.as_str(), prs[0].comment_count), ("abc", 5));
+        assert!(!parse_mr_list(&json, "", &pipelines)[0].review_requested);
+    }
+
+    #[test]
+    fn parse_discussions_splits_threads_and_conversation() {
+        let json = json!([
+            { "id": "d1", "notes": [
+                { "id": 1, "body": "why?", "author": { "username": "ann" },
+                  "created_at": "2024-05-01T10:00:00Z", "resolved": true,
+                  "position": { "new_path": "src/a.rs", "old_path": "src/a.rs",
+                                "new_line": 12, "old_line": null, "head_sha": "abc" } },
+                { "id": 2, "body": "because", "author": { "username": "bob" },
+                  "created_at": "2024-05-01T11:00:00Z" },
+            ]},
+            { "id": "d2", "notes": [
+                { "id": 3, "body": "deleted line", "author": { "username": "ann" },
+                  "position": { "new_path": "src/b.rs", "old_path": "src/old_b.rs",
+                                "new_line": null, "old_line": 4, "head_sha": "abc" } },
+            ]},
+            { "id": "d3", "individual_note": true, "notes": [
+                { "id": 4, "body": "LGTM", "author": { "username": "bob" } },
+            ]},
+            { "id": "d4", "notes": [
+                { "id": 5, "body": "added 1 commit", "system": true },
+            ]},
+        ]);
+
+        let (comments, threads) = parse_discussions(&json);
+        assert_eq!(comments.len(), 1);
+        assert!(comments[0].conversation);
+        assert_eq!(comments[0].body, "LGTM");
+
+        let thread = &threads["src/a.rs"][0];
+        assert_eq!(thread.node_id.as_deref(), Some("d1"));
+        assert!(thread.resolved);
+        assert_eq!(thread.root().anchor(), Some(("abc", 12)));
+        assert_eq!(thread.replies()[0].in_reply_to_id, Some(1));
+
+        let left = threads["src/old_b.rs"][0].root();
+        assert_eq!(left.side.as_deref(), Some("LEFT"));
+        assert_eq!(left.line, Some(4));
+    }
+
+    #[test]
+    fn parse_jobs_as_checks_in_creation_order() {
+        let json = json!([
+            {
+                "id": 12, "name": "rspec", "stage": "test", "status": "failed",
+                "started_at": "2024-05-01T10:01:00.000Z", "finished_at": "2024-05-01T10:03:00.500Z",
+                "web_url": "https://gitlab.com/g/r/-/jobs/12"
+            },
+            { "id": 11, "name": "compile", "stage": "build", "status": "success" },
+            { "id": 13, "name": "deploy", "stage": "deploy", "status": "manual" }
+        ]);
+        let jobs = parse_jobs(&json);
+        let names: Vec<&str> = jobs.iter().map(|j| j.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["build / compile", "test / rspec", "deploy / deploy"]
+        );
+        assert_eq!(jobs[1].status, CheckStatus::Failure);
+        assert_eq!(jobs[1].duration(), Some(120));
+        assert_eq!(jobs[1].job_id, Some(12));
+        assert_eq!(jobs[2].status, CheckStatus::Pending);
+    }
+
+    #[test]
+    fn check_log_reads_the_job_trace() {
+        let (url, server) = serve_once("200 OK", "\u{1b}[0;m$ cargo test\nerror: failed");
+        let client = GitLabClient::with(&url, Some("glpat"), Some("group/repo"));
+
+        let log = client.check_log(12).unwrap();
+        assert_eq!(log, vec!["$ cargo test", "error: failed"]);
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /projects/group%2Frepo/jobs/12/trace HTTP/1.1"));
+    }
+
+    #[test]
+    fn create_pr_posts_draft_merge_request() {
+        let (url, server) = serve_once("201 Created", r#"{"iid": 12}"#);
+        let client = GitLabClient::with(&url, Some("glpat"), Some("group/repo"));
+        let pr = NewPr {
+            head: "feature".to_string(),
+            base: "main".to_string(),
+            title: "Add feature".to_string(),
+            body: "Details".to_string(),
+            draft: true,
+            reviewers: vec![],
+            head_repo: None,
+        };
+
+        assert_eq!(client.create_pr(&pr).unwrap(), 12);
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /projects/group%2Frepo/merge_requests HTTP/1.1"));
+        assert!(request.contains(r#""title":"Draft: Add feature""#));
+        assert!(request.contains(r#""source_branch":"feature""#));
+    }
+
+    #[test]
+    fn parse_mr_reads_metadata() {
+        let pr = parse_mr(&json!({
+            "iid": 3,
+            "draft": true,
+            "labels": ["bug", "ui"],
+            "assignees": [{ "id": 1, "username": "ann" }],
+            "reviewers": [{ "id": 2, "username": "bob" }],
+        }));
+        assert!(pr.draft);
+        assert_eq!(pr.labels, ["bug", "ui"]);
+        assert_eq!(pr.assignees, ["ann"]);
+        assert_eq!(pr.requested_reviewers, ["bob"]);
+    }
+
+    #[test]
+    fn updated_user_ids_removes_by_name_and_adds_ids() {
+        let users = json!([{ "id": 1, "username": "ann" }, { "id": 2, "username": "bob" }]);
+        assert_eq!(
+            updated_user_ids(&users, &[3, 2], &["ann".to_string()]),
+            [2, 3]
+        );
+    }
+
+    #[test]
+    fn draft_title_adds_and_strips_prefixes() {
+        assert_eq!(draft_title("Fix parser", true), "Draft: Fix parser");
+        assert_eq!(draft_title("Draft: Fix parser", true), "Draft: Fix parser");
+        assert_eq!(draft_title("[Draft] Fix parser", false), "Fix parser");
+        assert_eq!(draft_title("draft:Fix", false), "Fix");
+        assert_eq!(draft_title("Drafting docs", false), "Drafting docs");
+    }
+
+    #[test]
+    fn parse_approvals_as_reviews() {
+        let reviews = parse_approvals(&json!({
+            "approved_by": [{ "user": { "id": 7, "username": "ann" } }],
+        }));
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(reviews[0].author, "ann");
+        assert_eq!(reviews[0].state, "APPROVED");
+    }
+
+    #[test]
+    fn parse_merge_status_maps_detailed_status() {
+        let approvals = json!({ "approved": false, "approvals_left": 1 });
+        let mr = json!({
+            "detailed_merge_status": "not_approved",
+            "has_conflicts": false,
+            "head_pipeline": { "status": "success" }
+        });
+        let status = parse_merge_status(&mr, &approvals);
+        assert_eq!(status.mergeable, Some(true));
+        assert_eq!(status.review_decision.as_deref(), Some("REVIEW_REQUIRED"));
+        assert_eq!(
+            status.required_checks,
+            vec![("pipeline".to_string(), CheckStatus::Success)]
+        );
+        assert_eq!(status.blockers, vec!["Approval required"]);
+
+        let mr = json!({ "detailed_merge_status": "mergeable", "has_conflicts": false });
+        let status = parse_merge_status(&mr, &json!({ "approved": true }));
+        assert_eq!(status.review_decision.as_deref(), Some("APPROVED"));
+        assert!(status.blockers.is_empty());
+
+        let mr = json!({ "detailed_merge_status": "checking" });
+        assert_eq!(parse_merge_status(&mr, &json!({})).mergeable, None);
+    }
+
+    #[test]
+    fn discussion_payload_positions_by_side() {
+        let refs = json!({ "base_sha": "b", "start_sha": "s", "head_sha": "h" });
+        let draft = DraftComment {
+            path: "src/a.rs".to_string(),
+            line: 3,
+            side: "LEFT".to_string(),
+            body: "gone?".to_string(),
+        };
+        let payload = discussion_payload(&refs, &draft);
+        assert_eq!(payload["position"]["old_line"], 3);
+        assert!(payload["position"].get("new_line").is_none());
+        assert_eq!(payload["position"]["head_sha"], "h");
+    }
+}
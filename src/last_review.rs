@@ -0,0 +1,64 @@
+//! Where the user last reviewed each PR
+//!
+//! The PR head at the time of the user's last approve / comment / request
+//! changes, recorded on submit and updated from the forge's reviews. Stored
+//! in `.git/timecop/last-review.json` for the since-review timeline position.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// PR number → head commit id at the last review
+#[derive(Debug)]
+pub struct LastReviewStore {
+    path: PathBuf,
+    commits: BTreeMap<u64, String>,
+}
+
+impl LastReviewStore {
+    /// Load the last reviews for the repository at `git_dir`
+    pub fn load(git_dir: &Path) -> Self {
+        let path = git_dir.join("timecop").join("last-review.json");
+        let commits = std::fs::read(&path)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+        Self { path, commits }
+    }
+
+    pub fn get(&self, pr_number: u64) -> Option<&str> {
+        self.commits.get(&pr_number).map(String::as_str)
+    }
+
+    /// Record the commit a PR was reviewed on
+    pub fn record(&mut self, pr_number: u64, sha: &str) -> Result<()> {
+        if sha.is_empty() || self.get(pr_number) == Some(sha) {
+            return Ok(());
+        }
+        self.commits.insert(pr_number, sha.to_string());
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).context("Failed to create state directory")?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&self.commits)?).context("Failed to save last review")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn record_persists_and_ignores_unknown_commits() {
+        let dir = TempDir::new("last-review");
+        let mut store = LastReviewStore::load(&dir);
+        store.record(7, "abc").unwrap();
+        store.record(7, "").unwrap();
+        store.record(8, "def").unwrap();
+
+        let store = LastReviewStore::load(&dir);
+        assert_eq!(store.get(7), Some("abc"));
+        assert_eq!(store.get(8), Some("def"));
+        assert_eq!(store.get(9), None);
+    }
+}
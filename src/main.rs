@@ -1,11 +1,22 @@
 mod app;
 mod async_loader;
 mod config;
+mod drafts;
 mod event;
+mod export;
+mod forge;
 mod git;
 mod github;
+mod gitlab;
+mod last_review;
+mod pr_template;
+mod return_point;
+mod seen;
+#[cfg(test)]
+mod test_util;
 mod theme;
 mod ui;
+mod viewed;
 
 use anyhow::Result;
 use clap::Parser;
@@ -32,6 +43,15 @@ struct Args {
     /// Path to git repository
     #[arg(default_value = ".")]
     path: PathBuf,
+
+    /// Remote PRs are opened against, saved for this repository
+    /// (default: upstream, else origin)
+    #[arg(long)]
+    remote: Option<String>,
+
+    /// Start in follow mode, jumping to files as they change
+    #[arg(long)]
+    follow: bool,
 }
 
 fn main() -> Result<()> {
@@ -50,6 +70,10 @@ fn main() -> Result<()> {
     // Resolve path
     let path = args.path.canonicalize().unwrap_or(args.path);
 
+    if let Some(remote) = &args.remote {
+        git::pin_remote(&path, remote)?;
+    }
+
     // Create app first (fails early if not a git repo)
     let mut app = App::new(path.to_str().unwrap_or(".")).map_err(|_| {
         anyhow::anyhow!(
@@ -57,6 +81,7 @@ fn main() -> Result<()> {
             path.display()
         )
     })?;
+    app.follow = args.follow;
 
     // Initialize terminal
     enable_raw_mode()?;
@@ -105,21 +130,22 @@ fn run_app<B: Backend + io::Write>(
             AppEvent::Tick => {
                 app.handle_tick();
             }
-            AppEvent::FileChanged => {
+            AppEvent::FileChanged(mut paths) => {
                 // Drain queued FileChanged events to coalesce rapid saves
                 let mut pending = Vec::new();
                 while let Some(evt) = events.try_next() {
-                    if !matches!(evt, AppEvent::FileChanged) {
-                        pending.push(evt);
+                    match evt {
+                        AppEvent::FileChanged(more) => paths.extend(more),
+                        evt => pending.push(evt),
                     }
                 }
-                app.refresh()?;
+                app.handle_file_change(paths)?;
                 for evt in pending {
                     match evt {
                         AppEvent::Key(key) => app.handle_key(key)?,
                         AppEvent::Mouse(mouse) => app.handle_mouse(mouse)?,
                         AppEvent::Tick => app.handle_tick(),
-                        AppEvent::FileChanged => {}
+                        AppEvent::FileChanged(_) => {}
                     }
                 }
             }
@@ -0,0 +1,117 @@
+//! Prefilled title and description for a new PR
+//!
+//! The title is the first commit's summary. The description is the
+//! repository's pull request template when it has one, else it is composed
+//! from the commit messages.
+
+use std::path::Path;
+
+/// Directories GitHub looks in for a template, relative to the repository root
+const TEMPLATE_DIRS: [&str; 3] = [".github", "", "docs"];
+
+/// Template file name (any case), with or without an extension
+const TEMPLATE_NAME: &str = "pull_request_template";
+
+/// The repository's PR template. A `PULL_REQUEST_TEMPLATE/` directory of
+/// several templates yields the first by file name.
+pub fn find_template(root: &Path) -> Option<String> {
+    TEMPLATE_DIRS.iter().find_map(|dir| {
+        let mut entries: Vec<_> = std::fs::read_dir(root.join(dir)).ok()?.flatten().map(|e| e.path()).collect();
+        entries.sort();
+        let is_template = |path: &Path| {
+            path.file_stem()
+                .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(TEMPLATE_NAME))
+        };
+
+        let file = entries.iter().find(|path| path.is_file() && is_template(path));
+        let template_dir = entries.iter().find(|path| path.is_dir() && is_template(path));
+        let path = match (file, template_dir) {
+            (Some(file), _) => file.clone(),
+            (None, Some(dir)) => {
+                let mut templates: Vec<_> = std::fs::read_dir(dir)
+                    .ok()?
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+                    .collect();
+                templates.sort();
+                templates.into_iter().next()?
+            }
+            (None, None) => return None,
+        };
+        std::fs::read_to_string(path).ok().filter(|t| !t.trim().is_empty())
+    })
+}
+
+/// Title and body from commit messages (oldest first) and an optional
+/// template. One commit lends its message body; several are listed by summary.
+pub fn compose(messages: &[String], template: Option<&str>) -> (String, String) {
+    let summary = |message: &str| message.lines().next().unwrap_or_default().trim().to_string();
+    let title = messages.first().map(|m| summary(m)).unwrap_or_default();
+
+    let body = match (template, messages) {
+        (Some(template), _) => template.trim_end().to_string(),
+        (None, [message]) => message
+            .split_once('\n')
+            .map(|(_, rest)| rest.trim().to_string())
+            .unwrap_or_default(),
+        _ => messages
+            .iter()
+            .map(|m| format!("- {}", summary(m)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    (title, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn messages(list: &[&str]) -> Vec<String> {
+        list.iter().map(|m| m.to_string()).collect()
+    }
+
+    // --- compose ---
+
+    #[test]
+    fn compose_single_commit_uses_its_body() {
+        let (title, body) = compose(&messages(&["Fix parser\n\nHandles empty input.\n"]), None);
+        assert_eq!(title, "Fix parser");
+        assert_eq!(body, "Handles empty input.");
+    }
+
+    #[test]
+    fn compose_lists_several_commits() {
+        let (title, body) = compose(&messages(&["Add lexer\n\nDetails", "Add parser\n"]), None);
+        assert_eq!(title, "Add lexer");
+        assert_eq!(body, "- Add lexer\n- Add parser");
+    }
+
+    #[test]
+    fn compose_prefers_template() {
+        let (title, body) = compose(&messages(&["Fix\n\nBody"]), Some("## Summary\n\n"));
+        assert_eq!(title, "Fix");
+        assert_eq!(body, "## Summary");
+    }
+
+    // --- find_template ---
+
+    #[test]
+    fn find_template_in_github_dir_any_case() {
+        let dir = TempDir::new("template");
+        std::fs::create_dir_all(dir.join(".github/PULL_REQUEST_TEMPLATE")).unwrap();
+        std::fs::write(dir.join(".github/PULL_REQUEST_TEMPLATE/b.md"), "B").unwrap();
+        std::fs::write(dir.join(".github/PULL_REQUEST_TEMPLATE/a.md"), "A").unwrap();
+        assert_eq!(find_template(&dir).as_deref(), Some("A"));
+
+        std::fs::write(dir.join(".github/PULL_REQUEST_TEMPLATE.md"), "Single").unwrap();
+        assert_eq!(find_template(&dir).as_deref(), Some("Single"));
+
+        std::fs::remove_dir_all(dir.join(".github")).unwrap();
+        assert_eq!(find_template(&dir), None);
+        std::fs::write(dir.join("pull_request_template.md"), "Root").unwrap();
+        assert_eq!(find_template(&dir).as_deref(), Some("Root"));
+    }
+}
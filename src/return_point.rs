@@ -0,0 +1,70 @@
+//! Where to go back to after checking out a PR
+//!
+//! Checking out a PR remembers the branch it left and, if uncommitted work
+//! was stashed on the way, the stash commit. Stored under `.git/timecop/` so
+//! it survives app restarts.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Branch (or detached commit) to return to, and the stash to restore there
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReturnPoint {
+    pub branch: String,
+    /// Stash commit id; stash indices shift, the commit doesn't
+    #[serde(default)]
+    pub stash: Option<String>,
+}
+
+impl ReturnPoint {
+    fn file_path(git_dir: &Path) -> PathBuf {
+        git_dir.join("timecop").join("return.json")
+    }
+
+    /// The saved return point, if any
+    pub fn load(git_dir: &Path) -> Option<Self> {
+        let data = std::fs::read(Self::file_path(git_dir)).ok()?;
+        serde_json::from_slice(&data).ok()
+    }
+
+    pub fn save(&self, git_dir: &Path) -> Result<()> {
+        let path = Self::file_path(git_dir);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).context("Failed to create state directory")?;
+        }
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?).context("Failed to save return point")
+    }
+
+    /// Forget the return point (after returning)
+    pub fn clear(git_dir: &Path) -> Result<()> {
+        let path = Self::file_path(git_dir);
+        if path.exists() {
+            std::fs::remove_file(&path).context("Failed to remove return point")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn return_point_round_trips() {
+        let dir = TempDir::new("return");
+        assert_eq!(ReturnPoint::load(&dir), None);
+
+        let point = ReturnPoint {
+            branch: "feature".to_string(),
+            stash: Some("abc123".to_string()),
+        };
+        point.save(&dir).unwrap();
+        assert_eq!(ReturnPoint::load(&dir), Some(point));
+
+        ReturnPoint::clear(&dir).unwrap();
+        assert_eq!(ReturnPoint::load(&dir), None);
+        ReturnPoint::clear(&dir).unwrap();
+    }
+}
@@ -0,0 +1,261 @@
+//! New activity on PRs since they were last looked at
+//!
+//! Each PR's activity (head commit, review and comment counts, CI state,
+//! review request) is remembered when the user selects it and compared with
+//! every poll of the PR list; differences are unread. Stored under
+//! `.git/timecop/seen.json` so unread markers survive app restarts.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::forge::{CheckStatus, PrSummary};
+
+/// What a PR looked like at one point
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Activity {
+    pub head_sha: String,
+    pub reviews: u64,
+    pub comments: u64,
+    /// Rollup of the head's checks; None without checks
+    pub checks: Option<CheckStatus>,
+    pub review_requested: bool,
+}
+
+impl Activity {
+    pub fn of(pr: &PrSummary) -> Self {
+        Self {
+            head_sha: pr.head_sha.clone(),
+            reviews: pr.review_count,
+            comments: pr.comment_count,
+            checks: rollup(&pr.checks),
+            review_requested: pr.review_requested,
+        }
+    }
+
+    /// What happened since `before`, e.g. ["new commits", "checks failed"]
+    pub fn news_since(&self, before: &Activity) -> Vec<&'static str> {
+        let mut news = Vec::new();
+        if self.head_sha != before.head_sha && !before.head_sha.is_empty() {
+            news.push("new commits");
+        }
+        if self.reviews > before.reviews {
+            news.push("new review");
+        }
+        if self.comments > before.comments {
+            news.push("new comments");
+        }
+        if self.checks != before.checks {
+            // Checks going pending just follows a push
+            match self.checks {
+                Some(CheckStatus::Failure) => news.push("checks failed"),
+                Some(CheckStatus::Success) => news.push("checks passed"),
+                _ => {}
+            }
+        }
+        if self.review_requested && !before.review_requested {
+            news.push("review requested");
+        }
+        news
+    }
+}
+
+/// Overall state of a PR's checks: any failure fails, any pending is pending
+pub fn rollup(checks: &[CheckStatus]) -> Option<CheckStatus> {
+    if checks.is_empty() {
+        None
+    } else if checks.contains(&CheckStatus::Failure) {
+        Some(CheckStatus::Failure)
+    } else if checks.contains(&CheckStatus::Pending) {
+        Some(CheckStatus::Pending)
+    } else {
+        Some(CheckStatus::Success)
+    }
+}
+
+/// Changes between two polls of the PR list, as "#12 new review, checks failed"
+pub fn poll_news(previous: &HashMap<u64, Activity>, prs: &[PrSummary]) -> Vec<String> {
+    prs.iter()
+        .filter_map(|pr| {
+            let now = Activity::of(pr);
+            let news = match previous.get(&pr.number) {
+                Some(before) => now.news_since(before),
+                // A PR opened since the last poll only matters if it wants a review
+                None if pr.review_requested => vec!["review requested"],
+                None => Vec::new(),
+            };
+            (!news.is_empty()).then(|| format!("#{} {}", pr.number, news.join(", ")))
+        })
+        .collect()
+}
+
+/// Activity last seen per PR number
+#[derive(Debug)]
+pub struct SeenStore {
+    path: PathBuf,
+    seen: HashMap<u64, Activity>,
+}
+
+impl SeenStore {
+    /// Load the seen state for the repository at `git_dir`
+    pub fn load(git_dir: &Path) -> Self {
+        let path = git_dir.join("timecop").join("seen.json");
+        let seen = std::fs::read(&path)
+            .ok()
+            .and_then(|data| serde_json::from_slice::<Vec<(u64, Activity)>>(&data).ok())
+            .map(|entries| entries.into_iter().collect())
+            .unwrap_or_default();
+        Self { path, seen }
+    }
+
+    /// What's new on a PR since it was last seen. A PR never seen before only
+    /// has news if it asks for the user's review.
+    pub fn news(&self, pr: &PrSummary) -> Vec<&'static str> {
+        match self.seen.get(&pr.number) {
+            Some(seen) => Activity::of(pr).news_since(seen),
+            None if pr.review_requested => vec!["review requested"],
+            None => Vec::new(),
+        }
+    }
+
+    /// Remember the PR's current activity as seen
+    pub fn mark_seen(&mut self, pr: &PrSummary) -> Result<()> {
+        let activity = Activity::of(pr);
+        if self.seen.get(&pr.number) == Some(&activity) {
+            return Ok(());
+        }
+        self.seen.insert(pr.number, activity);
+        self.save()
+    }
+
+    /// Take in a poll of the open PRs. On the very first poll everything
+    /// counts as seen; later, new PRs are seen unless they request a review,
+    /// and PRs no longer open are forgotten.
+    pub fn sync(&mut self, prs: &[PrSummary]) -> Result<()> {
+        // An empty list is as likely a failed fetch as no open PRs
+        if prs.is_empty() {
+            return Ok(());
+        }
+        let first_poll = self.seen.is_empty();
+        let before = self.seen.len();
+        self.seen.retain(|number, _| prs.iter().any(|pr| pr.number == *number));
+        let mut changed = self.seen.len() != before;
+
+        for pr in prs {
+            if !self.seen.contains_key(&pr.number) && (first_poll || !pr.review_requested) {
+                self.seen.insert(pr.number, Activity::of(pr));
+                changed = true;
+            }
+        }
+        if changed {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).context("Failed to create state directory")?;
+        }
+        // JSON object keys must be strings; store pairs sorted by number
+        let mut entries: Vec<_> = self.seen.iter().collect();
+        entries.sort_by_key(|(number, _)| **number);
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&entries)?).context("Failed to save seen PR state")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn pr(number: u64, head_sha: &str) -> PrSummary {
+        PrSummary {
+            number,
+            title: String::new(),
+            author: String::new(),
+            branch: String::new(),
+            base_branch: String::new(),
+            updated_at: String::new(),
+            created_at: String::new(),
+            review_requested: false,
+            authored_by_viewer: false,
+            draft: false,
+            labels: Vec::new(),
+            additions: 0,
+            deletions: 0,
+            checks: Vec::new(),
+            head_sha: head_sha.to_string(),
+            head_repo: String::new(),
+            review_count: 0,
+            comment_count: 0,
+        }
+    }
+
+    // --- Activity ---
+
+    #[test]
+    fn news_since_lists_each_change() {
+        let before = Activity::of(&pr(1, "a"));
+        let mut now = pr(1, "b");
+        now.review_count = 1;
+        now.comment_count = 2;
+        now.checks = vec![CheckStatus::Success, CheckStatus::Failure];
+        now.review_requested = true;
+        assert_eq!(
+            Activity::of(&now).news_since(&before),
+            ["new commits", "new review", "new comments", "checks failed", "review requested"]
+        );
+        assert!(Activity::of(&now).news_since(&Activity::of(&now)).is_empty());
+    }
+
+    #[test]
+    fn pending_checks_are_not_news() {
+        let mut now = pr(1, "a");
+        now.checks = vec![CheckStatus::Success, CheckStatus::Pending];
+        assert_eq!(rollup(&now.checks), Some(CheckStatus::Pending));
+        assert!(Activity::of(&now).news_since(&Activity::of(&pr(1, "a"))).is_empty());
+    }
+
+    #[test]
+    fn poll_news_reports_changes_and_new_review_requests() {
+        let previous: HashMap<u64, Activity> = [(1, Activity::of(&pr(1, "a")))].into();
+        let mut requested = pr(2, "x");
+        requested.review_requested = true;
+        let prs = vec![pr(1, "b"), requested, pr(3, "y")];
+        assert_eq!(poll_news(&previous, &prs), ["#1 new commits", "#2 review requested"]);
+    }
+
+    // --- SeenStore ---
+
+    #[test]
+    fn first_sync_marks_everything_seen() {
+        let dir = TempDir::new("seen-first");
+        let mut store = SeenStore::load(&dir);
+        let mut requested = pr(2, "x");
+        requested.review_requested = true;
+        store.sync(&[pr(1, "a"), requested.clone()]).unwrap();
+        assert!(store.news(&requested).is_empty());
+
+        // Later polls leave new review requests unread
+        let mut another = pr(3, "y");
+        another.review_requested = true;
+        store.sync(&[pr(1, "a"), requested, another.clone()]).unwrap();
+        assert_eq!(store.news(&another), ["review requested"]);
+    }
+
+    #[test]
+    fn mark_seen_persists_and_closed_prs_are_forgotten() {
+        let dir = TempDir::new("seen-persist");
+        let mut store = SeenStore::load(&dir);
+        store.sync(&[pr(1, "a"), pr(2, "b")]).unwrap();
+        assert_eq!(store.news(&pr(1, "c")), ["new commits"]);
+
+        store.mark_seen(&pr(1, "c")).unwrap();
+        store.sync(&[pr(1, "c")]).unwrap();
+        let reloaded = SeenStore::load(&dir);
+        assert!(reloaded.news(&pr(1, "c")).is_empty());
+        assert!(!reloaded.seen.contains_key(&2));
+    }
+}
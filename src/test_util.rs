@@ -0,0 +1,77 @@
+//! Fixtures shared by the unit tests
+
+use git2::{Oid, Repository};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// Empty directory under the system temp dir, removed again when dropped
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// `name` keeps the directories of tests running in parallel apart
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("timecop-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Repository initialized in its own `TempDir`
+pub struct TestRepo {
+    pub repo: Repository,
+    pub dir: TempDir,
+}
+
+impl TestRepo {
+    pub fn new(name: &str) -> Self {
+        let dir = TempDir::new(name);
+        let repo = Repository::init(&dir).unwrap();
+        Self { repo, dir }
+    }
+
+    /// Commit `files` on top of `parent`'s tree (an empty one without a
+    /// parent), pointing `update_ref` at the new commit when given
+    pub fn commit(&self, parent: Option<Oid>, message: &str, files: &[(&str, &str)], update_ref: Option<&str>) -> Oid {
+        let repo = &self.repo;
+        let parent = parent.map(|oid| repo.find_commit(oid).unwrap());
+        let mut builder = repo.treebuilder(parent.as_ref().map(|p| p.tree().unwrap()).as_ref()).unwrap();
+        for (path, content) in files {
+            builder.insert(path, repo.blob(content.as_bytes()).unwrap(), 0o100644).unwrap();
+        }
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        let oid = repo.commit(None, &sig, &sig, message, &tree, &parents).unwrap();
+        if let Some(name) = update_ref {
+            repo.reference(name, oid, true, "test").unwrap();
+        }
+        oid
+    }
+
+    /// Point HEAD at `refname` and make the index and working tree match it
+    pub fn checkout(&self, refname: &str) {
+        self.repo.set_head(refname).unwrap();
+        self.repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force())).unwrap();
+    }
+}
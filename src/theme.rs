@@ -205,7 +205,7 @@ impl ThemeMode {
     fn parse_osc11_response(response: &str) -> Option<Self> {
         let rgb_start = response.find("rgb:")?;
         let rgb_part = &response[rgb_start + 4..];
-        let rgb_end = rgb_part.find(|c| c == '\x07' || c == '\x1b').unwrap_or(rgb_part.len());
+        let rgb_end = rgb_part.find(['\x07', '\x1b']).unwrap_or(rgb_part.len());
         let rgb_str = &rgb_part[..rgb_end];
 
         let parts: Vec<&str> = rgb_str.split('/').collect();
@@ -0,0 +1,31 @@
+//! Formatting helpers shared by the app and its widgets
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Current time in seconds since the epoch
+pub fn unix_now() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Time since `timestamp` (seconds since the epoch), e.g. "45s", "3m", "2h"
+pub fn format_age(timestamp: i64) -> String {
+    let secs = (unix_now() - timestamp).max(0);
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        _ => format!("{}h", secs / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_age_picks_units() {
+        let now = unix_now();
+        assert_eq!(format_age(now + 5), "0s");
+        assert_eq!(format_age(now - 150), "2m");
+        assert_eq!(format_age(now - 7200), "2h");
+    }
+}
@@ -26,11 +26,11 @@ pub struct LayoutAreas {
 }
 
 impl AppLayout {
-    pub fn compute(&self, area: Rect, pr_count: usize) -> LayoutAreas {
-        // PR panel height: fits all PRs, max 16 visible
-        let pr_height = match pr_count {
+    pub fn compute(&self, area: Rect, pr_rows: usize) -> LayoutAreas {
+        // PR panel height: fits all rows (tab row + PRs), max 16 visible
+        let pr_height = match pr_rows {
             0 => 3,                                        // border + "No open PRs"
-            _ => ((pr_count + 2) as u16).min(18),          // border + count, max 16 visible
+            _ => ((pr_rows + 2) as u16).min(18),           // border + rows, max 16 visible
         };
 
         // Split: header | main content | PR panel | status bar
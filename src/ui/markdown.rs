@@ -0,0 +1,730 @@
+//! Terminal rendering for markdown in PR descriptions and comments
+//!
+//! Covers what typically shows up in reviews: headings, emphasis, lists,
+//! block quotes, fenced code (highlighted via `Highlighter`), tables, links
+//! and rules. Parsing is done once per body; wrapping is a separate pass so
+//! panes can re-wrap cheaply when their width changes.
+
+use std::collections::{HashMap, HashSet};
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::config::Colors;
+
+use super::Highlighter;
+
+/// What a span of text is, for styling purposes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpanKind {
+    Text,
+    Heading,
+    Quote,
+    /// List bullets, numbers and task boxes
+    Marker,
+    Code,
+    Link,
+    /// Horizontal rules and table borders
+    Rule,
+    /// Syntax-highlighted code from a fenced block
+    Syntax(Style),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MdStyle {
+    pub kind: SpanKind,
+    pub strong: bool,
+    pub emphasis: bool,
+    pub strike: bool,
+}
+
+impl MdStyle {
+    fn of(kind: SpanKind) -> Self {
+        Self { kind, strong: false, emphasis: false, strike: false }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MdSpan {
+    pub text: String,
+    pub style: MdStyle,
+}
+
+/// One rendered line of markdown
+#[derive(Debug, Clone, PartialEq)]
+pub struct MdLine {
+    /// Decoration before the first row (list marker, quote bar, indent)
+    pub prefix: Vec<MdSpan>,
+    /// Decoration before wrapped continuation rows
+    pub continuation: Vec<MdSpan>,
+    pub spans: Vec<MdSpan>,
+    /// Code blocks and tables keep their layout and are never wrapped
+    pub wrap: bool,
+    /// Horizontal rule, drawn across the available width
+    pub rule: bool,
+}
+
+impl MdLine {
+    fn new(spans: Vec<MdSpan>) -> Self {
+        Self { prefix: vec![], continuation: vec![], spans, wrap: true, rule: false }
+    }
+
+    /// Text without styling, including the prefix
+    pub fn plain_text(&self) -> String {
+        self.prefix.iter().chain(&self.spans).map(|s| s.text.as_str()).collect()
+    }
+}
+
+fn span(text: impl Into<String>, kind: SpanKind) -> MdSpan {
+    MdSpan { text: text.into(), style: MdStyle::of(kind) }
+}
+
+fn spans_width(spans: &[MdSpan]) -> usize {
+    spans.iter().map(|s| s.text.width()).sum()
+}
+
+/// Parse markdown into unwrapped lines. Fenced code is highlighted when a
+/// highlighter is given and the fence names a language.
+pub fn parse_markdown(text: &str, highlighter: Option<&Highlighter>) -> Vec<MdLine> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let raw = lines[i];
+        let trimmed = raw.trim_start();
+
+        if let Some(fence) = fence_marker(trimmed) {
+            let lang = trimmed[fence.len()..].trim();
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with(fence) {
+                code.push(lines[i]);
+                i += 1;
+            }
+            i += 1; // closing fence
+            out.extend(code_block(&code, lang, highlighter));
+            continue;
+        }
+
+        if trimmed.starts_with('|') && lines.get(i + 1).is_some_and(|l| is_table_separator(l)) {
+            let mut rows = vec![split_row(trimmed)];
+            i += 2;
+            while i < lines.len() && lines[i].trim_start().starts_with('|') {
+                rows.push(split_row(lines[i]));
+                i += 1;
+            }
+            out.extend(table(&rows));
+            continue;
+        }
+
+        out.push(block_line(raw));
+        i += 1;
+    }
+
+    out
+}
+
+fn fence_marker(line: &str) -> Option<&'static str> {
+    if line.starts_with("```") {
+        Some("```")
+    } else if line.starts_with("~~~") {
+        Some("~~~")
+    } else {
+        None
+    }
+}
+
+/// File extension the highlighter understands for a fence language
+fn fence_extension(lang: &str) -> &str {
+    let lang = lang.split(|c: char| c.is_whitespace() || c == ',' || c == '{').next().unwrap_or("");
+    match lang.to_ascii_lowercase().as_str() {
+        "rust" => "rs",
+        "python" | "py" => "py",
+        "javascript" | "js" | "node" => "js",
+        "typescript" | "ts" => "ts",
+        "bash" | "shell" | "sh" | "zsh" | "console" => "sh",
+        "ruby" | "rb" => "rb",
+        "golang" | "go" => "go",
+        "c++" | "cpp" => "cpp",
+        "csharp" | "c#" | "cs" => "cs",
+        "yaml" | "yml" => "yaml",
+        "markdown" | "md" => "md",
+        "diff" | "patch" => "diff",
+        _ => lang,
+    }
+}
+
+fn code_block(code: &[&str], lang: &str, highlighter: Option<&Highlighter>) -> Vec<MdLine> {
+    let highlighted = match highlighter {
+        Some(h) if !lang.is_empty() => {
+            Some(h.highlight_file(&code.join("\n"), &format!("code.{}", fence_extension(lang))))
+        }
+        _ => None,
+    };
+
+    code.iter()
+        .enumerate()
+        .map(|(n, line)| {
+            let spans = match highlighted.as_ref().and_then(|h| h.get(n)) {
+                Some(hl) => hl
+                    .iter()
+                    .map(|(text, style)| span(text.replace('\t', "    "), SpanKind::Syntax(*style)))
+                    .collect(),
+                None => vec![span(line.replace('\t', "    "), SpanKind::Code)],
+            };
+            MdLine {
+                prefix: vec![span("  ", SpanKind::Code)],
+                wrap: false,
+                ..MdLine::new(spans)
+            }
+        })
+        .collect()
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let line = line.trim();
+    line.contains('-') && line.chars().all(|c| matches!(c, '|' | ':' | '-' | ' '))
+}
+
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(|cell| cell.trim().to_string()).collect()
+}
+
+fn table(rows: &[Vec<String>]) -> Vec<MdLine> {
+    let cells: Vec<Vec<Vec<MdSpan>>> = rows
+        .iter()
+        .enumerate()
+        .map(|(r, row)| {
+            row.iter()
+                .map(|cell| {
+                    let mut spans = inline(cell, SpanKind::Text);
+                    if r == 0 {
+                        spans.iter_mut().for_each(|s| s.style.strong = true);
+                    }
+                    spans
+                })
+                .collect()
+        })
+        .collect();
+
+    let columns = cells.iter().map(|r| r.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| cells.iter().filter_map(|r| r.get(c)).map(|s| spans_width(s)).max().unwrap_or(0))
+        .collect();
+
+    let mut out = Vec::new();
+    for (r, row) in cells.into_iter().enumerate() {
+        let mut spans = Vec::new();
+        for (c, width) in widths.iter().enumerate() {
+            if c > 0 {
+                spans.push(span(" │ ", SpanKind::Rule));
+            }
+            let cell = row.get(c).cloned().unwrap_or_default();
+            let pad = width - spans_width(&cell);
+            spans.extend(cell);
+            if pad > 0 {
+                spans.push(span(" ".repeat(pad), SpanKind::Text));
+            }
+        }
+        out.push(MdLine { wrap: false, ..MdLine::new(spans) });
+
+        if r == 0 {
+            let border = widths.iter().map(|w| "─".repeat(*w)).collect::<Vec<_>>().join("─┼─");
+            out.push(MdLine { wrap: false, ..MdLine::new(vec![span(border, SpanKind::Rule)]) });
+        }
+    }
+    out
+}
+
+fn is_rule(line: &str) -> bool {
+    let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.len() >= 3 && matches!(chars[0], '-' | '*' | '_') && chars.iter().all(|c| *c == chars[0])
+}
+
+fn heading(line: &str) -> Option<&str> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if rest.is_empty() {
+        return Some("");
+    }
+    rest.strip_prefix(' ').map(|r| r.trim().trim_end_matches('#').trim_end())
+}
+
+/// List marker to display and the item text
+fn list_item(line: &str) -> Option<(String, &str)> {
+    let (marker, rest) = if let Some(rest) = ["- ", "* ", "+ "].iter().find_map(|m| line.strip_prefix(m)) {
+        ("•".to_string(), rest)
+    } else {
+        let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 || digits > 9 {
+            return None;
+        }
+        let rest = line[digits..].strip_prefix(". ").or_else(|| line[digits..].strip_prefix(") "))?;
+        (format!("{}.", &line[..digits]), rest)
+    };
+
+    // Task list items
+    if let Some(rest) = rest.strip_prefix("[ ] ") {
+        return Some((format!("{} ☐", marker), rest));
+    }
+    if let Some(rest) = rest.strip_prefix("[x] ").or_else(|| rest.strip_prefix("[X] ")) {
+        return Some((format!("{} ☑", marker), rest));
+    }
+    Some((marker, rest))
+}
+
+/// Headings, rules, quotes, list items and paragraph lines
+fn block_line(raw: &str) -> MdLine {
+    let mut prefix = Vec::new();
+    let mut rest = raw;
+
+    // Block quotes, possibly nested
+    while let Some(inner) = rest.trim_start().strip_prefix('>') {
+        prefix.push(span("│ ", SpanKind::Quote));
+        rest = inner.strip_prefix(' ').unwrap_or(inner);
+    }
+    let base = if prefix.is_empty() { SpanKind::Text } else { SpanKind::Quote };
+    let continuation = prefix.clone();
+
+    let trimmed = rest.trim_start();
+    let indent = rest.len() - trimmed.len();
+
+    if is_rule(trimmed) {
+        return MdLine { prefix, continuation, rule: true, ..MdLine::new(vec![]) };
+    }
+
+    if let Some(text) = heading(trimmed) {
+        let mut spans = inline(text, SpanKind::Heading);
+        spans.iter_mut().for_each(|s| s.style.strong = true);
+        return MdLine { prefix, continuation, ..MdLine::new(spans) };
+    }
+
+    if let Some((marker, text)) = list_item(trimmed) {
+        let mut prefix = prefix;
+        let mut continuation = continuation;
+        let lead = " ".repeat(indent);
+        continuation.push(span(" ".repeat(indent + marker.width() + 1), SpanKind::Text));
+        prefix.push(span(format!("{}{} ", lead, marker), SpanKind::Marker));
+        return MdLine { prefix, continuation, ..MdLine::new(inline(text, base)) };
+    }
+
+    MdLine { prefix, continuation, ..MdLine::new(inline(rest, base)) }
+}
+
+/// Parse inline markup: emphasis, code, strikethrough, links and images
+fn inline(text: &str, base: SpanKind) -> Vec<MdSpan> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = Vec::new();
+    let mut buf = String::new();
+    let mut style = MdStyle::of(base);
+    let mut i = 0;
+
+    let flush = |buf: &mut String, spans: &mut Vec<MdSpan>, style: MdStyle| {
+        if !buf.is_empty() {
+            spans.push(MdSpan { text: std::mem::take(buf), style });
+        }
+    };
+    let closes_later = |from: usize, delim: &[char]| {
+        chars[from..].windows(delim.len()).any(|w| w == delim)
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        match c {
+            '\\' if next.is_some_and(|n| n.is_ascii_punctuation()) => {
+                buf.push(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+            '`' => {
+                if let Some(end) = chars[i + 1..].iter().position(|&ch| ch == '`') {
+                    flush(&mut buf, &mut spans, style);
+                    let code: String = chars[i + 1..i + 1 + end].iter().collect();
+                    spans.push(MdSpan { text: code, style: MdStyle { kind: SpanKind::Code, ..style } });
+                    i += end + 2;
+                    continue;
+                }
+            }
+            '*' | '_' => {
+                let double = next == Some(c);
+                let len = if double { 2 } else { 1 };
+                let delim = vec![c; len];
+                let open = if double { style.strong } else { style.emphasis };
+                // Underscores inside words (snake_case) are literal
+                let intraword = c == '_'
+                    && i > 0
+                    && chars[i - 1].is_alphanumeric()
+                    && chars.get(i + len).is_some_and(|ch| ch.is_alphanumeric());
+                let can_open = chars.get(i + len).is_some_and(|ch| !ch.is_whitespace())
+                    && closes_later(i + len + 1, &delim);
+                if !intraword && (open || can_open) {
+                    flush(&mut buf, &mut spans, style);
+                    if double {
+                        style.strong = !style.strong;
+                    } else {
+                        style.emphasis = !style.emphasis;
+                    }
+                    i += len;
+                    continue;
+                }
+            }
+            '~' if next == Some('~') && (style.strike || closes_later(i + 2, &['~', '~'])) => {
+                flush(&mut buf, &mut spans, style);
+                style.strike = !style.strike;
+                i += 2;
+                continue;
+            }
+            '!' if next == Some('[') => {
+                if let Some((label, _, end)) = parse_link(&chars, i + 1) {
+                    flush(&mut buf, &mut spans, style);
+                    let text = format!("[image: {}]", label);
+                    spans.push(MdSpan { text, style: MdStyle { kind: SpanKind::Link, ..style } });
+                    i = end;
+                    continue;
+                }
+            }
+            '[' => {
+                if let Some((label, _, end)) = parse_link(&chars, i) {
+                    flush(&mut buf, &mut spans, style);
+                    spans.extend(
+                        inline(&label, SpanKind::Link)
+                            .into_iter()
+                            .map(|s| MdSpan { style: MdStyle { kind: SpanKind::Link, ..merge(style, s.style) }, ..s }),
+                    );
+                    i = end;
+                    continue;
+                }
+            }
+            'h' if i == 0 || chars[i - 1].is_whitespace() || chars[i - 1] == '(' => {
+                let rest: String = chars[i..].iter().take(8).collect();
+                if rest.starts_with("http://") || rest.starts_with("https://") {
+                    flush(&mut buf, &mut spans, style);
+                    let len = chars[i..].iter().take_while(|ch| !ch.is_whitespace()).count();
+                    let mut url: String = chars[i..i + len].iter().collect();
+                    // Trailing punctuation belongs to the sentence
+                    while url.ends_with(['.', ',', ')', ';', ':']) {
+                        url.pop();
+                    }
+                    i += url.chars().count();
+                    spans.push(MdSpan { text: url, style: MdStyle { kind: SpanKind::Link, ..style } });
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        buf.push(c);
+        i += 1;
+    }
+
+    flush(&mut buf, &mut spans, style);
+    spans
+}
+
+fn merge(outer: MdStyle, inner: MdStyle) -> MdStyle {
+    MdStyle {
+        kind: inner.kind,
+        strong: outer.strong || inner.strong,
+        emphasis: outer.emphasis || inner.emphasis,
+        strike: outer.strike || inner.strike,
+    }
+}
+
+/// Parse `[label](url)` starting at `start`; returns label, url and the index after it
+fn parse_link(chars: &[char], start: usize) -> Option<(String, String, usize)> {
+    if chars.get(start) != Some(&'[') {
+        return None;
+    }
+    let close = start + chars[start..].iter().position(|&c| c == ']')?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+    let end = close + 1 + chars[close + 1..].iter().position(|&c| c == ')')?;
+    let label = chars[start + 1..close].iter().collect();
+    let url = chars[close + 2..end].iter().collect();
+    Some((label, url, end + 1))
+}
+
+/// Wrap lines to `width` columns, repeating quote bars and list indentation
+/// on continuation rows
+pub fn wrap_lines(lines: &[MdLine], width: usize) -> Vec<MdLine> {
+    let mut out = Vec::new();
+
+    for line in lines {
+        let prefix_width = spans_width(&line.prefix);
+        if line.rule {
+            let len = width.saturating_sub(prefix_width).clamp(3, 80);
+            out.push(MdLine { spans: vec![span("─".repeat(len), SpanKind::Rule)], rule: false, ..line.clone() });
+            continue;
+        }
+        if !line.wrap || prefix_width + spans_width(&line.spans) <= width {
+            out.push(line.clone());
+            continue;
+        }
+
+        let avail = width.saturating_sub(spans_width(&line.continuation).max(prefix_width)).max(10);
+        let mut rows: Vec<Vec<MdSpan>> = vec![vec![]];
+        let mut used = 0;
+
+        for s in &line.spans {
+            for token in split_words(&s.text) {
+                let is_space = token.trim().is_empty();
+                let mut token = token.to_string();
+                if used + token.width() > avail && used > 0 {
+                    if is_space {
+                        continue;
+                    }
+                    trim_row_end(rows.last_mut().unwrap());
+                    rows.push(vec![]);
+                    used = 0;
+                }
+                if is_space && used == 0 {
+                    continue;
+                }
+                // Hard-split words longer than a whole row
+                while token.width() > avail {
+                    let (head, tail) = split_at_width(&token, avail - used);
+                    push_text(rows.last_mut().unwrap(), head, s.style);
+                    rows.push(vec![]);
+                    used = 0;
+                    token = tail;
+                }
+                used += token.width();
+                push_text(rows.last_mut().unwrap(), token, s.style);
+            }
+        }
+
+        for (n, spans) in rows.into_iter().enumerate() {
+            let prefix = if n == 0 { line.prefix.clone() } else { line.continuation.clone() };
+            out.push(MdLine { prefix, spans, ..line.clone() });
+        }
+    }
+
+    out
+}
+
+/// Split into alternating runs of words and whitespace
+fn split_words(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut in_space = None;
+    for (idx, c) in text.char_indices() {
+        let space = c.is_whitespace();
+        if in_space.is_some_and(|s| s != space) {
+            tokens.push(&text[start..idx]);
+            start = idx;
+        }
+        in_space = Some(space);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+fn split_at_width(text: &str, width: usize) -> (String, String) {
+    let mut used = 0;
+    for (idx, c) in text.char_indices() {
+        let w = c.width().unwrap_or(0);
+        if used + w > width && idx > 0 {
+            return (text[..idx].to_string(), text[idx..].to_string());
+        }
+        used += w;
+    }
+    (text.to_string(), String::new())
+}
+
+/// Drop whitespace left at the end of a row before it wraps
+fn trim_row_end(row: &mut Vec<MdSpan>) {
+    while let Some(last) = row.last_mut() {
+        let trimmed = last.text.trim_end().len();
+        if trimmed > 0 {
+            last.text.truncate(trimmed);
+            return;
+        }
+        row.pop();
+    }
+}
+
+fn push_text(row: &mut Vec<MdSpan>, text: String, style: MdStyle) {
+    match row.last_mut() {
+        Some(last) if last.style == style => last.text.push_str(&text),
+        _ => row.push(MdSpan { text, style }),
+    }
+}
+
+/// Convert a wrapped line to a styled terminal line on top of `base`
+pub fn to_line(line: &MdLine, base: Style, colors: &Colors) -> Line<'static> {
+    line.prefix
+        .iter()
+        .chain(&line.spans)
+        .map(|s| Span::styled(s.text.clone(), span_style(s.style, base, colors)))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+fn span_style(style: MdStyle, base: Style, colors: &Colors) -> Style {
+    let mut result = match style.kind {
+        SpanKind::Text => base,
+        SpanKind::Heading => base.fg(colors.header),
+        SpanKind::Quote => base.fg(colors.muted).add_modifier(Modifier::ITALIC),
+        SpanKind::Marker => base.fg(colors.header),
+        SpanKind::Code => base.fg(colors.modified),
+        SpanKind::Link => base.fg(colors.renamed).add_modifier(Modifier::UNDERLINED),
+        SpanKind::Rule => base.fg(colors.muted),
+        // Keep the pane's background; take only the highlighter's colors
+        SpanKind::Syntax(hl) => {
+            let styled = match hl.fg {
+                Some(fg) => base.fg(fg),
+                None => base,
+            };
+            styled.add_modifier(hl.add_modifier)
+        }
+    };
+    if style.strong {
+        result = result.add_modifier(Modifier::BOLD);
+    }
+    if style.emphasis {
+        result = result.add_modifier(Modifier::ITALIC);
+    }
+    if style.strike {
+        result = result.add_modifier(Modifier::CROSSED_OUT);
+    }
+    result
+}
+
+/// Parsed markdown keyed by source text, so bodies are highlighted once and
+/// re-wrapped cheaply on resize
+#[derive(Debug, Default)]
+pub struct MarkdownCache {
+    parsed: HashMap<String, Vec<MdLine>>,
+}
+
+impl MarkdownCache {
+    /// Keep highlighted parses of exactly `texts`, parsing only new ones
+    pub fn prime<'a>(&mut self, texts: impl IntoIterator<Item = &'a str>, highlighter: &Highlighter) {
+        let texts: HashSet<&str> = texts.into_iter().filter(|t| !t.is_empty()).collect();
+        self.parsed.retain(|text, _| texts.contains(text.as_str()));
+        for text in texts {
+            if !self.parsed.contains_key(text) {
+                self.parsed.insert(text.to_string(), parse_markdown(text, Some(highlighter)));
+            }
+        }
+    }
+
+    /// Render `text` wrapped to `width`, with `indent` before every row.
+    /// Texts that weren't primed are parsed without syntax highlighting.
+    pub fn render(&self, text: &str, indent: &str, width: usize) -> Vec<MdLine> {
+        let width = width.saturating_sub(indent.width());
+        let mut lines = match self.parsed.get(text) {
+            Some(parsed) => wrap_lines(parsed, width),
+            None => wrap_lines(&parse_markdown(text, None), width),
+        };
+        if !indent.is_empty() {
+            for line in &mut lines {
+                line.prefix.insert(0, span(indent, SpanKind::Text));
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(spans: &[MdSpan]) -> Vec<&str> {
+        spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    // --- inline ---
+
+    #[test]
+    fn inline_emphasis_and_code() {
+        let spans = inline("a **bold** and *em* with `code`", SpanKind::Text);
+        assert_eq!(texts(&spans), vec!["a ", "bold", " and ", "em", " with ", "code"]);
+        assert!(spans[1].style.strong);
+        assert!(spans[3].style.emphasis);
+        assert_eq!(spans[5].style.kind, SpanKind::Code);
+    }
+
+    #[test]
+    fn inline_leaves_unmatched_markers_and_snake_case() {
+        let spans = inline("2 * 3 = some_var_name", SpanKind::Text);
+        assert_eq!(texts(&spans), vec!["2 * 3 = some_var_name"]);
+    }
+
+    #[test]
+    fn inline_links_and_images() {
+        let spans = inline("see [docs](https://x.dev) ![logo](a.png) https://y.dev.", SpanKind::Text);
+        assert_eq!(texts(&spans), vec!["see ", "docs", " ", "[image: logo]", " ", "https://y.dev", "."]);
+        assert_eq!(spans[1].style.kind, SpanKind::Link);
+        assert_eq!(spans[5].style.kind, SpanKind::Link);
+    }
+
+    // --- blocks ---
+
+    #[test]
+    fn lists_quotes_and_headings() {
+        let lines = parse_markdown("## Title\n- item\n  1. nested\n- [x] done\n> quoted", None);
+        assert_eq!(lines[0].plain_text(), "Title");
+        assert!(lines[0].spans[0].style.strong);
+        assert_eq!(lines[1].plain_text(), "• item");
+        assert_eq!(lines[2].plain_text(), "  1. nested");
+        assert_eq!(lines[3].plain_text(), "• ☑ done");
+        assert_eq!(lines[4].plain_text(), "│ quoted");
+    }
+
+    #[test]
+    fn fenced_code_is_kept_verbatim() {
+        let lines = parse_markdown("```\nlet **x** = 1;\n```\nafter", None);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].plain_text(), "  let **x** = 1;");
+        assert!(!lines[0].wrap);
+        assert_eq!(lines[1].plain_text(), "after");
+    }
+
+    #[test]
+    fn fenced_code_is_highlighted_with_language() {
+        let highlighter = Highlighter::for_theme(crate::theme::ThemeMode::Dark);
+        let lines = parse_markdown("```rust\nfn main() {}\n```", Some(&highlighter));
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].spans.iter().all(|s| matches!(s.style.kind, SpanKind::Syntax(_))));
+    }
+
+    #[test]
+    fn tables_align_columns() {
+        let lines = parse_markdown("| a | long |\n|---|---|\n| xyz | b |", None);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].plain_text(), "a   │ long");
+        assert_eq!(lines[1].plain_text(), "────┼─────");
+        assert_eq!(lines[2].plain_text(), "xyz │ b   ");
+    }
+
+    // --- wrapping ---
+
+    #[test]
+    fn wrap_keeps_list_indent_on_continuation() {
+        let lines = wrap_lines(&parse_markdown("- one two three four five six", None), 14);
+        let rows: Vec<String> = lines.iter().map(|l| l.plain_text()).collect();
+        assert_eq!(rows, vec!["• one two", "  three four", "  five six"]);
+    }
+
+    #[test]
+    fn render_indents_every_row() {
+        let cache = MarkdownCache::default();
+        let lines = cache.render("alpha beta gamma", "  ", 14);
+        let rows: Vec<String> = lines.iter().map(|l| l.plain_text()).collect();
+        assert_eq!(rows, vec!["  alpha beta", "  gamma"]);
+    }
+}
@@ -1,7 +1,10 @@
+pub mod format;
 pub mod layout;
+pub mod markdown;
 pub mod syntax;
 pub mod widgets;
 
+pub use format::format_age;
 pub use layout::{AppLayout, LayoutAreas, centered_rect};
 pub use syntax::Highlighter;
 pub use widgets::*;
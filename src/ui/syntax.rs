@@ -125,6 +125,33 @@ fn syntect_to_ratatui_color(color: syntect::highlighting::Color) -> Color {
     Color::Rgb(color.r, color.g, color.b)
 }
 
+/// Darken colors for light mode - convert bright colors to dark equivalents
+fn darken_for_light_mode(color: syntect::highlighting::Color) -> Color {
+    // Convert to HSL-like adjustment: reduce lightness significantly
+    let r = color.r as f32 / 255.0;
+    let g = color.g as f32 / 255.0;
+    let b = color.b as f32 / 255.0;
+
+    // Calculate luminance
+    let lum = 0.299 * r + 0.587 * g + 0.114 * b;
+
+    // If the color is bright (designed for dark bg), darken it substantially
+    let (new_r, new_g, new_b) = if lum > 0.5 {
+        // Darken bright colors - multiply by factor to reduce brightness
+        let factor = 0.35; // Make quite dark
+        (
+            (r * factor * 255.0) as u8,
+            (g * factor * 255.0) as u8,
+            (b * factor * 255.0) as u8,
+        )
+    } else {
+        // Already dark, keep as is or slightly adjust
+        (color.r, color.g, color.b)
+    };
+
+    Color::Rgb(new_r, new_g, new_b)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -167,30 +194,3 @@ mod tests {
         assert_eq!(reconstructed, content);
     }
 }
-
-/// Darken colors for light mode - convert bright colors to dark equivalents
-fn darken_for_light_mode(color: syntect::highlighting::Color) -> Color {
-    // Convert to HSL-like adjustment: reduce lightness significantly
-    let r = color.r as f32 / 255.0;
-    let g = color.g as f32 / 255.0;
-    let b = color.b as f32 / 255.0;
-
-    // Calculate luminance
-    let lum = 0.299 * r + 0.587 * g + 0.114 * b;
-
-    // If the color is bright (designed for dark bg), darken it substantially
-    let (new_r, new_g, new_b) = if lum > 0.5 {
-        // Darken bright colors - multiply by factor to reduce brightness
-        let factor = 0.35; // Make quite dark
-        (
-            (r * factor * 255.0) as u8,
-            (g * factor * 255.0) as u8,
-            (b * factor * 255.0) as u8,
-        )
-    } else {
-        // Already dark, keep as is or slightly adjust
-        (color.r, color.g, color.b)
-    };
-
-    Color::Rgb(new_r, new_g, new_b)
-}
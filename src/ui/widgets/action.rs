@@ -4,13 +4,27 @@
 
 use std::path::PathBuf;
 
+use crate::forge::{MergeMethod, MetadataKind};
+
 /// Type of review action being performed
 #[derive(Debug, Clone, PartialEq)]
 pub enum ReviewAction {
     Approve { pr_number: u64 },
     RequestChanges { pr_number: u64 },
     Comment { pr_number: u64 },
-    LineComment { pr_number: u64, path: String, line: u32 },
+    /// `side` is "LEFT" for a removed line (`line` in the old file), else "RIGHT"
+    LineComment { pr_number: u64, path: String, line: u32, side: &'static str },
+    ReplyToThread { pr_number: u64, comment_id: u64, author: String },
+    ResolveThread { pr_number: u64, thread_id: String, resolve: bool },
+    EditDraft { pr_number: u64, index: usize },
+    DeleteDraft { pr_number: u64, index: usize },
+    EditComment { pr_number: u64, comment_id: u64 },
+    DeleteComment { pr_number: u64, comment_id: u64 },
+    EditReview { pr_number: u64, review_id: u64 },
+    ConversationComment { pr_number: u64 },
+    StashAndCheckout { pr_number: u64 },
+    Merge { pr_number: u64, method: MergeMethod, delete_branch: bool },
+    SetDraft { pr_number: u64, draft: bool },
 }
 
 impl ReviewAction {
@@ -19,19 +33,83 @@ impl ReviewAction {
             Self::Approve { pr_number } => format!("Approve PR #{}", pr_number),
             Self::RequestChanges { pr_number } => format!("Request Changes - PR #{}", pr_number),
             Self::Comment { pr_number } => format!("Comment on PR #{}", pr_number),
-            Self::LineComment { pr_number, path, line } => {
-                format!("Comment on {}:{} - PR #{}", path, line, pr_number)
+            Self::LineComment { pr_number, path, line, side } => {
+                let removed = if *side == "LEFT" { " (removed)" } else { "" };
+                format!("Pending comment on {}:{}{} - PR #{}", path, line, removed, pr_number)
+            }
+            Self::ReplyToThread { pr_number, author, .. } => {
+                format!("Reply to @{} - PR #{}", author, pr_number)
+            }
+            Self::ResolveThread { pr_number, resolve, .. } => {
+                if *resolve {
+                    format!("Resolve thread - PR #{}", pr_number)
+                } else {
+                    format!("Unresolve thread - PR #{}", pr_number)
+                }
             }
+            Self::EditDraft { pr_number, .. } => format!("Edit pending comment - PR #{}", pr_number),
+            Self::DeleteDraft { pr_number, .. } => format!("Delete pending comment - PR #{}", pr_number),
+            Self::EditComment { pr_number, .. } => format!("Edit comment - PR #{}", pr_number),
+            Self::DeleteComment { pr_number, .. } => format!("Delete comment - PR #{}", pr_number),
+            Self::EditReview { pr_number, .. } => format!("Edit review - PR #{}", pr_number),
+            Self::ConversationComment { pr_number } => format!("Reply to conversation - PR #{}", pr_number),
+            Self::StashAndCheckout { pr_number } => format!("Check out PR #{}", pr_number),
+            Self::Merge { pr_number, method, delete_branch } => format!(
+                "Merge PR #{} ({}{})",
+                pr_number,
+                method.label(),
+                if *delete_branch { ", delete branch" } else { "" }
+            ),
+            Self::SetDraft { pr_number, draft: true } => format!("Convert PR #{} to draft", pr_number),
+            Self::SetDraft { pr_number, draft: false } => format!("Ready for review - PR #{}", pr_number),
+        }
+    }
+
+    /// Change an option of a confirmation with a key; false if the key isn't an option
+    pub fn toggle_option(&mut self, key: char) -> bool {
+        match (self, key) {
+            (Self::Merge { method, .. }, 'm') => *method = method.next(),
+            (Self::Merge { delete_branch, .. }, 'd') => *delete_branch = !*delete_branch,
+            _ => return false,
         }
+        true
     }
 
     pub fn needs_body(&self) -> bool {
-        matches!(self, Self::RequestChanges { .. } | Self::Comment { .. } | Self::LineComment { .. })
+        matches!(
+            self,
+            Self::RequestChanges { .. }
+                | Self::Comment { .. }
+                | Self::LineComment { .. }
+                | Self::ReplyToThread { .. }
+                | Self::EditDraft { .. }
+                | Self::EditComment { .. }
+                | Self::EditReview { .. }
+                | Self::ConversationComment { .. }
+        )
+    }
+
+    /// Actions that only touch the local pending review (no GitHub call)
+    pub fn is_local(&self) -> bool {
+        matches!(
+            self,
+            Self::LineComment { .. } | Self::EditDraft { .. } | Self::DeleteDraft { .. }
+        )
     }
 
     pub fn confirmation_message(&self) -> Option<&str> {
         match self {
             Self::Approve { .. } => Some("Are you sure you want to approve this PR?"),
+            Self::ResolveThread { resolve: true, .. } => Some("Mark this thread as resolved?"),
+            Self::ResolveThread { resolve: false, .. } => Some("Reopen this resolved thread?"),
+            Self::DeleteDraft { .. } => Some("Discard this pending comment?"),
+            Self::DeleteComment { .. } => Some("Delete this comment on GitHub?"),
+            Self::StashAndCheckout { .. } => {
+                Some("Stash uncommitted and untracked changes, then check out? (b returns and restores)")
+            }
+            Self::Merge { .. } => Some("Merge this PR? (m: merge method, d: delete branch)"),
+            Self::SetDraft { draft: true, .. } => Some("Convert this PR to a draft?"),
+            Self::SetDraft { draft: false, .. } => Some("Mark this PR as ready for review?"),
             _ => None,
         }
     }
@@ -50,16 +128,32 @@ pub enum Action {
     // File list actions
     /// File was selected (Enter on file)
     FileSelected(PathBuf),
+    /// Mark a file as viewed, or unviewed again
+    ToggleViewed(String),
 
     // PR list actions
     /// PR was selected
     PrSelected(u64),
     /// Checkout PR
     CheckoutPr(u64),
+    /// Review PR head without checking it out (toggles off for the PR being reviewed)
+    ReviewInPlace(u64),
+    /// Open a PR from the current branch
+    CreatePr,
+    /// Pick reviewers, assignees or labels for a PR
+    EditMetadata { pr_number: u64, kind: MetadataKind },
 
     /// Expand an ignored directory (lazy load its contents)
     ExpandIgnoredDir(String),
 
+    // PR details actions
+    /// Fetch a CI job's log into the details pane
+    LoadCheckLog { job_id: u64, name: String },
+    /// Open a check's page in the browser (checks without a fetchable log)
+    OpenUrl(String),
+    /// Show the diff of a file at a line (a reference in a job log)
+    OpenLocation { path: String, line: u32 },
+
     // Review actions
     /// Open review modal
     OpenReviewModal(ReviewAction),
@@ -0,0 +1,206 @@
+//! Activity log: every file change the watcher saw this session
+//!
+//! Lists changes newest first with their age and the lines added and removed
+//! since the file's previous change, so the order in which an agent touched
+//! files can be followed. Typing filters by path; Enter opens the file's diff.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use crate::config::Colors;
+use crate::ui::format::{format_age, unix_now};
+
+use super::{InputResult, ScrollState};
+
+/// One change to a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityEntry {
+    pub path: String,
+    /// Seconds since the epoch
+    pub at: i64,
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// Activity log state; keeps the session's entries while hidden
+#[derive(Debug, Default)]
+pub struct ActivityLogState {
+    pub visible: bool,
+    /// Oldest first
+    entries: Vec<ActivityEntry>,
+    pub query: String,
+    pub scroll: ScrollState,
+}
+
+impl ActivityLogState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a change, stamped with the current time
+    pub fn record(&mut self, path: String, added: usize, removed: usize) {
+        self.entries.push(ActivityEntry { path, at: unix_now(), added, removed });
+        // Keep the cursor on the same entry as the list grows at the top
+        if self.visible && self.matches(self.entries.last()) {
+            self.scroll.set_len(self.filtered().len());
+            if self.scroll.cursor > 0 {
+                self.scroll.move_down();
+            }
+        }
+    }
+
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.query.clear();
+        self.scroll = ScrollState::new();
+        self.scroll.set_len(self.filtered().len());
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    fn matches(&self, entry: Option<&ActivityEntry>) -> bool {
+        entry.is_some_and(|e| e.path.to_lowercase().contains(&self.query.to_lowercase()))
+    }
+
+    /// Entries matching the query (case-insensitive path substring), newest first
+    fn filtered(&self) -> Vec<&ActivityEntry> {
+        self.entries.iter().rev().filter(|e| self.matches(Some(e))).collect()
+    }
+
+    /// Path of the entry under the cursor
+    pub fn selected_path(&self) -> Option<String> {
+        self.filtered().get(self.scroll.cursor).map(|e| e.path.clone())
+    }
+
+    fn set_query(&mut self, change: impl FnOnce(&mut String)) {
+        change(&mut self.query);
+        self.scroll = ScrollState::new();
+        self.scroll.set_len(self.filtered().len());
+    }
+
+    /// Handle key input: ↑/↓ move, typing filters, Enter opens the diff
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputResult {
+        match key.code {
+            KeyCode::Esc => {
+                self.hide();
+                return InputResult::Cancelled;
+            }
+            KeyCode::Enter => return InputResult::Submit,
+            KeyCode::Down => self.scroll.move_down(),
+            KeyCode::Up => self.scroll.move_up(),
+            KeyCode::PageDown => self.scroll.move_down_n(10),
+            KeyCode::PageUp => self.scroll.move_up_n(10),
+            KeyCode::Backspace => self.set_query(|q| {
+                q.pop();
+            }),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.set_query(|q| q.push(c))
+            }
+            _ => {}
+        }
+        InputResult::Continue
+    }
+}
+
+/// Activity log modal widget
+pub struct ActivityLog<'a> {
+    colors: &'a Colors,
+    state: &'a mut ActivityLogState,
+}
+
+impl<'a> ActivityLog<'a> {
+    pub fn new(colors: &'a Colors, state: &'a mut ActivityLogState) -> Self {
+        Self { colors, state }
+    }
+}
+
+impl<'a> Widget for ActivityLog<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.state.visible {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let title = format!("Activity ({} changes)", self.state.entries.len());
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.colors.style_border_focused())
+            .title(Span::styled(title, self.colors.style_header()))
+            .title_alignment(Alignment::Center);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let text_style = Style::reset().fg(self.colors.text);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "↑/↓ move, type to filter, Enter open diff, Esc close",
+                self.colors.style_muted(),
+            )),
+            Line::from(vec![
+                Span::styled("Filter: ", self.colors.style_muted()),
+                Span::styled(self.state.query.clone(), text_style),
+            ]),
+            Line::from(""),
+        ];
+
+        let list_height = (inner.height as usize).saturating_sub(lines.len());
+        self.state.scroll.ensure_visible(list_height);
+
+        let filtered = self.state.filtered();
+        if filtered.is_empty() {
+            let message = if self.state.entries.is_empty() { "No file changes yet" } else { "No matches" };
+            lines.push(Line::from(Span::styled(message, self.colors.style_muted())));
+        }
+        for (idx, entry) in filtered.iter().enumerate().skip(self.state.scroll.offset).take(list_height) {
+            let selected = idx == self.state.scroll.cursor;
+            let style = |style: Style| if selected { self.colors.style_selected() } else { style };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>4} ago  ", format_age(entry.at)), style(self.colors.style_muted())),
+                Span::styled(format!("{:>5}", format!("+{}", entry.added)), style(self.colors.style_added())),
+                Span::styled(format!(" {:<6}", format!("-{}", entry.removed)), style(self.colors.style_removed())),
+                Span::styled(entry.path.clone(), style(text_style)),
+            ]));
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn lists_newest_first_and_filters_by_path() {
+        let mut state = ActivityLogState::new();
+        state.record("src/main.rs".to_string(), 3, 1);
+        state.record("README.md".to_string(), 1, 0);
+        state.record("src/app.rs".to_string(), 0, 2);
+        state.show();
+        assert_eq!(state.selected_path().as_deref(), Some("src/app.rs"));
+
+        for c in "src/".chars() {
+            state.handle_key(key(KeyCode::Char(c)));
+        }
+        state.handle_key(key(KeyCode::Down));
+        assert_eq!(state.selected_path().as_deref(), Some("src/main.rs"));
+
+        // A new change keeps the cursor on the same entry
+        state.record("src/lib.rs".to_string(), 1, 1);
+        assert_eq!(state.selected_path().as_deref(), Some("src/main.rs"));
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), InputResult::Submit);
+    }
+}
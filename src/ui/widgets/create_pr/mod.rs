@@ -0,0 +1,408 @@
+//! Form for opening a PR from the current branch
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
+};
+
+use crate::config::Colors;
+use crate::forge::NewPr;
+
+use super::InputResult;
+
+/// Form fields in display and Tab order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Field {
+    #[default]
+    Title,
+    Base,
+    Reviewers,
+    Draft,
+    Body,
+}
+
+impl Field {
+    const ALL: [Field; 5] = [Field::Title, Field::Base, Field::Reviewers, Field::Draft, Field::Body];
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&f| f == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn prev(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Title => "Title",
+            Self::Base => "Base",
+            Self::Reviewers => "Reviewers",
+            Self::Draft => "Draft",
+            Self::Body => "Body",
+        }
+    }
+}
+
+/// Editable text with a cursor (a char index)
+#[derive(Debug, Default, Clone)]
+pub struct TextField {
+    pub text: String,
+    pub cursor: usize,
+}
+
+impl TextField {
+    fn set(&mut self, text: String) {
+        self.cursor = text.chars().count();
+        self.text = text;
+    }
+
+    fn byte_pos(&self) -> usize {
+        self.text
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.text.len())
+    }
+
+    fn insert(&mut self, c: char) {
+        let pos = self.byte_pos();
+        self.text.insert(pos, c);
+        self.cursor += 1;
+    }
+
+    /// Edit with a key; Ctrl+N inserts a newline in multiline fields
+    fn handle_key(&mut self, key: &KeyEvent, multiline: bool) {
+        match key.code {
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) && multiline => self.insert('\n'),
+            KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {}
+            KeyCode::Char(c) => self.insert(c),
+            KeyCode::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                let pos = self.byte_pos();
+                self.text.remove(pos);
+            }
+            KeyCode::Delete if self.cursor < self.text.chars().count() => {
+                let pos = self.byte_pos();
+                self.text.remove(pos);
+            }
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.text.chars().count()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.text.chars().count(),
+            _ => {}
+        }
+    }
+}
+
+/// Create PR form state
+#[derive(Debug, Default)]
+pub struct CreatePrState {
+    pub visible: bool,
+    /// Branch the PR is opened from
+    pub head: String,
+    /// Base branch choices, the detected base first
+    pub bases: Vec<String>,
+    pub base_index: usize,
+    pub title: TextField,
+    pub body: TextField,
+    /// Comma or space separated usernames
+    pub reviewers: TextField,
+    pub draft: bool,
+    pub focus: Field,
+    pub error: Option<String>,
+    /// Extra lines shown above the form (e.g. that the branch will be pushed)
+    pub context: Vec<String>,
+}
+
+impl CreatePrState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open the form prefilled for `head`
+    pub fn show(&mut self, head: String, bases: Vec<String>, title: String, body: String) {
+        *self = Self {
+            visible: true,
+            head,
+            bases,
+            ..Self::default()
+        };
+        self.title.set(title);
+        self.body.set(body);
+    }
+
+    pub fn hide(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn base(&self) -> &str {
+        self.bases.get(self.base_index).map(String::as_str).unwrap_or_default()
+    }
+
+    /// The PR as filled in
+    pub fn new_pr(&self) -> NewPr {
+        NewPr {
+            head: self.head.clone(),
+            base: self.base().to_string(),
+            title: self.title.text.trim().to_string(),
+            body: self.body.text.clone(),
+            draft: self.draft,
+            reviewers: self
+                .reviewers
+                .text
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .map(|r| r.trim_start_matches('@'))
+                .filter(|r| !r.is_empty())
+                .map(str::to_string)
+                .collect(),
+            head_repo: None,
+        }
+    }
+
+    /// Handle key input: Tab moves between fields, Enter creates, Esc cancels
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputResult {
+        self.error = None;
+
+        match key.code {
+            KeyCode::Esc => {
+                self.hide();
+                return InputResult::Cancelled;
+            }
+            KeyCode::Enter => {
+                if self.title.text.trim().is_empty() {
+                    self.error = Some("Title cannot be empty".to_string());
+                    return InputResult::Continue;
+                }
+                return InputResult::Submit;
+            }
+            KeyCode::Tab if key.modifiers.contains(KeyModifiers::SHIFT) => self.focus = self.focus.prev(),
+            KeyCode::BackTab => self.focus = self.focus.prev(),
+            KeyCode::Tab => self.focus = self.focus.next(),
+            _ => match self.focus {
+                Field::Title => self.title.handle_key(&key, false),
+                Field::Body => self.body.handle_key(&key, true),
+                Field::Reviewers => self.reviewers.handle_key(&key, false),
+                Field::Base => match key.code {
+                    KeyCode::Left | KeyCode::Char('h') if !self.bases.is_empty() => {
+                        self.base_index = (self.base_index + self.bases.len() - 1) % self.bases.len();
+                    }
+                    KeyCode::Right | KeyCode::Char('l' | ' ') if !self.bases.is_empty() => {
+                        self.base_index = (self.base_index + 1) % self.bases.len();
+                    }
+                    _ => {}
+                },
+                Field::Draft => {
+                    if matches!(key.code, KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right) {
+                        self.draft = !self.draft;
+                    }
+                }
+            },
+        }
+        InputResult::Continue
+    }
+}
+
+/// Create PR form widget
+pub struct CreatePrForm<'a> {
+    colors: &'a Colors,
+    state: &'a CreatePrState,
+}
+
+impl<'a> CreatePrForm<'a> {
+    pub fn new(colors: &'a Colors, state: &'a CreatePrState) -> Self {
+        Self { colors, state }
+    }
+
+    fn label(&self, field: Field) -> Span<'static> {
+        let style = if self.state.focus == field {
+            self.colors.style_header()
+        } else {
+            self.colors.style_muted()
+        };
+        Span::styled(format!("{:<10}", field.label()), style)
+    }
+
+    /// Text split into lines, with a cursor block when the field is focused
+    fn text_lines(&self, field: &TextField, focused: bool) -> Vec<Vec<Span<'static>>> {
+        let text_style = Style::reset().fg(self.colors.text);
+        let cursor_style = text_style.add_modifier(Modifier::REVERSED);
+
+        let mut lines = vec![vec![]];
+        let mut chars = field.text.chars().chain(std::iter::once(' ')).enumerate().peekable();
+        while let Some((i, ch)) = chars.next() {
+            let at_end = chars.peek().is_none();
+            if focused && i == field.cursor {
+                let shown = if ch == '\n' { ' ' } else { ch };
+                lines.last_mut().unwrap().push(Span::styled(shown.to_string(), cursor_style));
+            } else if ch != '\n' && !at_end {
+                lines.last_mut().unwrap().push(Span::styled(ch.to_string(), text_style));
+            }
+            if ch == '\n' {
+                lines.push(vec![]);
+            }
+        }
+        lines
+    }
+}
+
+impl<'a> Widget for CreatePrForm<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.state.visible {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.colors.style_border_focused())
+            .title(Span::styled(format!("Create PR from {}", self.state.head), self.colors.style_header()))
+            .title_alignment(Alignment::Center);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let text_style = Style::reset().fg(self.colors.text);
+        let focus = self.state.focus;
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "Tab: next field, ←/→: base, Space: draft, Ctrl+N: newline, Enter: create, Esc: cancel",
+                self.colors.style_muted(),
+            )),
+            Line::from(""),
+        ];
+        for context_line in &self.state.context {
+            lines.push(Line::from(Span::styled(context_line.clone(), self.colors.style_muted())));
+        }
+        if !self.state.context.is_empty() {
+            lines.push(Line::from(""));
+        }
+
+        let mut title = vec![self.label(Field::Title)];
+        title.extend(self.text_lines(&self.state.title, focus == Field::Title).concat());
+        lines.push(Line::from(title));
+
+        lines.push(Line::from(vec![
+            self.label(Field::Base),
+            Span::styled("◂ ", self.colors.style_muted()),
+            Span::styled(self.state.base().to_string(), text_style),
+            Span::styled(" ▸", self.colors.style_muted()),
+        ]));
+
+        let mut reviewers = vec![self.label(Field::Reviewers)];
+        reviewers.extend(self.text_lines(&self.state.reviewers, focus == Field::Reviewers).concat());
+        lines.push(Line::from(reviewers));
+
+        let draft_style = if focus == Field::Draft { text_style.add_modifier(Modifier::REVERSED) } else { text_style };
+        lines.push(Line::from(vec![
+            self.label(Field::Draft),
+            Span::styled(if self.state.draft { "[x]" } else { "[ ]" }, draft_style),
+        ]));
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(self.label(Field::Body)));
+        for spans in self.text_lines(&self.state.body, focus == Field::Body) {
+            lines.push(Line::from(spans));
+        }
+
+        if let Some(error) = &self.state.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(error.clone(), self.colors.style_removed())));
+        }
+
+        Paragraph::new(lines).wrap(Wrap { trim: false }).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn form() -> CreatePrState {
+        let mut state = CreatePrState::new();
+        state.show(
+            "feature".to_string(),
+            vec!["main".to_string(), "release".to_string()],
+            "Add feature".to_string(),
+            "Body".to_string(),
+        );
+        state
+    }
+
+    // --- editing ---
+
+    #[test]
+    fn tab_cycles_fields_and_edits_focused_one() {
+        let mut state = form();
+        assert_eq!(state.focus, Field::Title);
+        state.handle_key(key(KeyCode::Char('!')));
+        assert_eq!(state.title.text, "Add feature!");
+
+        state.handle_key(key(KeyCode::Tab));
+        state.handle_key(key(KeyCode::Right));
+        assert_eq!(state.base(), "release");
+        state.handle_key(key(KeyCode::Right));
+        assert_eq!(state.base(), "main");
+
+        state.handle_key(key(KeyCode::Tab));
+        state.handle_key(key(KeyCode::Tab));
+        state.handle_key(key(KeyCode::Char(' ')));
+        assert!(state.draft);
+
+        state.handle_key(key(KeyCode::BackTab));
+        assert_eq!(state.focus, Field::Reviewers);
+    }
+
+    #[test]
+    fn ctrl_n_adds_newline_in_body_only() {
+        let mut state = form();
+        let ctrl_n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL);
+        state.handle_key(ctrl_n);
+        assert_eq!(state.title.text, "Add feature");
+
+        state.focus = Field::Body;
+        state.handle_key(ctrl_n);
+        state.handle_key(key(KeyCode::Char('x')));
+        assert_eq!(state.body.text, "Body\nx");
+    }
+
+    // --- submitting ---
+
+    #[test]
+    fn enter_requires_title() {
+        let mut state = form();
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), InputResult::Submit);
+
+        state.title.set(" ".to_string());
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), InputResult::Continue);
+        assert!(state.error.is_some());
+        assert_eq!(state.handle_key(key(KeyCode::Esc)), InputResult::Cancelled);
+        assert!(!state.visible);
+    }
+
+    #[test]
+    fn new_pr_splits_reviewers() {
+        let mut state = form();
+        state.reviewers.set("@ann, bob acme/core".to_string());
+        let pr = state.new_pr();
+        assert_eq!(pr.head, "feature");
+        assert_eq!(pr.base, "main");
+        assert_eq!(pr.reviewers, ["ann", "bob", "acme/core"]);
+    }
+}
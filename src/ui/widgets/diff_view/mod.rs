@@ -10,14 +10,17 @@ use ratatui::{
 };
 
 use crate::config::Colors;
+use crate::drafts::DraftComment;
 use crate::event::KeyInput;
-use crate::github::PrInfo;
+use crate::forge::{short_timestamp, Comment, PrInfo, ReviewThread};
+use crate::ui::markdown::{self, MarkdownCache, MdLine};
 use crate::ui::Highlighter;
 
 use parser::{
-    extract_diff_sides, is_binary, parse_diff, parse_file_content,
-    parse_hunk_header, truncate_or_pad, wrap_text, DiffLine, LineType,
+    extract_diff_sides, is_binary, newest_hunk_line, parse_diff, parse_file_content,
+    truncate_or_pad, DiffLine, LineType,
 };
+pub use parser::parse_hunk_header;
 use super::{Action, ReviewAction, ScrollState};
 
 /// What to show in the diff view
@@ -69,6 +72,23 @@ pub struct DiffViewState {
     pub max_indent_level: usize,
     /// Per-file line positions (persists across file switches)
     file_line_positions: std::collections::HashMap<String, usize>,
+    /// Rendered row index -> (root comment id, comment id) for review thread rows
+    thread_rows: std::collections::HashMap<usize, (u64, u64)>,
+    /// Hide resolved review threads
+    pub unresolved_only: bool,
+    /// Root comment id -> line on the displayed version (None = can't be mapped).
+    /// Threads without an entry fall back to GitHub's line numbers.
+    thread_anchors: std::collections::HashMap<u64, Option<u32>>,
+    /// Pending review comments for the selected PR (all files)
+    pending: Vec<DraftComment>,
+    /// Rendered row index -> index into `pending`
+    pending_rows: std::collections::HashMap<usize, usize>,
+    /// Parsed comment bodies for the selected PR
+    markdown: MarkdownCache,
+    /// Rendered row index -> styled markdown for comment body rows
+    markdown_rows: std::collections::HashMap<usize, MdLine>,
+    /// Width comment bodies are wrapped to (follows the pane width)
+    comment_width: usize,
 }
 
 impl Default for DiffViewState {
@@ -86,6 +106,14 @@ impl Default for DiffViewState {
             highlighted_right: std::collections::HashMap::new(),
             max_indent_level: 1, // Default: show 0-1 indent levels
             file_line_positions: std::collections::HashMap::new(),
+            thread_rows: std::collections::HashMap::new(),
+            unresolved_only: false,
+            thread_anchors: std::collections::HashMap::new(),
+            pending: Vec::new(),
+            pending_rows: std::collections::HashMap::new(),
+            markdown: MarkdownCache::default(),
+            markdown_rows: std::collections::HashMap::new(),
+            comment_width: DEFAULT_COMMENT_WIDTH,
         }
     }
 }
@@ -175,6 +203,14 @@ impl DiffViewState {
         self.parse_content();
     }
 
+    /// Set the PR, syntax-highlighting code blocks in its comments
+    pub fn set_pr_highlighted(&mut self, pr: Option<PrInfo>, highlighter: &Highlighter) {
+        if let Some(pr) = &pr {
+            self.markdown.prime(pr.bodies(), highlighter);
+        }
+        self.set_pr(pr);
+    }
+
     pub fn set_pr(&mut self, pr: Option<PrInfo>) {
         self.pr = pr;
         // Re-parse to inject comments
@@ -219,36 +255,75 @@ impl DiffViewState {
         self.scroll.set_len(self.lines.len());
     }
 
-    fn inject_comments(&self, lines: Vec<DiffLine>) -> Vec<DiffLine> {
-        let pr = match &self.pr {
-            Some(pr) => pr,
-            None => return lines,
-        };
+    /// Set where review threads land on the displayed file version.
+    /// Takes effect on the next content or PR update.
+    pub fn set_thread_anchors(&mut self, anchors: std::collections::HashMap<u64, Option<u32>>) {
+        self.thread_anchors = anchors;
+    }
 
-        let comments = match pr.file_comments.get(&self.current_file) {
-            Some(c) => c,
-            None => return lines,
-        };
+    /// Line a RIGHT-side thread should be shown at, None if it's outdated
+    fn thread_target(&self, thread: &ReviewThread) -> Option<u32> {
+        match self.thread_anchors.get(&thread.id()) {
+            Some(anchor) => *anchor,
+            None => thread.root().line,
+        }
+    }
+
+    /// Set the pending review comments to show inline
+    pub fn set_pending_comments(&mut self, pending: Vec<DraftComment>) {
+        self.pending = pending;
+        self.parse_content();
+    }
+
+    /// Index of the pending comment under the cursor
+    pub fn pending_at_cursor(&self) -> Option<usize> {
+        self.pending_rows.get(&self.scroll.cursor).copied()
+    }
+
+    /// Insert review threads and pending comments below the lines they are
+    /// anchored to, recording which rendered rows belong to which thread/draft.
+    fn inject_comments(&mut self, lines: Vec<DiffLine>) -> Vec<DiffLine> {
+        let mut thread_rows = std::collections::HashMap::new();
+        let mut pending_rows = std::collections::HashMap::new();
+        let mut markdown_rows = std::collections::HashMap::new();
+        let body = CommentBody { markdown: &self.markdown, width: self.comment_width };
+
+        let threads: &[ReviewThread] = self
+            .pr
+            .as_ref()
+            .and_then(|pr| pr.file_threads.get(&self.current_file))
+            .map(|t| t.as_slice())
+            .unwrap_or(&[]);
+        let drafts: Vec<(usize, &DraftComment)> = self
+            .pending
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.path == self.current_file)
+            .collect();
 
-        if comments.is_empty() {
+        if threads.is_empty() && drafts.is_empty() {
+            self.thread_rows = thread_rows;
+            self.pending_rows = pending_rows;
+            self.markdown_rows = markdown_rows;
             return lines;
         }
 
-        let mut result = Vec::with_capacity(lines.len() + comments.len() * 2);
-        let wrap_width = 120; // Wrap comments at this width
-        let mut rendered_comments: std::collections::HashSet<usize> = std::collections::HashSet::new();
+        let comment_count: usize = threads.iter().map(|t| t.comments.len()).sum::<usize>() + drafts.len();
+        let mut result = Vec::with_capacity(lines.len() + comment_count * 2);
+        let mut rendered_threads: std::collections::HashSet<usize> = std::collections::HashSet::new();
 
         for line in lines {
             result.push(line.clone());
 
-            // Check if there are comments for this line
-            for (idx, comment) in comments.iter().enumerate() {
-                // Skip already rendered comments
-                if rendered_comments.contains(&idx) {
+            // Check if there are threads anchored to this line
+            for (idx, thread) in threads.iter().enumerate() {
+                // Skip already rendered threads (and resolved ones when filtering)
+                if rendered_threads.contains(&idx) || (self.unresolved_only && thread.resolved) {
                     continue;
                 }
 
-                // Match comment to line based on side
+                // Match thread to line based on the root comment's side
+                let comment = thread.root();
                 let matches = match comment.side.as_deref() {
                     Some("LEFT") => {
                         // Comment on old file - match against left_num or original_line
@@ -256,44 +331,84 @@ impl DiffViewState {
                         line.left_num.map(|n| n as u32) == target
                     }
                     Some("RIGHT") | None => {
-                        // Comment on new file - match against right_num
+                        // Comment on new file - match against the line it maps to
                         // Default to RIGHT if side not specified (most common case)
-                        line.right_num.map(|n| n as u32) == comment.line
+                        let target = self.thread_target(thread);
+                        target.is_some() && line.right_num.map(|n| n as u32) == target
                     }
                     _ => false,
                 };
 
                 if matches {
-                    rendered_comments.insert(idx);
-                    // Add comment header
-                    result.push(DiffLine {
-                        left_text: Some(format!("💬 {}", comment.author)),
-                        right_text: None,
-                        left_num: None,
-                        right_num: None,
-                        line_type: LineType::Comment,
-                        is_header: true,
-                    });
-                    // Add comment body lines with wrapping
-                    for body_line in comment.body.lines() {
-                        for wrapped in wrap_text(body_line, wrap_width) {
-                            result.push(DiffLine {
-                                left_text: Some(format!("   {}", wrapped)),
-                                right_text: None,
-                                left_num: None,
-                                right_num: None,
-                                line_type: LineType::Comment,
-                                is_header: true,
-                            });
-                        }
+                    rendered_threads.insert(idx);
+                    for (comment_id, row) in render_thread(thread, &body) {
+                        thread_rows.insert(result.len(), (thread.id(), comment_id));
+                        row.push_into(&mut result, &mut markdown_rows);
                     }
                 }
             }
+
+            // Pending comments follow any published threads on the same line
+            for (idx, draft) in &drafts {
+                let target = if draft.side == "LEFT" { line.left_num } else { line.right_num };
+                if target.map(|n| n as u32) == Some(draft.line) {
+                    for row in render_pending(draft, &body) {
+                        pending_rows.insert(result.len(), *idx);
+                        row.push_into(&mut result, &mut markdown_rows);
+                    }
+                }
+            }
+        }
+
+        // Threads that no longer map onto this version go in an outdated section
+        let outdated: Vec<&ReviewThread> = threads
+            .iter()
+            .filter(|t| t.root().side.as_deref() != Some("LEFT"))
+            .filter(|t| !(self.unresolved_only && t.resolved))
+            .filter(|t| self.thread_target(t).is_none())
+            .collect();
+        if !outdated.is_empty() && matches!(self.content, PreviewContent::FileDiff { .. }) {
+            result.push(comment_header(format!("Outdated comments ({})", outdated.len()), LineType::Header));
+            for thread in outdated {
+                let root = thread.root();
+                if let Some(line) = root.original_line {
+                    let commit = root.original_commit_id.as_deref().unwrap_or("");
+                    let short = commit.get(..7).unwrap_or(commit);
+                    thread_rows.insert(result.len(), (thread.id(), root.id));
+                    result.push(comment_header(format!("line {} at {}", line, short), LineType::Info));
+                }
+                for (comment_id, row) in render_thread(thread, &body) {
+                    thread_rows.insert(result.len(), (thread.id(), comment_id));
+                    row.push_into(&mut result, &mut markdown_rows);
+                }
+            }
         }
 
+        self.thread_rows = thread_rows;
+        self.pending_rows = pending_rows;
+        self.markdown_rows = markdown_rows;
         result
     }
 
+    /// Review thread under the cursor
+    pub fn thread_at_cursor(&self) -> Option<&ReviewThread> {
+        let (root_id, _) = self.thread_rows.get(&self.scroll.cursor)?;
+        self.pr.as_ref()?.find_thread(*root_id)
+    }
+
+    /// Comment under the cursor, if the current user wrote it
+    fn own_comment_at_cursor(&self) -> Option<&Comment> {
+        let (_, comment_id) = self.thread_rows.get(&self.scroll.cursor)?;
+        let pr = self.pr.as_ref()?;
+        pr.find_comment(*comment_id).filter(|c| pr.is_own(&c.author))
+    }
+
+    /// Toggle hiding of resolved threads
+    pub fn set_unresolved_only(&mut self, unresolved_only: bool) {
+        self.unresolved_only = unresolved_only;
+        self.parse_content();
+    }
+
     pub fn title(&self) -> String {
         match &self.content {
             PreviewContent::Empty => "Preview".to_string(),
@@ -320,6 +435,41 @@ impl DiffViewState {
         })
     }
 
+    /// Line and side a new comment on the cursor line attaches to: the old
+    /// file's line ("LEFT") for a removed line, else the new file's ("RIGHT")
+    pub fn comment_anchor(&self) -> Option<(usize, &'static str)> {
+        let line = self.lines.get(self.scroll.cursor)?;
+        match (line.left_num, line.right_num) {
+            (Some(left), None) if line.line_type == LineType::Removed => Some((left, "LEFT")),
+            _ => self.get_current_line_number().map(|n| (n, "RIGHT")),
+        }
+    }
+
+    /// Move the cursor to a line of the new file (of the file itself in browse
+    /// mode), or the next line shown after it when it's outside the hunks
+    pub fn go_to_line(&mut self, line: usize) {
+        let content_view = self.is_file_content_view();
+        let target = self.lines.iter().position(|l| {
+            let num = if content_view { l.left_num } else { l.right_num };
+            num.is_some_and(|n| n >= line)
+        });
+        if let Some(idx) = target {
+            self.scroll.cursor = idx;
+        }
+    }
+
+    /// Move the cursor to the hunk of the shown diff that `previous` (an
+    /// earlier diff of the same file) didn't have; without one, to the last hunk
+    pub fn go_to_newest_hunk(&mut self, previous: Option<&str>) {
+        let line = match &self.content {
+            PreviewContent::FileDiff { content, .. } => newest_hunk_line(previous.unwrap_or(content), content),
+            _ => None,
+        };
+        if let Some(line) = line {
+            self.go_to_line(line);
+        }
+    }
+
     /// Get the current file path being displayed
     pub fn get_current_file(&self) -> Option<&str> {
         match &self.content {
@@ -401,22 +551,74 @@ impl DiffViewState {
     /// Handle key input, return action for App to dispatch
     /// pr_number is needed for line comments
     pub fn handle_key(&mut self, key: &KeyEvent, pr_number: Option<u64>) -> Action {
-        // Line comment
+        if KeyInput::is_toggle_viewed(key) {
+            return match self.get_current_file() {
+                Some(path) => Action::ToggleViewed(path.to_string()),
+                None => Action::None,
+            };
+        }
+
+        // Edit or discard the pending comment under the cursor
+        if let (Some(pr_num), Some(index)) = (pr_number, self.pending_at_cursor()) {
+            if KeyInput::is_comment(key) {
+                return Action::OpenReviewModal(ReviewAction::EditDraft { pr_number: pr_num, index });
+            }
+            if KeyInput::is_delete(key) {
+                return Action::OpenReviewModal(ReviewAction::DeleteDraft { pr_number: pr_num, index });
+            }
+        }
+
+        // Edit or delete a comment the current user wrote
+        if KeyInput::is_edit(key) || KeyInput::is_delete(key) {
+            if let (Some(pr_num), Some(comment)) = (pr_number, self.own_comment_at_cursor()) {
+                let comment_id = comment.id;
+                return Action::OpenReviewModal(if KeyInput::is_edit(key) {
+                    ReviewAction::EditComment { pr_number: pr_num, comment_id }
+                } else {
+                    ReviewAction::DeleteComment { pr_number: pr_num, comment_id }
+                });
+            }
+            return Action::None;
+        }
+
+        // Reply when the cursor is on a review thread, otherwise line comment
         if KeyInput::is_comment(key) {
-            if let (Some(pr_num), Some(path), Some(line)) = (
+            if let (Some(pr_num), Some(thread)) = (pr_number, self.thread_at_cursor()) {
+                return Action::OpenReviewModal(ReviewAction::ReplyToThread {
+                    pr_number: pr_num,
+                    comment_id: thread.id(),
+                    author: thread.root().author.clone(),
+                });
+            }
+            if let (Some(pr_num), Some(path), Some((line, side))) = (
                 pr_number,
                 self.get_current_file().map(|s| s.to_string()),
-                self.get_current_line_number(),
+                self.comment_anchor(),
             ) {
                 return Action::OpenReviewModal(ReviewAction::LineComment {
                     pr_number: pr_num,
                     path,
                     line: line as u32,
+                    side,
                 });
             }
             return Action::None;
         }
 
+        // Resolve / unresolve the thread under the cursor
+        if KeyInput::is_resolve_thread(key) {
+            if let (Some(pr_num), Some(thread)) = (pr_number, self.thread_at_cursor()) {
+                if let Some(thread_id) = thread.node_id.clone() {
+                    return Action::OpenReviewModal(ReviewAction::ResolveThread {
+                        pr_number: pr_num,
+                        thread_id,
+                        resolve: !thread.resolved,
+                    });
+                }
+            }
+            return Action::None;
+        }
+
         // h/l adjust indent level in file content view (browse mode)
         if self.is_file_content_view() {
             if KeyInput::is_left(key) {
@@ -458,6 +660,113 @@ impl DiffViewState {
     }
 }
 
+/// Wrap comments at this width until the pane has been rendered
+const DEFAULT_COMMENT_WIDTH: usize = 120;
+
+/// How comment bodies are rendered: parsed markdown wrapped to a width
+struct CommentBody<'a> {
+    markdown: &'a MarkdownCache,
+    width: usize,
+}
+
+impl CommentBody<'_> {
+    fn rows(&self, body: &str, indent: &str) -> Vec<CommentRow> {
+        self.markdown
+            .render(body, indent, self.width)
+            .into_iter()
+            .map(|md| CommentRow { line: comment_line(md.plain_text()), md: Some(md) })
+            .collect()
+    }
+}
+
+/// An injected comment row, with styled markdown for body text
+struct CommentRow {
+    line: DiffLine,
+    md: Option<MdLine>,
+}
+
+impl CommentRow {
+    fn plain(line: DiffLine) -> Self {
+        Self { line, md: None }
+    }
+
+    fn push_into(self, lines: &mut Vec<DiffLine>, markdown_rows: &mut std::collections::HashMap<usize, MdLine>) {
+        if let Some(md) = self.md {
+            markdown_rows.insert(lines.len(), md);
+        }
+        lines.push(self.line);
+    }
+}
+
+/// Render a review thread: root comment, then indented replies.
+/// Resolved threads collapse to a single dimmed summary line.
+/// Each row is paired with the id of the comment it belongs to.
+fn render_thread(thread: &ReviewThread, body: &CommentBody) -> Vec<(u64, CommentRow)> {
+    if thread.resolved {
+        let summary = comment_header(resolved_summary(thread), LineType::Info);
+        return vec![(thread.id(), CommentRow::plain(summary))];
+    }
+
+    let mut rows = Vec::new();
+    push_comment_rows(&mut rows, thread.root(), body, "💬 ", "   ");
+    for reply in thread.replies() {
+        push_comment_rows(&mut rows, reply, body, "   ↳ ", "      ");
+    }
+    rows
+}
+
+/// One-line summary for a collapsed resolved thread
+fn resolved_summary(thread: &ReviewThread) -> String {
+    let root = thread.root();
+    let first_line = root.body.lines().next().unwrap_or("");
+    let mut summary = format!("✓ resolved · {}: {}", root.author, truncate_or_pad(first_line, 60).trim_end());
+    match thread.replies().len() {
+        0 => {}
+        1 => summary.push_str(" (+1 reply)"),
+        n => summary.push_str(&format!(" (+{} replies)", n)),
+    }
+    summary
+}
+
+fn push_comment_rows(
+    rows: &mut Vec<(u64, CommentRow)>,
+    comment: &Comment,
+    body: &CommentBody,
+    marker: &str,
+    indent: &str,
+) {
+    let header = if comment.created_at.is_empty() {
+        format!("{}{}", marker, comment.author)
+    } else {
+        format!("{}{} · {}", marker, comment.author, short_timestamp(&comment.created_at))
+    };
+    rows.push((comment.id, CommentRow::plain(comment_line(header))));
+    rows.extend(body.rows(&comment.body, indent).into_iter().map(|row| (comment.id, row)));
+}
+
+/// Render a not-yet-submitted comment from the pending review
+fn render_pending(draft: &DraftComment, body: &CommentBody) -> Vec<CommentRow> {
+    let mut rows = vec![CommentRow::plain(comment_line("✎ pending".to_string()))];
+    rows.extend(body.rows(&draft.body, "   "));
+    rows
+}
+
+/// Full-width header/info line (not tied to a file line)
+fn comment_header(text: String, line_type: LineType) -> DiffLine {
+    DiffLine {
+        left_text: Some(text),
+        right_text: None,
+        left_num: None,
+        right_num: None,
+        line_type,
+        is_header: true,
+    }
+}
+
+fn comment_line(text: String) -> DiffLine {
+    comment_header(text, LineType::Comment)
+}
+
 /// Diff view widget
 pub struct DiffView<'a> {
     colors: &'a Colors,
@@ -523,6 +832,15 @@ impl<'a> StatefulWidget for DiffView<'a> {
             return;
         }
 
+        // Re-wrap comment bodies to the pane (minus the comment bar)
+        let comment_width = (inner.width as usize).saturating_sub(2);
+        if state.comment_width != comment_width {
+            state.comment_width = comment_width;
+            if !state.markdown_rows.is_empty() {
+                state.parse_content();
+            }
+        }
+
         state.scroll.ensure_visible(inner.height as usize);
 
         let visible_lines: Vec<_> = state
@@ -541,7 +859,9 @@ impl<'a> StatefulWidget for DiffView<'a> {
             let y = inner.y + i as u16;
             let is_cursor = self.focused && idx == state.scroll.cursor;
 
-            let line = if diff_line.is_header {
+            let line = if let Some(md) = state.markdown_rows.get(&idx) {
+                render_markdown_line(md, is_cursor, self.colors)
+            } else if diff_line.is_header {
                 render_header_line(diff_line, is_cursor, self.colors)
             } else if is_file_content {
                 // File content view (browse mode) - single column with syntax highlighting
@@ -601,6 +921,15 @@ fn render_header_line(diff_line: &DiffLine, cursor: bool, colors: &Colors) -> Li
     Line::from(Span::styled(display_text, content_style))
 }
 
+/// Comment body row: markdown on the comment background, behind the comment bar
+fn render_markdown_line(md: &MdLine, cursor: bool, colors: &Colors) -> Line<'static> {
+    let style = Style::default().fg(colors.comment).bg(colors.comment_bg);
+    let style = if cursor { style.add_modifier(ratatui::style::Modifier::REVERSED) } else { style };
+    let mut line = markdown::to_line(md, style, colors);
+    line.spans.insert(0, Span::styled("\u{2502} ", style));
+    line
+}
+
 fn render_highlighted_diff_line(
     diff_line: &DiffLine,
     left_hl: Option<&Vec<(String, Style)>>,
@@ -60,19 +60,19 @@ pub fn parse_diff(content: &str) -> Vec<DiffLine> {
                 line_type: LineType::Header,
                 is_header: true,
             });
-        } else if line.starts_with('+') {
+        } else if let Some(text) = line.strip_prefix('+') {
             lines.push(DiffLine {
                 left_text: None,
-                right_text: Some(line[1..].to_string()),
+                right_text: Some(text.to_string()),
                 left_num: None,
                 right_num: Some(right_num),
                 line_type: LineType::Added,
                 is_header: false,
             });
             right_num += 1;
-        } else if line.starts_with('-') {
+        } else if let Some(text) = line.strip_prefix('-') {
             lines.push(DiffLine {
-                left_text: Some(line[1..].to_string()),
+                left_text: Some(text.to_string()),
                 right_text: None,
                 left_num: Some(left_num),
                 right_num: None,
@@ -80,10 +80,10 @@ pub fn parse_diff(content: &str) -> Vec<DiffLine> {
                 is_header: false,
             });
             left_num += 1;
-        } else if line.starts_with(' ') {
+        } else if let Some(text) = line.strip_prefix(' ') {
             lines.push(DiffLine {
-                left_text: Some(line[1..].to_string()),
-                right_text: Some(line[1..].to_string()),
+                left_text: Some(text.to_string()),
+                right_text: Some(text.to_string()),
                 left_num: Some(left_num),
                 right_num: Some(right_num),
                 line_type: LineType::Context,
@@ -123,13 +123,13 @@ pub fn extract_diff_sides(diff: &str) -> (Vec<String>, Vec<String>) {
             continue;
         }
 
-        if line.starts_with('-') {
-            left_lines.push(line[1..].to_string());
-        } else if line.starts_with('+') {
-            right_lines.push(line[1..].to_string());
-        } else if line.starts_with(' ') {
-            left_lines.push(line[1..].to_string());
-            right_lines.push(line[1..].to_string());
+        if let Some(text) = line.strip_prefix('-') {
+            left_lines.push(text.to_string());
+        } else if let Some(text) = line.strip_prefix('+') {
+            right_lines.push(text.to_string());
+        } else if let Some(text) = line.strip_prefix(' ') {
+            left_lines.push(text.to_string());
+            right_lines.push(text.to_string());
         }
     }
 
@@ -164,47 +164,38 @@ pub fn parse_hunk_header(line: &str) -> Option<(usize, usize)> {
     Some((left_start, right_start))
 }
 
-/// Wrap text at word boundaries to fit within max_width
-pub fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
-    if text.is_empty() {
-        return vec![String::new()];
-    }
-
-    let mut lines = Vec::new();
-    let mut current_line = String::new();
-
-    for word in text.split_whitespace() {
-        if current_line.is_empty() {
-            if word.len() > max_width {
-                // Word is longer than max_width, split it
-                let mut remaining = word;
-                while remaining.len() > max_width {
-                    let (chunk, rest) = remaining.split_at(max_width);
-                    lines.push(chunk.to_string());
-                    remaining = rest;
+/// New-file line of the first changed line in the hunk of `after` that
+/// isn't in `before` (an earlier diff of the same file); the last hunk when
+/// every hunk was already there. Hunks are compared without their headers,
+/// so hunks that only moved still count as old.
+pub fn newest_hunk_line(before: &str, after: &str) -> Option<usize> {
+    fn hunks(diff: &str) -> Vec<(usize, Vec<&str>)> {
+        let mut hunks: Vec<(usize, Vec<&str>)> = Vec::new();
+        for line in diff.lines() {
+            if line.starts_with("@@") {
+                if let Some((_, right_start)) = parse_hunk_header(line) {
+                    hunks.push((right_start, Vec::new()));
                 }
-                current_line = remaining.to_string();
-            } else {
-                current_line = word.to_string();
+            } else if let Some((_, body)) = hunks.last_mut() {
+                body.push(line);
             }
-        } else if current_line.len() + 1 + word.len() <= max_width {
-            current_line.push(' ');
-            current_line.push_str(word);
-        } else {
-            lines.push(current_line);
-            current_line = word.to_string();
         }
-    }
-
-    if !current_line.is_empty() {
-        lines.push(current_line);
-    }
-
-    if lines.is_empty() {
-        vec![String::new()]
-    } else {
-        lines
-    }
+        hunks
+    }
+
+    let old = hunks(before);
+    let new = hunks(after);
+    let (start, body) = new
+        .iter()
+        .find(|(_, body)| !old.iter().any(|(_, old_body)| old_body == body))
+        .or(new.last())?;
+
+    // Land on the first added or removed line rather than leading context
+    let first_change = (*start..)
+        .zip(body)
+        .find(|(_, text)| text.starts_with('+') || text.starts_with('-'))
+        .map(|(line, _)| line);
+    Some(first_change.unwrap_or(*start))
 }
 
 /// Truncate a string to fit within a width, or pad it to that width
@@ -327,6 +318,19 @@ mod tests {
         assert_eq!(parse_hunk_header("@@"), None);
     }
 
+    // --- newest_hunk_line ---
+
+    #[test]
+    fn newest_hunk_line_finds_the_new_hunk() {
+        let before = "@@ -1,2 +1,3 @@\n a\n+b\n c\n@@ -20,2 +21,2 @@\n x\n-y\n+z\n";
+        // A hunk appears between the two; the last one only moved down
+        let after = "@@ -1,2 +1,3 @@\n a\n+b\n c\n@@ -10,2 +11,3 @@\n m\n+n\n o\n@@ -20,2 +22,2 @@\n x\n-y\n+z\n";
+        assert_eq!(newest_hunk_line(before, after), Some(12));
+        // Nothing new: the last hunk
+        assert_eq!(newest_hunk_line(after, after), Some(23));
+        assert_eq!(newest_hunk_line("", ""), None);
+    }
+
     // --- parse_diff ---
 
     #[test]
@@ -402,29 +406,6 @@ mod tests {
         assert_eq!(right, vec!["new"]);
     }
 
-    // --- wrap_text ---
-
-    #[test]
-    fn wrap_text_fits_in_width() {
-        assert_eq!(wrap_text("hello world", 20), vec!["hello world"]);
-    }
-
-    #[test]
-    fn wrap_text_wraps_at_boundary() {
-        assert_eq!(wrap_text("hello world", 5), vec!["hello", "world"]);
-    }
-
-    #[test]
-    fn wrap_text_long_word() {
-        let result = wrap_text("abcdefghij", 4);
-        assert_eq!(result, vec!["abcd", "efgh", "ij"]);
-    }
-
-    #[test]
-    fn wrap_text_empty() {
-        assert_eq!(wrap_text("", 10), vec![""]);
-    }
-
     // --- truncate_or_pad ---
 
     #[test]
@@ -29,6 +29,8 @@ pub struct TreeEntry {
     pub children: Vec<String>,
     pub has_comments: bool,
     pub ignored: bool,
+    /// File (or every file below a directory) marked as viewed
+    pub viewed: bool,
 }
 
 /// File list widget state
@@ -39,6 +41,8 @@ pub struct FileListState {
     pub collapsed: HashSet<String>,
     pub files: Vec<StatusEntry>,
     pub has_comments: HashMap<String, bool>,
+    /// Paths of files marked as viewed (with an unchanged diff)
+    viewed: HashSet<String>,
     // Persisted across timeline switches
     selected_path: Option<String>,
     browse_collapsed: HashSet<String>,
@@ -61,12 +65,33 @@ impl FileListState {
         self.rebuild_tree();
     }
 
+    pub fn set_viewed(&mut self, viewed: HashSet<String>) {
+        self.viewed = viewed;
+        self.rebuild_tree();
+    }
+
+    pub fn is_viewed(&self, path: &str) -> bool {
+        self.viewed.contains(path)
+    }
+
+    /// Listed files marked as viewed
+    pub fn viewed_count(&self) -> usize {
+        self.files.iter().filter(|f| self.viewed.contains(&f.path)).count()
+    }
+
     pub fn file_count(&self) -> usize {
         self.files.len()
     }
 
     pub fn rebuild_tree(&mut self) {
         self.entries = build_tree(&self.files, &self.collapsed, &self.has_comments);
+        for entry in &mut self.entries {
+            entry.viewed = if entry.is_dir {
+                !entry.children.is_empty() && entry.children.iter().all(|c| self.viewed.contains(c))
+            } else {
+                self.viewed.contains(&entry.path)
+            };
+        }
         self.scroll.set_len(self.entries.len());
     }
 
@@ -204,6 +229,17 @@ impl FileListState {
         }
     }
 
+    /// Expand the directories containing a file and select it
+    pub fn reveal(&mut self, path: &str) {
+        let mut dir = std::path::Path::new(path).parent();
+        while let Some(d) = dir.filter(|d| !d.as_os_str().is_empty()) {
+            self.collapsed.remove(d.to_string_lossy().as_ref());
+            dir = d.parent();
+        }
+        self.rebuild_tree();
+        self.select_path_or_parent(path);
+    }
+
     /// Restore selection to the saved path (or closest parent)
     pub fn restore_selection(&mut self) {
         if let Some(ref path) = self.selected_path.clone() {
@@ -236,6 +272,11 @@ impl FileListState {
             Action::None
         } else if KeyInput::is_right(key) {
             self.expand()
+        } else if KeyInput::is_toggle_viewed(key) {
+            match self.selected() {
+                Some(entry) if !entry.is_dir => Action::ToggleViewed(entry.path.clone()),
+                _ => Action::None,
+            }
         } else if KeyInput::is_enter(key) {
             // Enter on file -> select it, enter on dir -> expand/collapse
             if let Some(entry) = self.selected() {
@@ -305,6 +346,7 @@ fn build_tree(
         children: all_paths,
         has_comments: false,
         ignored: false,
+        viewed: false,
     });
 
     if collapsed.contains("") {
@@ -431,6 +473,7 @@ fn flatten_tree(
             children,
             has_comments: node_has_comments,
             ignored,
+            viewed: false,
         });
 
         // Recurse into children if not collapsed
@@ -505,6 +548,68 @@ impl<'a> StatefulWidget for FileList<'a> {
     }
 }
 
+fn render_entry(entry: &TreeEntry, selected: bool, colors: &Colors) -> Line<'static> {
+    let mut spans = vec![];
+
+    // Cursor
+    let cursor = if selected { ">" } else { " " };
+    spans.push(Span::raw(cursor.to_string()));
+
+    // Status indicator in fixed left column (1 char: A/M/D/R for files, blank for dirs)
+    if !entry.is_dir && entry.status != FileStatus::Unchanged {
+        let status_style = match entry.status {
+            FileStatus::Modified => colors.style_modified(),
+            FileStatus::Added => colors.style_added(),
+            FileStatus::Deleted => colors.style_removed(),
+            FileStatus::Renamed => Style::reset().fg(colors.renamed),
+            FileStatus::Unchanged => colors.style_muted(),
+        };
+        spans.push(Span::styled(entry.status.to_string(), status_style));
+    } else {
+        spans.push(Span::raw(" ".to_string()));
+    }
+
+    // Indent (1 space per level)
+    let indent = " ".repeat(entry.depth);
+    spans.push(Span::raw(indent));
+
+    // Directory prefix
+    if entry.is_dir {
+        let prefix = if entry.collapsed { "▶ " } else { "▼ " };
+        spans.push(Span::styled(prefix.to_string(), colors.style_muted()));
+    } else {
+        spans.push(Span::raw("  ".to_string()));
+    }
+
+    // Name (directories in header color, files in text color, ignored and viewed dimmed)
+    let name_style = if selected {
+        colors.style_selected()
+    } else if entry.ignored || entry.viewed {
+        colors.style_muted() // Dim ignored and already reviewed files/folders
+    } else if entry.is_dir {
+        colors.style_header() // Directories in distinct color
+    } else {
+        Style::reset().fg(colors.text)
+    };
+    spans.push(Span::styled(entry.display.clone(), name_style));
+
+    // Trailing indicators (after filename — may get clipped on narrow panels)
+    if entry.uncommitted {
+        spans.push(Span::raw(" ".to_string()));
+        spans.push(Span::styled("●".to_string(), colors.style_modified()));
+    }
+    if entry.has_comments {
+        spans.push(Span::raw(" ".to_string()));
+        spans.push(Span::styled("C".to_string(), colors.style_header()));
+    }
+    if entry.viewed && !entry.is_root {
+        spans.push(Span::raw(" ".to_string()));
+        spans.push(Span::styled("✓".to_string(), colors.style_muted()));
+    }
+
+    Line::from(spans)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -647,62 +752,41 @@ mod tests {
         state.expand();
         assert_eq!(state.entries.len(), 4); // expanded again
     }
-}
-
-fn render_entry(entry: &TreeEntry, selected: bool, colors: &Colors) -> Line<'static> {
-    let mut spans = vec![];
-
-    // Cursor
-    let cursor = if selected { ">" } else { " " };
-    spans.push(Span::raw(cursor.to_string()));
-
-    // Status indicator in fixed left column (1 char: A/M/D/R for files, blank for dirs)
-    if !entry.is_dir && entry.status != FileStatus::Unchanged {
-        let status_style = match entry.status {
-            FileStatus::Modified => colors.style_modified(),
-            FileStatus::Added => colors.style_added(),
-            FileStatus::Deleted => colors.style_removed(),
-            FileStatus::Renamed => Style::reset().fg(colors.renamed),
-            FileStatus::Unchanged => colors.style_muted(),
-        };
-        spans.push(Span::styled(entry.status.to_string(), status_style));
-    } else {
-        spans.push(Span::raw(" ".to_string()));
-    }
 
-    // Indent (1 space per level)
-    let indent = " ".repeat(entry.depth);
-    spans.push(Span::raw(indent));
+    #[test]
+    fn viewed_marks_files_and_fully_viewed_dirs() {
+        let mut state = FileListState::new();
+        state.set_files(vec![
+            make_entry("src/a.rs", FileStatus::Modified),
+            make_entry("src/b.rs", FileStatus::Added),
+            make_entry("README.md", FileStatus::Modified),
+        ]);
+        state.set_viewed(["src/a.rs".to_string()].into());
+        let viewed = |state: &FileListState, path: &str| state.entries.iter().any(|e| e.path == path && e.viewed);
+        assert!(viewed(&state, "src/a.rs"));
+        assert!(!viewed(&state, "src"));
+        assert_eq!(state.viewed_count(), 1);
 
-    // Directory prefix
-    if entry.is_dir {
-        let prefix = if entry.collapsed { "▶ " } else { "▼ " };
-        spans.push(Span::styled(prefix.to_string(), colors.style_muted()));
-    } else {
-        spans.push(Span::raw("  ".to_string()));
+        state.set_viewed(["src/a.rs".to_string(), "src/b.rs".to_string()].into());
+        assert!(viewed(&state, "src"));
+        state.scroll.cursor = state.entries.iter().position(|e| e.path == "README.md").unwrap();
+        let key = KeyEvent::new(crossterm::event::KeyCode::Char('v'), crossterm::event::KeyModifiers::NONE);
+        assert_eq!(state.handle_key(&key), Action::ToggleViewed("README.md".to_string()));
     }
 
-    // Name (directories in header color, files in text color, ignored dimmed)
-    let name_style = if selected {
-        colors.style_selected()
-    } else if entry.ignored {
-        colors.style_muted() // Dim ignored files/folders
-    } else if entry.is_dir {
-        colors.style_header() // Directories in distinct color
-    } else {
-        Style::reset().fg(colors.text)
-    };
-    spans.push(Span::styled(entry.display.clone(), name_style));
+    #[test]
+    fn reveal_expands_parents_and_selects_file() {
+        let mut state = FileListState::new();
+        state.set_files(vec![
+            make_entry("README.md", FileStatus::Modified),
+            make_entry("src/ui/view.rs", FileStatus::Modified),
+        ]);
+        state.collapsed.insert("src".to_string());
+        state.collapsed.insert("src/ui".to_string());
+        state.rebuild_tree();
 
-    // Trailing indicators (after filename — may get clipped on narrow panels)
-    if entry.uncommitted {
-        spans.push(Span::raw(" ".to_string()));
-        spans.push(Span::styled("●".to_string(), colors.style_modified()));
-    }
-    if entry.has_comments {
-        spans.push(Span::raw(" ".to_string()));
-        spans.push(Span::styled("C".to_string(), colors.style_header()));
+        state.reveal("src/ui/view.rs");
+        assert_eq!(state.selected().map(|e| e.path.as_str()), Some("src/ui/view.rs"));
+        assert!(state.collapsed.is_empty());
     }
-
-    Line::from(spans)
 }
@@ -63,6 +63,14 @@ impl<'a> Widget for HelpModal<'a> {
                 "  ○ commits  ● wip  [full] all changes  [files] browse",
                 self.colors.style_muted(),
             )),
+            Line::from(Span::styled(
+                "  [rev] since your last review (before [full], once reviewed)",
+                self.colors.style_muted(),
+            )),
+            Line::from(Span::styled(
+                "  · checkpoints of uncommitted edits, one step each (before ●)",
+                self.colors.style_muted(),
+            )),
             Line::from(""),
             Line::from(Span::styled("Navigation", self.colors.style_header())),
             format_binding("j/k", "Move up/down", self.colors),
@@ -71,7 +79,13 @@ impl<'a> Widget for HelpModal<'a> {
             format_binding("h/l", "Collapse/expand folders", self.colors),
             format_binding("Tab", "Cycle panes (Files → Preview → PRs)", self.colors),
             format_binding("Enter", "Open diff / Checkout PR", self.colors),
-            format_binding("Esc", "Back to file list", self.colors),
+            format_binding("i", "Review PR in place (no checkout)", self.colors),
+            format_binding("b", "Back to branch before checkout", self.colors),
+            format_binding("1-4", "PR tabs: all/review/mine/drafts", self.colors),
+            format_binding("/", "Search PRs (author: label: terms)", self.colors),
+            format_binding("S", "Sort PRs: updated/created/size", self.colors),
+            format_binding("Enter", "Check: load log / log: open file:line", self.colors),
+            format_binding("Esc", "Close log / back to file list", self.colors),
             format_binding(",", "Timeline: go left (older)", self.colors),
             format_binding(".", "Timeline: go right (newer)", self.colors),
             Line::from(""),
@@ -82,13 +96,26 @@ impl<'a> Widget for HelpModal<'a> {
             Line::from(Span::styled("Actions", self.colors.style_header())),
             format_binding("o", "Open in $EDITOR (or PR in browser)", self.colors),
             format_binding("y", "Copy path to clipboard", self.colors),
+            format_binding("f", "Follow changes / pause following", self.colors),
+            format_binding("H", "Activity log of file changes", self.colors),
             format_binding("r", "Refresh", self.colors),
             format_binding("q", "Quit", self.colors),
             Line::from(""),
             Line::from(Span::styled("PR Review", self.colors.style_header())),
-            format_binding("a", "Approve", self.colors),
-            format_binding("x", "Request changes", self.colors),
-            format_binding("c", "Comment (PR or line)", self.colors),
+            format_binding("a", "Approve (submits pending comments)", self.colors),
+            format_binding("x", "Request changes (submits pending)", self.colors),
+            format_binding("v", "Mark file viewed (full diff)", self.colors),
+            format_binding("c", "Comment, draft line comment, reply", self.colors),
+            format_binding("e", "Edit own comment or review", self.colors),
+            format_binding("d", "Delete own comment / pending draft", self.colors),
+            format_binding("R", "Resolve/unresolve thread", self.colors),
+            format_binding("M", "Merge PR (m: method, d: delete branch)", self.colors),
+            format_binding("N", "Open PR from current branch", self.colors),
+            format_binding("F", "Range-diff two branch revisions", self.colors),
+            format_binding("E", "Export review to .timecop/review.md", self.colors),
+            format_binding("V/A/L", "Pick reviewers / assignees / labels", self.colors),
+            format_binding("D", "Toggle draft / ready for review", self.colors),
+            format_binding("U", "Show unresolved threads only", self.colors),
             Line::from(""),
             Line::from(Span::styled(
                 "Press ? or Esc to close",
@@ -18,6 +18,12 @@ pub struct InputModalState {
     pub input: String,
     pub cursor_pos: usize,
     pub error: Option<String>,
+    /// Extra lines shown above the input (e.g. pending comments being submitted)
+    pub context: Vec<String>,
+    /// Allow submitting an empty message even if the action normally needs one
+    pub body_optional: bool,
+    /// Why the action can't be confirmed (e.g. unmet merge requirements)
+    pub blocked: Option<String>,
 }
 
 impl InputModalState {
@@ -31,6 +37,16 @@ impl InputModalState {
         self.input.clear();
         self.cursor_pos = 0;
         self.error = None;
+        self.context.clear();
+        self.body_optional = false;
+        self.blocked = None;
+    }
+
+    /// Show the modal with existing text to edit
+    pub fn show_with_input(&mut self, action: ReviewAction, input: String) {
+        self.show(action);
+        self.cursor_pos = input.chars().count();
+        self.input = input;
     }
 
     pub fn hide(&mut self) {
@@ -39,6 +55,9 @@ impl InputModalState {
         self.input.clear();
         self.cursor_pos = 0;
         self.error = None;
+        self.context.clear();
+        self.body_optional = false;
+        self.blocked = None;
     }
 
     pub fn set_error(&mut self, error: String) {
@@ -55,10 +74,14 @@ impl InputModalState {
                 self.hide();
                 InputResult::Cancelled
             }
+            KeyCode::Enter | KeyCode::Char('y' | 'Y') if self.blocked.is_some() => {
+                self.error = self.blocked.clone();
+                InputResult::Continue
+            }
             KeyCode::Enter => {
                 // Enter submits (acts as "y" for confirmations)
                 if let Some(action) = &self.action {
-                    if action.needs_body() && self.input.trim().is_empty() {
+                    if action.needs_body() && !self.body_optional && self.input.trim().is_empty() {
                         self.error = Some("Message cannot be empty".to_string());
                         InputResult::Continue
                     } else {
@@ -72,6 +95,9 @@ impl InputModalState {
                 // For confirmation dialogs (no body needed), handle y/n specially
                 let is_confirmation = self.action.as_ref().map(|a| !a.needs_body()).unwrap_or(false);
                 if is_confirmation {
+                    if self.action.as_mut().is_some_and(|a| a.toggle_option(c)) {
+                        return InputResult::Continue;
+                    }
                     match c {
                         'y' | 'Y' => return InputResult::Submit,
                         'n' | 'N' => {
@@ -202,6 +228,12 @@ impl<'a> Widget for InputModal<'a> {
                 )),
                 Line::from(""),
             ];
+            for context_line in &self.state.context {
+                lines.push(Line::from(Span::styled(context_line.clone(), self.colors.style_muted())));
+            }
+            if !self.state.context.is_empty() {
+                lines.push(Line::from(""));
+            }
 
             // Show input with cursor
             let input = &self.state.input;
@@ -277,15 +309,24 @@ impl<'a> Widget for InputModal<'a> {
                     confirm_msg.to_string(),
                     ratatui::style::Style::reset().fg(self.colors.text),
                 )),
-                Line::from(""),
-                Line::from(vec![
+            ];
+            for context_line in &self.state.context {
+                lines.push(Line::from(Span::styled(context_line.clone(), self.colors.style_muted())));
+            }
+            lines.push(Line::from(""));
+            match &self.state.blocked {
+                Some(reason) => lines.push(Line::from(vec![
+                    Span::styled(reason.clone(), self.colors.style_removed()),
+                    Span::styled(" - Esc to close", self.colors.style_muted()),
+                ])),
+                None => lines.push(Line::from(vec![
                     Span::styled("Press ", self.colors.style_muted()),
                     Span::styled("Enter/y", self.colors.style_added()),
                     Span::styled(" to confirm, ", self.colors.style_muted()),
                     Span::styled("n/Esc", self.colors.style_removed()),
                     Span::styled(" to cancel", self.colors.style_muted()),
-                ]),
-            ];
+                ])),
+            }
 
             // Show error if any
             if let Some(error) = &self.state.error {
@@ -1,17 +1,25 @@
 mod action;
+mod activity_log;
+mod create_pr;
 mod diff_view;
 mod file_list;
 mod help;
 mod input;
+mod picker;
 mod pr_details;
 mod pr_list;
+mod range_diff;
 mod scroll;
 
 pub use action::{Action, ReviewAction};
-pub use diff_view::{DiffView, DiffViewState, PreviewContent};
+pub use activity_log::{ActivityLog, ActivityLogState};
+pub use create_pr::{CreatePrForm, CreatePrState};
+pub use diff_view::{parse_hunk_header, DiffView, DiffViewState, PreviewContent};
 pub use file_list::{FileList, FileListState};
 pub use help::HelpModal;
 pub use input::{InputModal, InputModalState, InputResult};
-pub use pr_details::{PrDetailsView, PrDetailsViewState};
+pub use picker::{Picker, PickerState};
+pub use pr_details::{resolve_path, CheckLog, PrDetailsView, PrDetailsViewState};
 pub use pr_list::{PrListPanel, PrListPanelState};
+pub use range_diff::{RangeDiffModal, RangeDiffState, RevisionChoice};
 pub use scroll::ScrollState;
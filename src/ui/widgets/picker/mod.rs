@@ -0,0 +1,254 @@
+//! Multi-select picker for PR reviewers, assignees and labels
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use std::collections::BTreeSet;
+
+use crate::config::Colors;
+use crate::forge::MetadataKind;
+
+use super::{InputResult, ScrollState};
+
+/// Picker state: options with checkmarks, filtered by typing
+#[derive(Debug)]
+pub struct PickerState {
+    pub visible: bool,
+    pub pr_number: u64,
+    pub kind: MetadataKind,
+    options: Vec<String>,
+    /// Entries on the PR when the picker opened
+    initial: BTreeSet<String>,
+    chosen: BTreeSet<String>,
+    pub query: String,
+    pub scroll: ScrollState,
+    pub error: Option<String>,
+}
+
+impl Default for PickerState {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            pr_number: 0,
+            kind: MetadataKind::Reviewers,
+            options: Vec::new(),
+            initial: BTreeSet::new(),
+            chosen: BTreeSet::new(),
+            query: String::new(),
+            scroll: ScrollState::new(),
+            error: None,
+        }
+    }
+}
+
+impl PickerState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open with the available options; current entries are checked (and
+    /// listed even if they aren't options)
+    pub fn show(&mut self, pr_number: u64, kind: MetadataKind, options: Vec<String>, current: &[String]) {
+        let initial: BTreeSet<String> = current.iter().cloned().collect();
+        let mut options: Vec<String> = options.into_iter().chain(current.iter().cloned()).collect();
+        options.sort_by_key(|o| o.to_lowercase());
+        options.dedup();
+        *self = Self {
+            visible: true,
+            pr_number,
+            kind,
+            options,
+            chosen: initial.clone(),
+            initial,
+            ..Self::default()
+        };
+        self.scroll.set_len(self.filtered().len());
+    }
+
+    pub fn hide(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// Options matching the query (case-insensitive substring)
+    fn filtered(&self) -> Vec<&String> {
+        let query = self.query.to_lowercase();
+        self.options
+            .iter()
+            .filter(|o| o.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    /// Entries to add and to remove
+    pub fn changes(&self) -> (Vec<String>, Vec<String>) {
+        let add = self.chosen.difference(&self.initial).cloned().collect();
+        let remove = self.initial.difference(&self.chosen).cloned().collect();
+        (add, remove)
+    }
+
+    fn set_query(&mut self, change: impl FnOnce(&mut String)) {
+        change(&mut self.query);
+        self.scroll = ScrollState::new();
+        self.scroll.set_len(self.filtered().len());
+    }
+
+    /// Handle key input: ↑/↓ move, Space checks, typing filters, Enter applies
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputResult {
+        self.error = None;
+
+        match key.code {
+            KeyCode::Esc => {
+                self.hide();
+                return InputResult::Cancelled;
+            }
+            KeyCode::Enter => return InputResult::Submit,
+            KeyCode::Down => self.scroll.move_down(),
+            KeyCode::Up => self.scroll.move_up(),
+            KeyCode::Char(' ') => {
+                if let Some(option) = self.filtered().get(self.scroll.cursor).map(|o| o.to_string()) {
+                    if !self.chosen.remove(&option) {
+                        self.chosen.insert(option);
+                    }
+                }
+            }
+            KeyCode::Backspace => self.set_query(|q| {
+                q.pop();
+            }),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.set_query(|q| q.push(c))
+            }
+            _ => {}
+        }
+        InputResult::Continue
+    }
+}
+
+/// Picker widget
+pub struct Picker<'a> {
+    colors: &'a Colors,
+    state: &'a mut PickerState,
+}
+
+impl<'a> Picker<'a> {
+    pub fn new(colors: &'a Colors, state: &'a mut PickerState) -> Self {
+        Self { colors, state }
+    }
+}
+
+impl<'a> Widget for Picker<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.state.visible {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let title = format!("{} - PR #{}", self.state.kind.label(), self.state.pr_number);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.colors.style_border_focused())
+            .title(Span::styled(title, self.colors.style_header()))
+            .title_alignment(Alignment::Center);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let text_style = Style::reset().fg(self.colors.text);
+        let mut lines = vec![
+            Line::from(Span::styled(
+                "↑/↓ move, Space toggle, type to filter, Enter apply, Esc cancel",
+                self.colors.style_muted(),
+            )),
+            Line::from(vec![
+                Span::styled("Filter: ", self.colors.style_muted()),
+                Span::styled(self.state.query.clone(), text_style),
+            ]),
+            Line::from(""),
+        ];
+
+        let footer = if self.state.error.is_some() { 2 } else { 0 };
+        let list_height = (inner.height as usize).saturating_sub(lines.len() + footer);
+        self.state.scroll.ensure_visible(list_height);
+
+        let filtered = self.state.filtered();
+        if filtered.is_empty() {
+            lines.push(Line::from(Span::styled("No matches", self.colors.style_muted())));
+        }
+        for (idx, option) in filtered.iter().enumerate().skip(self.state.scroll.offset).take(list_height) {
+            let checked = self.state.chosen.contains(*option);
+            let style = if idx == self.state.scroll.cursor {
+                self.colors.style_selected()
+            } else if checked {
+                self.colors.style_added()
+            } else {
+                text_style
+            };
+            let mark = if checked { "[x]" } else { "[ ]" };
+            lines.push(Line::from(Span::styled(format!("{} {}", mark, option), style)));
+        }
+
+        if let Some(error) = &self.state.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(error.clone(), self.colors.style_removed())));
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn picker() -> PickerState {
+        let mut state = PickerState::new();
+        let options = vec!["bug".to_string(), "docs".to_string(), "ui".to_string()];
+        state.show(4, MetadataKind::Labels, options, &["ui".to_string(), "wontfix".to_string()]);
+        state
+    }
+
+    #[test]
+    fn show_lists_options_and_current_entries() {
+        let state = picker();
+        assert_eq!(state.filtered(), ["bug", "docs", "ui", "wontfix"]);
+        assert_eq!(state.changes(), (vec![], vec![]));
+    }
+
+    #[test]
+    fn toggling_reports_added_and_removed() {
+        let mut state = picker();
+        state.handle_key(key(KeyCode::Char(' ')));
+        for c in "ui".chars() {
+            state.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(state.filtered(), ["ui"]);
+        state.handle_key(key(KeyCode::Char(' ')));
+
+        assert_eq!(state.changes(), (vec!["bug".to_string()], vec!["ui".to_string()]));
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), InputResult::Submit);
+    }
+
+    #[test]
+    fn filter_resets_cursor() {
+        let mut state = picker();
+        state.handle_key(key(KeyCode::Down));
+        state.handle_key(key(KeyCode::Down));
+        state.handle_key(key(KeyCode::Char('d')));
+        assert_eq!(state.scroll.cursor, 0);
+        assert_eq!(state.filtered(), ["docs"]);
+        state.handle_key(key(KeyCode::Backspace));
+        assert_eq!(state.filtered().len(), 4);
+    }
+}
@@ -0,0 +1,128 @@
+//! CI job log shown in the PR details pane, with file:line references
+
+/// A job log loaded from the forge
+#[derive(Debug, Clone)]
+pub struct CheckLog {
+    /// Name of the check the log belongs to
+    pub name: String,
+    pub lines: Vec<LogLine>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub text: String,
+    /// File and line referenced by the text, e.g. a compiler error location
+    pub location: Option<(String, u32)>,
+}
+
+impl CheckLog {
+    pub fn new(name: String, lines: Vec<String>) -> Self {
+        let lines = lines
+            .into_iter()
+            .map(|text| LogLine { location: file_location(&text), text })
+            .collect();
+        Self { name, lines }
+    }
+
+    /// Placeholder while the log is being fetched
+    pub fn loading(name: String) -> Self {
+        Self::new(name, vec!["Loading log...".to_string()])
+    }
+}
+
+/// First `path:line` reference in a log line: `src/lib.rs:12:5`,
+/// `(/home/runner/work/app/app/test/a.test.js:10:3)` or Python's
+/// `File "app/main.py", line 42`
+pub fn file_location(text: &str) -> Option<(String, u32)> {
+    if let Some(rest) = text.trim_start().strip_prefix("File \"") {
+        let (path, rest) = rest.split_once("\", line ")?;
+        let line = rest.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+        return is_file_path(path).then(|| (path.to_string(), line));
+    }
+
+    text.split(|c: char| c.is_whitespace() || "()[]<>\"'`,".contains(c))
+        .filter(|token| !token.contains("://"))
+        .find_map(|token| {
+            let mut parts = token.split(':');
+            let path = parts.next()?;
+            let line = parts.next()?.parse::<u32>().ok().filter(|&l| l > 0)?;
+            is_file_path(path).then(|| (path.to_string(), line))
+        })
+}
+
+/// Looks like a file name with an extension, optionally in directories
+fn is_file_path(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let has_extension = name
+        .rsplit_once('.')
+        .is_some_and(|(stem, ext)| {
+            !stem.is_empty()
+                && ext.starts_with(|c: char| c.is_ascii_alphabetic())
+                && ext.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+    has_extension
+        && path
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./@+".contains(c))
+}
+
+/// Repository file a log path refers to. CI logs use paths relative to the
+/// checkout or absolute ones on the runner, so the longest file path that the
+/// log path ends with wins.
+pub fn resolve_path<'a>(log_path: &str, files: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let log_path = log_path.strip_prefix("./").unwrap_or(log_path);
+    files
+        .into_iter()
+        .filter(|file| {
+            log_path == *file
+                || log_path
+                    .strip_suffix(*file)
+                    .is_some_and(|prefix| prefix.ends_with('/'))
+        })
+        .max_by_key(|file| file.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // --- file_location ---
+
+    #[test]
+    fn file_location_finds_compiler_and_test_runner_references() {
+        assert_eq!(file_location("  --> src/forge/mod.rs:12:5"), Some(("src/forge/mod.rs".to_string(), 12)));
+        assert_eq!(
+            file_location("    at Object.<anonymous> (/home/runner/work/app/app/test/a.test.js:10:3)"),
+            Some(("/home/runner/work/app/app/test/a.test.js".to_string(), 10))
+        );
+        assert_eq!(file_location("parser_test.go:25: expected 3"), Some(("parser_test.go".to_string(), 25)));
+        assert_eq!(file_location("  File \"app/main.py\", line 42, in run"), Some(("app/main.py".to_string(), 42)));
+    }
+
+    #[test]
+    fn file_location_ignores_times_urls_and_plain_words() {
+        assert_eq!(file_location("Finished at 10:00:00"), None);
+        assert_eq!(file_location("See https://example.com:443/a.html:3"), None);
+        assert_eq!(file_location("error: could not compile `timecop`"), None);
+        assert_eq!(file_location("version 1.2:3"), None);
+    }
+
+    // --- resolve_path ---
+
+    #[test]
+    fn resolve_path_matches_relative_and_runner_paths() {
+        let files = ["src/lib.rs", "lib.rs", "test/a.test.js"];
+        assert_eq!(resolve_path("src/lib.rs", files), Some("src/lib.rs"));
+        assert_eq!(resolve_path("./src/lib.rs", files), Some("src/lib.rs"));
+        assert_eq!(resolve_path("/home/runner/work/app/app/test/a.test.js", files), Some("test/a.test.js"));
+        assert_eq!(resolve_path("other/src/lib.rs", files), Some("src/lib.rs"));
+        assert_eq!(resolve_path("mylib.rs", files), None);
+    }
+
+    #[test]
+    fn check_log_marks_lines_with_locations() {
+        let log = CheckLog::new("test".to_string(), vec!["running".to_string(), "src/a.rs:3:1".to_string()]);
+        assert_eq!(log.lines[0].location, None);
+        assert_eq!(log.lines[1].location, Some(("src/a.rs".to_string(), 3)));
+    }
+}
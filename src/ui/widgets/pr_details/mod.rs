@@ -1,7 +1,10 @@
 //! PR Details view widget
 //!
 //! Displays detailed information about a pull request including
-//! title, description, reviews, comments, and file comments.
+//! title, CI checks, description, reviews, comments, and file comments.
+//! A check's job log can be opened in place of the details.
+
+mod check_log;
 
 use crossterm::event::KeyEvent;
 use ratatui::{
@@ -13,16 +16,35 @@ use ratatui::{
 };
 
 use crate::config::Colors;
+use crate::drafts::DraftComment;
 use crate::event::KeyInput;
-use crate::github::PrInfo;
+use crate::forge::{format_duration, short_timestamp, CheckStatus, ConversationItem, PrInfo, ReviewThread};
+use crate::ui::markdown::{self, MarkdownCache, MdLine};
+use crate::ui::Highlighter;
+
+use super::{Action, ReviewAction, ScrollState};
 
-use super::{Action, ScrollState};
+pub use check_log::{resolve_path, CheckLog};
 
 /// A parsed line ready for display
 #[derive(Debug, Clone)]
 struct DisplayLine {
     text: String,
     line_type: LineType,
+    /// Root comment id of the review thread this line belongs to
+    thread: Option<u64>,
+    /// Review comment this line belongs to
+    comment: Option<u64>,
+    /// Review (summary) this line belongs to
+    review: Option<u64>,
+    /// Index of the pending comment this line belongs to
+    draft: Option<usize>,
+    /// Index into `PrInfo::checks` of the check this line belongs to
+    check: Option<usize>,
+    /// File and line a job log line refers to
+    location: Option<(String, u32)>,
+    /// Styled markdown for body text (`text` holds its plain form)
+    md: Option<MdLine>,
 }
 
 /// Type of line for styling purposes
@@ -34,6 +56,8 @@ enum LineType {
     Added,
     Removed,
     Comment,
+    /// Job log line with a file:line reference
+    Location,
 }
 
 /// PR details view state
@@ -43,6 +67,18 @@ pub struct PrDetailsViewState {
     pub loading_message: Option<String>,
     lines: Vec<DisplayLine>,
     pub scroll: ScrollState,
+    /// Hide resolved review threads
+    pub unresolved_only: bool,
+    /// Pending review comments for this PR
+    pending: Vec<DraftComment>,
+    /// Parsed PR description and comment bodies
+    markdown: MarkdownCache,
+    /// Width bodies are wrapped to (inner pane width)
+    width: usize,
+    /// Job log shown instead of the details
+    log: Option<CheckLog>,
+    /// Details scroll position to restore when the log is closed
+    details_scroll: ScrollState,
 }
 
 impl PrDetailsViewState {
@@ -50,11 +86,54 @@ impl PrDetailsViewState {
         Self::default()
     }
 
+    /// Set the PR, syntax-highlighting code blocks in its markdown bodies
+    pub fn set_pr_highlighted(&mut self, pr: Option<PrInfo>, highlighter: &Highlighter) {
+        if let Some(pr) = &pr {
+            self.markdown.prime(pr.bodies(), highlighter);
+        }
+        self.set_pr(pr);
+    }
+
     pub fn set_pr(&mut self, pr: Option<PrInfo>) {
+        // Refreshing the same PR keeps an open job log
+        let same_pr = matches!((&self.pr, &pr), (Some(a), Some(b)) if a.number == b.number);
         self.pr = pr;
         self.loading_message = None;
+        if !(same_pr && self.log.is_some()) {
+            self.log = None;
+            self.scroll = ScrollState::new();
+        }
+        self.rebuild_lines();
+    }
+
+    /// Show a job log in place of the details. The cursor starts on the
+    /// first file reference, or at the end where failures usually are.
+    pub fn show_log(&mut self, log: CheckLog) {
+        if self.log.is_none() {
+            self.details_scroll = self.scroll.clone();
+        }
+        let first_location = log.lines.iter().position(|l| l.location.is_some());
+        let last = log.lines.len().saturating_sub(1);
+        self.log = Some(log);
         self.scroll = ScrollState::new();
         self.rebuild_lines();
+        self.scroll.cursor = first_location.unwrap_or(last);
+    }
+
+    /// Go back from the job log to the details; false if no log was open
+    pub fn close_log(&mut self) -> bool {
+        if self.log.take().is_none() {
+            return false;
+        }
+        self.rebuild_lines();
+        self.scroll = self.details_scroll.clone();
+        self.scroll.set_len(self.lines.len());
+        true
+    }
+
+    /// Name of the check whose log is shown
+    pub fn log_name(&self) -> Option<&str> {
+        self.log.as_ref().map(|log| log.name.as_str())
     }
 
     pub fn set_loading(&mut self, message: String) {
@@ -67,27 +146,170 @@ impl PrDetailsViewState {
     pub fn clear(&mut self) {
         self.pr = None;
         self.loading_message = None;
+        self.log = None;
         self.lines.clear();
         self.scroll = ScrollState::new();
     }
 
+    /// Toggle hiding of resolved threads
+    pub fn set_unresolved_only(&mut self, unresolved_only: bool) {
+        self.unresolved_only = unresolved_only;
+        self.rebuild_lines();
+    }
+
+    /// Set the pending review comments to list
+    pub fn set_pending_comments(&mut self, pending: Vec<DraftComment>) {
+        self.pending = pending;
+        self.rebuild_lines();
+    }
+
     fn rebuild_lines(&mut self) {
-        self.lines = match &self.pr {
-            Some(pr) => parse_pr_details(pr),
-            None => vec![],
+        self.lines = match (&self.pr, &self.log) {
+            (Some(_), Some(log)) => parse_log(log),
+            (Some(pr), None) => {
+                let mut lines = parse_pr_details(pr, self.unresolved_only, &self.markdown, self.width);
+                lines.extend(parse_pending(&self.pending, &self.markdown, self.width));
+                lines
+            }
+            (None, _) => vec![],
         };
         self.scroll.set_len(self.lines.len());
     }
 
     pub fn title(&self) -> String {
-        match &self.pr {
-            Some(pr) => format!("PR #{} {}", pr.number, pr.title),
-            None => "PR Details".to_string(),
+        match (&self.pr, &self.log) {
+            (Some(pr), Some(log)) => format!("PR #{} log: {} (Esc to close)", pr.number, log.name),
+            (Some(pr), None) => format!("PR #{} {}", pr.number, pr.title),
+            (None, _) => "PR Details".to_string(),
+        }
+    }
+
+    /// Move the cursor to a clicked row; clicking a file reference in a log opens it
+    pub fn click_at(&mut self, visible_row: usize) -> Action {
+        self.scroll.click_at(visible_row);
+        match self.lines.get(self.scroll.cursor).and_then(|l| l.location.clone()) {
+            Some((path, line)) => Action::OpenLocation { path, line },
+            None => Action::None,
+        }
+    }
+
+    /// Enter on a check loads its log (or opens its page); in a log it opens
+    /// the referenced file
+    fn select_action(&self) -> Action {
+        let Some(line) = self.lines.get(self.scroll.cursor) else {
+            return Action::Ignored;
+        };
+        if let Some((path, line)) = line.location.clone() {
+            return Action::OpenLocation { path, line };
         }
+        let check = line.check.and_then(|i| self.pr.as_ref()?.checks.get(i));
+        match check {
+            Some(check) => match check.job_id {
+                Some(job_id) => Action::LoadCheckLog { job_id, name: check.name.clone() },
+                None if !check.url.is_empty() => Action::OpenUrl(check.url.clone()),
+                None => Action::None,
+            },
+            None => Action::Ignored,
+        }
+    }
+
+    /// Review thread under the cursor
+    fn thread_at_cursor(&self) -> Option<&ReviewThread> {
+        let root_id = self.lines.get(self.scroll.cursor)?.thread?;
+        self.pr.as_ref()?.find_thread(root_id)
+    }
+
+    /// Edit/delete action for the current user's comment or review under the cursor.
+    /// Submitted reviews can only be edited, not deleted.
+    fn own_item_action(&self, edit: bool) -> Action {
+        let (Some(pr), Some(line)) = (self.pr.as_ref(), self.lines.get(self.scroll.cursor)) else {
+            return Action::Ignored;
+        };
+
+        if let Some(comment) = line.comment.and_then(|id| pr.find_comment(id)) {
+            if !pr.is_own(&comment.author) {
+                return Action::None;
+            }
+            let comment_id = comment.id;
+            return Action::OpenReviewModal(if edit {
+                ReviewAction::EditComment { pr_number: pr.number, comment_id }
+            } else {
+                ReviewAction::DeleteComment { pr_number: pr.number, comment_id }
+            });
+        }
+
+        if let Some(review) = line.review.and_then(|id| pr.find_review(id)) {
+            if edit && pr.is_own(&review.author) {
+                return Action::OpenReviewModal(ReviewAction::EditReview {
+                    pr_number: pr.number,
+                    review_id: review.id,
+                });
+            }
+            return Action::None;
+        }
+
+        Action::Ignored
     }
 
     /// Handle key input, return action for App to dispatch
     pub fn handle_key(&mut self, key: &KeyEvent) -> Action {
+        if KeyInput::is_select(key) {
+            return self.select_action();
+        }
+        // A job log only scrolls
+        if self.log.is_some() {
+            return self.navigate(key);
+        }
+
+        // Edit or discard the pending comment under the cursor
+        let draft = self.lines.get(self.scroll.cursor).and_then(|l| l.draft);
+        if let (Some(pr), Some(index)) = (self.pr.as_ref(), draft) {
+            if KeyInput::is_comment(key) {
+                return Action::OpenReviewModal(ReviewAction::EditDraft { pr_number: pr.number, index });
+            }
+            if KeyInput::is_delete(key) {
+                return Action::OpenReviewModal(ReviewAction::DeleteDraft { pr_number: pr.number, index });
+            }
+        }
+
+        // Edit or delete comments and reviews the current user wrote
+        if KeyInput::is_edit(key) || KeyInput::is_delete(key) {
+            return self.own_item_action(KeyInput::is_edit(key));
+        }
+
+        // Thread actions: reply and resolve/unresolve
+        if KeyInput::is_comment(key) || KeyInput::is_resolve_thread(key) {
+            let Some(pr) = self.pr.as_ref() else {
+                return Action::Ignored;
+            };
+            let Some(thread) = self.thread_at_cursor() else {
+                // Outside review threads, `c` replies to the PR conversation
+                if KeyInput::is_comment(key) {
+                    return Action::OpenReviewModal(ReviewAction::ConversationComment { pr_number: pr.number });
+                }
+                return Action::None;
+            };
+            if KeyInput::is_comment(key) {
+                return Action::OpenReviewModal(ReviewAction::ReplyToThread {
+                    pr_number: pr.number,
+                    comment_id: thread.id(),
+                    author: thread.root().author.clone(),
+                });
+            }
+            return match thread.node_id.clone() {
+                Some(thread_id) => Action::OpenReviewModal(ReviewAction::ResolveThread {
+                    pr_number: pr.number,
+                    thread_id,
+                    resolve: !thread.resolved,
+                }),
+                None => Action::None,
+            };
+        }
+
+        self.navigate(key)
+    }
+
+    fn navigate(&mut self, key: &KeyEvent) -> Action {
         if KeyInput::is_down(key) {
             self.scroll.move_down();
             Action::None
@@ -166,6 +388,12 @@ impl<'a> StatefulWidget for PrDetailsView<'a> {
             return;
         }
 
+        // Re-wrap bodies when the pane width changes
+        if state.width != inner.width as usize {
+            state.width = inner.width as usize;
+            state.rebuild_lines();
+        }
+
         // Show empty state
         if state.lines.is_empty() {
             let msg = "Select a PR to view details";
@@ -195,12 +423,22 @@ impl<'a> StatefulWidget for PrDetailsView<'a> {
 }
 
 fn render_line(line: &DisplayLine, cursor: bool, colors: &Colors) -> Line<'static> {
+    if let Some(md) = &line.md {
+        let base = Style::reset().fg(colors.text);
+        let base = if cursor { base.add_modifier(ratatui::style::Modifier::REVERSED) } else { base };
+        return markdown::to_line(md, base, colors);
+    }
+
     let (style, prefix) = match line.line_type {
         LineType::Header => (colors.style_header(), ""),
         LineType::Info => (colors.style_muted(), ""),
         LineType::Context => (Style::reset().fg(colors.text), ""),
         LineType::Added => (colors.style_added(), ""),
         LineType::Removed => (colors.style_removed(), ""),
+        LineType::Location => (
+            colors.style_header().add_modifier(ratatui::style::Modifier::UNDERLINED),
+            "",
+        ),
         LineType::Comment => {
             let style = Style::default()
                 .fg(colors.comment)
@@ -221,11 +459,100 @@ fn render_line(line: &DisplayLine, cursor: bool, colors: &Colors) -> Line<'stati
 
 /// Create a display line
 fn make_line(text: String, line_type: LineType) -> DisplayLine {
-    DisplayLine { text, line_type }
+    DisplayLine {
+        text,
+        line_type,
+        thread: None,
+        comment: None,
+        review: None,
+        draft: None,
+        check: None,
+        location: None,
+        md: None,
+    }
+}
+
+/// Create a display line that belongs to a comment in a review thread
+fn make_thread_line(text: String, line_type: LineType, thread: u64, comment: u64) -> DisplayLine {
+    DisplayLine { thread: Some(thread), ..make_comment_line(text, line_type, comment) }
+}
+
+/// Create a display line that belongs to a review comment
+fn make_comment_line(text: String, line_type: LineType, comment: u64) -> DisplayLine {
+    DisplayLine { comment: Some(comment), ..make_line(text, line_type) }
+}
+
+/// Create a display line that belongs to a review summary
+fn make_review_line(text: String, line_type: LineType, review: u64) -> DisplayLine {
+    DisplayLine { review: Some(review), ..make_line(text, line_type) }
+}
+
+/// Create a display line that belongs to a pending comment
+fn make_draft_line(text: String, line_type: LineType, draft: usize) -> DisplayLine {
+    DisplayLine { draft: Some(draft), ..make_line(text, line_type) }
+}
+
+/// Create a display line that belongs to a CI check
+fn make_check_line(text: String, line_type: LineType, check: usize) -> DisplayLine {
+    DisplayLine { check: Some(check), ..make_line(text, line_type) }
+}
+
+/// Job log lines; lines referencing a file can be opened
+fn parse_log(log: &CheckLog) -> Vec<DisplayLine> {
+    log.lines
+        .iter()
+        .map(|line| match &line.location {
+            Some(location) => DisplayLine {
+                location: Some(location.clone()),
+                ..make_line(line.text.clone(), LineType::Location)
+            },
+            None => make_line(line.text.clone(), LineType::Context),
+        })
+        .collect()
+}
+
+/// Append a markdown body wrapped to `width`; rows copy the ids of `template`
+fn push_markdown(
+    lines: &mut Vec<DisplayLine>,
+    markdown: &MarkdownCache,
+    width: usize,
+    body: &str,
+    indent: &str,
+    template: DisplayLine,
+) {
+    for md in markdown.render(body, indent, width) {
+        lines.push(DisplayLine { text: md.plain_text(), md: Some(md), ..template.clone() });
+    }
+}
+
+/// Pending review section: comments drafted locally, not yet submitted
+fn parse_pending(pending: &[DraftComment], markdown: &MarkdownCache, width: usize) -> Vec<DisplayLine> {
+    let mut lines = vec![];
+    if pending.is_empty() {
+        return lines;
+    }
+
+    lines.push(make_line(String::new(), LineType::Context));
+    lines.push(make_line(format!("Pending Review ({})", pending.len()), LineType::Header));
+    lines.push(make_line("─".repeat(40), LineType::Info));
+    lines.push(make_line(
+        "  Submitted with your next approve / request changes / comment".to_string(),
+        LineType::Info,
+    ));
+    for (idx, draft) in pending.iter().enumerate() {
+        lines.push(make_draft_line(
+            format!("    ✎ {}:{}", draft.path, draft.line),
+            LineType::Comment,
+            idx,
+        ));
+        let template = make_draft_line(String::new(), LineType::Context, idx);
+        push_markdown(&mut lines, markdown, width, &draft.body, "      ", template);
+    }
+    lines
 }
 
 /// Parse PR details into display lines
-fn parse_pr_details(pr: &PrInfo) -> Vec<DisplayLine> {
+fn parse_pr_details(pr: &PrInfo, unresolved_only: bool, markdown: &MarkdownCache, width: usize) -> Vec<DisplayLine> {
     let mut lines = vec![];
 
     // PR header
@@ -233,76 +560,155 @@ fn parse_pr_details(pr: &PrInfo) -> Vec<DisplayLine> {
     lines.push(make_line("─".repeat(40), LineType::Info));
     lines.push(make_line(pr.title.clone(), LineType::Info));
     lines.push(make_line(String::new(), LineType::Context));
-    lines.push(make_line(format!("State:  {}", pr.state), LineType::Context));
-    lines.push(make_line(format!("Author: @{}", pr.author), LineType::Context));
-    lines.push(make_line(format!("URL:    {}", pr.url), LineType::Context));
-
-    // Description
-    if !pr.body.is_empty() {
-        lines.push(make_line(String::new(), LineType::Context));
-        lines.push(make_line("Description".to_string(), LineType::Header));
-        lines.push(make_line("─".repeat(40), LineType::Info));
-        for line in pr.body.lines() {
-            lines.push(make_line(format!("  {}", line), LineType::Context));
-        }
+    let state = if pr.draft { format!("{} (draft)", pr.state) } else { pr.state.clone() };
+    lines.push(make_line(format!("State:     {}", state), LineType::Context));
+    lines.push(make_line(format!("Author:    @{}", pr.author), LineType::Context));
+    if !pr.base_branch.is_empty() {
+        lines.push(make_line(format!("Base:      {}", pr.base_branch), LineType::Context));
     }
-
-    // Reviews
-    if !pr.reviews.is_empty() {
+    lines.push(make_line(format!("URL:       {}", pr.url), LineType::Context));
+    let people = |names: &[String]| {
+        if names.is_empty() {
+            "-".to_string()
+        } else {
+            names.iter().map(|n| format!("@{}", n)).collect::<Vec<_>>().join(", ")
+        }
+    };
+    lines.push(make_line(format!("Reviewers: {}", people(&pr.requested_reviewers)), LineType::Context));
+    lines.push(make_line(format!("Assignees: {}", people(&pr.assignees)), LineType::Context));
+    let labels = if pr.labels.is_empty() { "-".to_string() } else { pr.labels.join(", ") };
+    lines.push(make_line(format!("Labels:    {}", labels), LineType::Context));
+
+    // CI checks on the head commit
+    if !pr.checks.is_empty() {
+        let failing = pr.checks.iter().filter(|c| c.status == CheckStatus::Failure).count();
         lines.push(make_line(String::new(), LineType::Context));
-        lines.push(make_line("Reviews".to_string(), LineType::Header));
+        lines.push(make_line(format!("Checks ({} failing)", failing), LineType::Header));
         lines.push(make_line("─".repeat(40), LineType::Info));
-        for review in &pr.reviews {
-            let (icon, line_type) = match review.state.as_str() {
-                "APPROVED" => ("✓", LineType::Added),
-                "CHANGES_REQUESTED" => ("✗", LineType::Removed),
-                _ => ("○", LineType::Context),
+        for (idx, check) in pr.checks.iter().enumerate() {
+            let (icon, status, line_type) = match check.status {
+                CheckStatus::Success => ("✓", "passed", LineType::Added),
+                CheckStatus::Failure => ("✗", "failed", LineType::Removed),
+                CheckStatus::Pending => ("○", "pending", LineType::Context),
             };
-            lines.push(make_line(
-                format!("  {} {} - {}", icon, review.author, review.state),
+            let duration = check.duration().map(|d| format!(" · {}", format_duration(d))).unwrap_or_default();
+            let log_hint = if check.job_id.is_some() { " · ⏎ log" } else { "" };
+            lines.push(make_check_line(
+                format!("  {} {} - {}{}{}", icon, check.name, status, duration, log_hint),
                 line_type,
+                idx,
             ));
-            if !review.body.is_empty() {
-                for line in review.body.lines() {
-                    lines.push(make_line(format!("    {}", line), LineType::Context));
-                }
+            if !check.url.is_empty() {
+                lines.push(make_check_line(format!("    {}", check.url), LineType::Info, idx));
             }
         }
     }
 
-    // General comments
-    if !pr.comments.is_empty() {
+    // Description
+    if !pr.body.is_empty() {
+        lines.push(make_line(String::new(), LineType::Context));
+        lines.push(make_line("Description".to_string(), LineType::Header));
+        lines.push(make_line("─".repeat(40), LineType::Info));
+        let template = make_line(String::new(), LineType::Context);
+        push_markdown(&mut lines, markdown, width, &pr.body, "  ", template);
+    }
+
+    // Conversation: comments and review summaries, oldest first
+    let conversation = pr.conversation();
+    if !conversation.is_empty() {
         lines.push(make_line(String::new(), LineType::Context));
-        lines.push(make_line("Comments".to_string(), LineType::Header));
+        lines.push(make_line("Conversation".to_string(), LineType::Header));
         lines.push(make_line("─".repeat(40), LineType::Info));
-        for comment in &pr.comments {
-            lines.push(make_line(format!("  💬 {}", comment.author), LineType::Comment));
-            for line in comment.body.lines() {
-                lines.push(make_line(format!("    {}", line), LineType::Context));
+        for item in conversation {
+            match item {
+                ConversationItem::Review(review) => {
+                    let (icon, line_type) = match review.state.as_str() {
+                        "APPROVED" => ("✓", LineType::Added),
+                        "CHANGES_REQUESTED" => ("✗", LineType::Removed),
+                        _ => ("○", LineType::Context),
+                    };
+                    lines.push(make_review_line(
+                        format!(
+                            "  {} {} - {} · {}",
+                            icon,
+                            review.author,
+                            review.state,
+                            short_timestamp(&review.submitted_at)
+                        ),
+                        line_type,
+                        review.id,
+                    ));
+                    let template = make_review_line(String::new(), LineType::Context, review.id);
+                    push_markdown(&mut lines, markdown, width, &review.body, "    ", template);
+                }
+                ConversationItem::Comment(comment) => {
+                    lines.push(make_comment_line(
+                        format!("  💬 {} · {}", comment.author, short_timestamp(&comment.created_at)),
+                        LineType::Comment,
+                        comment.id,
+                    ));
+                    let template = make_comment_line(String::new(), LineType::Context, comment.id);
+                    push_markdown(&mut lines, markdown, width, &comment.body, "    ", template);
+                }
             }
             lines.push(make_line(String::new(), LineType::Context));
         }
     }
 
-    // File comments (grouped by file)
-    if !pr.file_comments.is_empty() {
+    // File comments (grouped by file, one entry per thread)
+    if !pr.file_threads.is_empty() {
         lines.push(make_line(String::new(), LineType::Context));
         lines.push(make_line("File Comments".to_string(), LineType::Header));
         lines.push(make_line("─".repeat(40), LineType::Info));
 
-        for (path, comments) in &pr.file_comments {
+        for (path, threads) in &pr.file_threads {
+            let visible: Vec<&ReviewThread> = threads
+                .iter()
+                .filter(|t| !(unresolved_only && t.resolved))
+                .collect();
+            if visible.is_empty() {
+                continue;
+            }
+
             lines.push(make_line(format!("  {}", path), LineType::Info));
-            for comment in comments {
-                let line_info = comment
-                    .line
-                    .map(|l| format!(":{}", l))
-                    .unwrap_or_default();
-                lines.push(make_line(
-                    format!("    💬 @{}{}", comment.author, line_info),
+            for thread in visible {
+                let id = thread.id();
+                let root = thread.root();
+                let line_info = match (root.line, root.original_line) {
+                    (Some(l), _) => format!(":{}", l),
+                    (None, Some(l)) => format!(":{} (outdated)", l),
+                    (None, None) => String::new(),
+                };
+
+                // Resolved threads collapse to a single dimmed line
+                if thread.resolved {
+                    let first_line = root.body.lines().next().unwrap_or("");
+                    lines.push(make_thread_line(
+                        format!("    ✓ @{}{} resolved · {}", root.author, line_info, first_line),
+                        LineType::Info,
+                        id,
+                        root.id,
+                    ));
+                    continue;
+                }
+
+                lines.push(make_thread_line(
+                    format!("    💬 @{}{} · {}", root.author, line_info, short_timestamp(&root.created_at)),
                     LineType::Comment,
+                    id,
+                    root.id,
                 ));
-                for line in comment.body.lines() {
-                    lines.push(make_line(format!("      {}", line), LineType::Context));
+                let template = make_thread_line(String::new(), LineType::Context, id, root.id);
+                push_markdown(&mut lines, markdown, width, &root.body, "      ", template);
+                for reply in thread.replies() {
+                    lines.push(make_thread_line(
+                        format!("      ↳ @{} · {}", reply.author, short_timestamp(&reply.created_at)),
+                        LineType::Comment,
+                        id,
+                        reply.id,
+                    ));
+                    let template = make_thread_line(String::new(), LineType::Context, id, reply.id);
+                    push_markdown(&mut lines, markdown, width, &reply.body, "        ", template);
                 }
             }
             lines.push(make_line(String::new(), LineType::Context));
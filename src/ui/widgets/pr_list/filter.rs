@@ -0,0 +1,178 @@
+//! Tabs, search and sort order for the PR list
+
+use crate::forge::PrSummary;
+
+/// Which PRs the list shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrTab {
+    #[default]
+    All,
+    ReviewRequested,
+    Mine,
+    Drafts,
+}
+
+impl PrTab {
+    pub const ALL: [PrTab; 4] = [PrTab::All, PrTab::ReviewRequested, PrTab::Mine, PrTab::Drafts];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::ReviewRequested => "review",
+            Self::Mine => "mine",
+            Self::Drafts => "drafts",
+        }
+    }
+
+    fn matches(self, pr: &PrSummary) -> bool {
+        match self {
+            Self::All => true,
+            Self::ReviewRequested => pr.review_requested,
+            Self::Mine => pr.authored_by_viewer,
+            Self::Drafts => pr.draft,
+        }
+    }
+}
+
+/// Sort order of the PR list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrSort {
+    /// Most recently updated first (the order PRs are fetched in)
+    #[default]
+    Updated,
+    /// Newest first
+    Created,
+    /// Largest (added + removed lines) first
+    Size,
+}
+
+impl PrSort {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Updated => Self::Created,
+            Self::Created => Self::Size,
+            Self::Size => Self::Updated,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Updated => "updated",
+            Self::Created => "created",
+            Self::Size => "size",
+        }
+    }
+}
+
+/// Active tab, search query and sort order
+#[derive(Debug, Clone, Default)]
+pub struct PrFilter {
+    pub tab: PrTab,
+    pub sort: PrSort,
+    /// Free text matched against title and branch; `author:<login>` and
+    /// `label:<name>` terms filter on those fields
+    pub query: String,
+}
+
+impl PrFilter {
+    /// Indices into `prs` of the PRs to show, in display order
+    pub fn apply(&self, prs: &[PrSummary]) -> Vec<usize> {
+        let mut visible: Vec<usize> = (0..prs.len()).filter(|&i| self.matches(&prs[i])).collect();
+        // Stable sorts keep the fetched order among equals
+        match self.sort {
+            PrSort::Updated => visible.sort_by(|&a, &b| prs[b].updated_at.cmp(&prs[a].updated_at)),
+            PrSort::Created => visible.sort_by(|&a, &b| prs[b].created_at.cmp(&prs[a].created_at)),
+            PrSort::Size => visible.sort_by_key(|&i| std::cmp::Reverse(prs[i].additions + prs[i].deletions)),
+        }
+        visible
+    }
+
+    pub fn matches(&self, pr: &PrSummary) -> bool {
+        if !self.tab.matches(pr) {
+            return false;
+        }
+        self.query.split_whitespace().all(|term| {
+            let term = term.to_lowercase();
+            if let Some(author) = term.strip_prefix("author:") {
+                pr.author.to_lowercase() == author
+            } else if let Some(label) = term.strip_prefix("label:") {
+                pr.labels.iter().any(|l| l.to_lowercase() == label)
+            } else {
+                pr.title.to_lowercase().contains(&term) || pr.branch.to_lowercase().contains(&term)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u64, title: &str, created_at: &str, size: u64) -> PrSummary {
+        PrSummary {
+            number,
+            title: title.to_string(),
+            author: "octo".to_string(),
+            branch: format!("branch-{}", number),
+            base_branch: "main".to_string(),
+            updated_at: String::new(),
+            created_at: created_at.to_string(),
+            review_requested: false,
+            authored_by_viewer: false,
+            draft: false,
+            labels: Vec::new(),
+            additions: size,
+            deletions: 0,
+            checks: Vec::new(),
+            head_sha: String::new(),
+            head_repo: String::new(),
+            review_count: 0,
+            comment_count: 0,
+        }
+    }
+
+    #[test]
+    fn tabs_filter_by_flags() {
+        let mut prs = vec![pr(1, "a", "", 0), pr(2, "b", "", 0), pr(3, "c", "", 0)];
+        prs[0].review_requested = true;
+        prs[1].authored_by_viewer = true;
+        prs[2].draft = true;
+
+        let shown = |tab| PrFilter { tab, ..Default::default() }.apply(&prs);
+        assert_eq!(shown(PrTab::All), vec![0, 1, 2]);
+        assert_eq!(shown(PrTab::ReviewRequested), vec![0]);
+        assert_eq!(shown(PrTab::Mine), vec![1]);
+        assert_eq!(shown(PrTab::Drafts), vec![2]);
+    }
+
+    #[test]
+    fn query_matches_title_branch_author_and_label() {
+        let mut prs = vec![pr(1, "Fix parser crash", "", 0), pr(2, "Add docs", "", 0)];
+        prs[1].author = "Alice".to_string();
+        prs[1].labels = vec!["Documentation".to_string()];
+
+        let shown = |query: &str| PrFilter { query: query.to_string(), ..Default::default() }.apply(&prs);
+        assert_eq!(shown("PARSER"), vec![0]);
+        assert_eq!(shown("branch-2"), vec![1]);
+        assert_eq!(shown("author:alice"), vec![1]);
+        assert_eq!(shown("label:documentation docs"), vec![1]);
+        assert_eq!(shown("label:documentation parser"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn sort_orders() {
+        let mut prs = vec![
+            pr(1, "a", "2024-01-02T00:00:00Z", 5),
+            pr(2, "b", "2024-03-01T00:00:00Z", 50),
+            pr(3, "c", "2024-02-01T00:00:00Z", 5),
+        ];
+        // Same day, ordered by time
+        prs[0].updated_at = "2024-05-01T09:00:00Z".to_string();
+        prs[1].updated_at = "2024-05-01T17:30:00Z".to_string();
+        prs[2].updated_at = "2024-04-30T23:00:00Z".to_string();
+        let sorted = |sort| PrFilter { sort, ..Default::default() }.apply(&prs);
+        assert_eq!(sorted(PrSort::Updated), vec![1, 0, 2]);
+        assert_eq!(sorted(PrSort::Created), vec![1, 2, 0]);
+        assert_eq!(sorted(PrSort::Size), vec![1, 0, 2]);
+    }
+}
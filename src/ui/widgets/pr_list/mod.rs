@@ -1,4 +1,7 @@
-use crossterm::event::KeyEvent;
+mod filter;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::HashSet;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -9,31 +12,47 @@ use ratatui::{
 
 use crate::config::Colors;
 use crate::event::KeyInput;
-use crate::github::{CheckStatus, PrSummary};
+use crate::forge::{CheckStatus, MergeMethod, MetadataKind, PrSummary};
 
 use super::{Action, ReviewAction, ScrollState};
 
+pub use filter::{PrFilter, PrTab};
+
 /// PR list panel widget state
 #[derive(Debug, Default)]
 pub struct PrListPanelState {
+    /// Every fetched PR; `visible` holds the filtered, sorted view into it
     pub prs: Vec<PrSummary>,
+    visible: Vec<usize>,
+    pub filter: PrFilter,
+    /// Typing goes to the search query
+    searching: bool,
+    /// Scroll over `visible`
     pub scroll: ScrollState,
     pub loading: bool,
     pub current_branch: String,
-    pub gh_available: bool,
+    /// Repository the current branch is pushed to ("owner/repo"), telling
+    /// apart same-named branches of other forks
+    pub push_repo: String,
+    /// PRs with activity the user hasn't seen yet
+    pub unread: HashSet<u64>,
+    pub forge_available: bool,
+    /// Shown instead of the list when the forge can't be reached
+    pub setup_hint: [&'static str; 2],
 }
 
 impl PrListPanelState {
     pub fn new() -> Self {
         Self {
             loading: true,
-            gh_available: true, // Assume available until told otherwise
+            forge_available: true, // Assume available until told otherwise
             ..Default::default()
         }
     }
 
-    pub fn set_gh_available(&mut self, available: bool) {
-        self.gh_available = available;
+    pub fn set_forge_available(&mut self, available: bool, setup_hint: [&'static str; 2]) {
+        self.forge_available = available;
+        self.setup_hint = setup_hint;
         if !available {
             self.loading = false;
         }
@@ -42,27 +61,81 @@ impl PrListPanelState {
     pub fn set_prs(&mut self, prs: Vec<PrSummary>) {
         self.prs = prs;
         self.loading = false;
-        self.scroll.set_len(self.prs.len());
+        let selected = self.selected_number();
+        self.apply_filter(selected);
 
         // Try to select the PR for current branch
-        if let Some(idx) = self.prs.iter().position(|pr| pr.branch == self.current_branch) {
-            self.scroll.cursor = idx;
-        }
+        self.select_current_branch();
     }
 
     pub fn set_current_branch(&mut self, branch: String) {
         self.current_branch = branch;
 
         // Auto-select current branch's PR if available
-        if let Some(idx) = self.prs.iter().position(|pr| pr.branch == self.current_branch) {
+        self.select_current_branch();
+    }
+
+    /// Open PR whose head is the current branch
+    pub fn current_branch_pr(&self) -> Option<&PrSummary> {
+        self.prs.iter().find(|pr| self.is_current_branch(pr))
+    }
+
+    /// Whether a PR's head is the current branch, in the repository it's pushed to
+    /// (when both are known)
+    fn is_current_branch(&self, pr: &PrSummary) -> bool {
+        pr.branch == self.current_branch
+            && (pr.head_repo.is_empty() || self.push_repo.is_empty() || pr.head_repo.eq_ignore_ascii_case(&self.push_repo))
+    }
+
+    fn select_current_branch(&mut self) {
+        if let Some(idx) = self.visible.iter().position(|&i| self.is_current_branch(&self.prs[i])) {
             self.scroll.cursor = idx;
         }
     }
 
-    /// Click at a visible row (relative to inner area). Returns true if selection changed.
+    /// Recompute the visible PRs, keeping `keep` selected if it's still shown
+    fn apply_filter(&mut self, keep: Option<u64>) {
+        self.visible = self.filter.apply(&self.prs);
+        self.scroll.set_len(self.visible.len());
+        self.scroll.cursor = keep
+            .and_then(|n| self.visible.iter().position(|&i| self.prs[i].number == n))
+            .unwrap_or(0);
+    }
+
+    /// Change the filter and report the (possibly new) selection
+    fn update_filter(&mut self, change: impl FnOnce(&mut PrFilter)) -> Action {
+        let selected = self.selected_number();
+        change(&mut self.filter);
+        self.apply_filter(selected);
+        match self.selected_number() {
+            Some(n) if Some(n) != selected => Action::PrSelected(n),
+            _ => Action::None,
+        }
+    }
+
+    /// Whether keys are being typed into the search query
+    pub fn is_searching(&self) -> bool {
+        self.searching
+    }
+
+    /// Rows the panel needs: the tab row plus one per shown PR (or the
+    /// "no matching PRs" line), none when there are no PRs at all
+    pub fn display_rows(&self) -> usize {
+        if self.prs.is_empty() {
+            0
+        } else {
+            self.visible.len().max(1) + 1
+        }
+    }
+
+    /// Click at a visible row (relative to inner area, below the tab row).
+    /// Returns true if selection changed.
     pub fn click_at(&mut self, visible_row: usize) -> bool {
-        let target = self.scroll.offset + visible_row;
-        if target < self.prs.len() && target != self.scroll.cursor {
+        let Some(row) = visible_row.checked_sub(1) else {
+            return false;
+        };
+        let target = self.scroll.offset + row;
+        if target < self.visible.len() && target != self.scroll.cursor {
             self.scroll.cursor = target;
             return true;
         }
@@ -70,7 +143,7 @@ impl PrListPanelState {
     }
 
     pub fn selected(&self) -> Option<&PrSummary> {
-        self.prs.get(self.scroll.cursor)
+        self.visible.get(self.scroll.cursor).map(|&i| &self.prs[i])
     }
 
     pub fn selected_number(&self) -> Option<u64> {
@@ -79,11 +152,28 @@ impl PrListPanelState {
 
     /// Handle key input, return action for App to dispatch
     pub fn handle_key(&mut self, key: &KeyEvent) -> Action {
-        // All PR actions require gh CLI
-        if !self.gh_available {
+        // All PR actions require forge access
+        if !self.forge_available {
             return Action::Ignored;
         }
 
+        if self.searching {
+            return self.handle_search_key(key);
+        }
+
+        if KeyInput::is_search(key) {
+            self.searching = true;
+            return Action::None;
+        }
+
+        if let Some(tab) = KeyInput::digit(key).and_then(|d| PrTab::ALL.get(d - 1).copied()) {
+            return self.update_filter(|f| f.tab = tab);
+        }
+
+        if KeyInput::is_cycle_sort(key) {
+            return self.update_filter(|f| f.sort = f.sort.next());
+        }
+
         // Review actions
         if KeyInput::is_approve(key) {
             if let Some(pr) = self.selected() {
@@ -112,6 +202,48 @@ impl PrListPanelState {
             return Action::None;
         }
 
+        if KeyInput::is_merge(key) {
+            if let Some(pr) = self.selected() {
+                return Action::OpenReviewModal(ReviewAction::Merge {
+                    pr_number: pr.number,
+                    method: MergeMethod::default(),
+                    delete_branch: false,
+                });
+            }
+            return Action::None;
+        }
+
+        // Metadata: pickers for people and labels, draft toggle
+        let metadata = if KeyInput::is_edit_reviewers(key) {
+            Some(MetadataKind::Reviewers)
+        } else if KeyInput::is_edit_assignees(key) {
+            Some(MetadataKind::Assignees)
+        } else if KeyInput::is_edit_labels(key) {
+            Some(MetadataKind::Labels)
+        } else {
+            None
+        };
+        if let Some(kind) = metadata {
+            return match self.selected() {
+                Some(pr) => Action::EditMetadata { pr_number: pr.number, kind },
+                None => Action::None,
+            };
+        }
+
+        if KeyInput::is_toggle_draft(key) {
+            if let Some(pr) = self.selected() {
+                return Action::OpenReviewModal(ReviewAction::SetDraft {
+                    pr_number: pr.number,
+                    draft: !pr.draft,
+                });
+            }
+            return Action::None;
+        }
+
+        if KeyInput::is_create_pr(key) {
+            return Action::CreatePr;
+        }
+
         if KeyInput::is_down(key) {
             self.scroll.move_down();
             if let Some(pr) = self.selected() {
@@ -135,10 +267,36 @@ impl PrListPanelState {
             return Action::None;
         }
 
+        if KeyInput::is_review_in_place(key) {
+            if let Some(pr) = self.selected() {
+                return Action::ReviewInPlace(pr.number);
+            }
+            return Action::None;
+        }
+
         // Note: 'o' (open in browser) is handled by App directly
 
         Action::Ignored
     }
+
+    /// Edit the search query: Enter keeps it, Esc clears it
+    fn handle_search_key(&mut self, key: &KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter => {
+                self.searching = false;
+                Action::None
+            }
+            KeyCode::Esc => {
+                self.searching = false;
+                self.update_filter(|f| f.query.clear())
+            }
+            KeyCode::Backspace => self.update_filter(|f| {
+                f.query.pop();
+            }),
+            KeyCode::Char(c) => self.update_filter(|f| f.query.push(c)),
+            _ => Action::None,
+        }
+    }
 }
 
 /// PR list panel widget
@@ -177,12 +335,17 @@ impl<'a> StatefulWidget for PrListPanel<'a> {
         const SPINNER: [char; 4] = ['◐', '◓', '◑', '◒'];
         let spinner = SPINNER[self.spinner_frame % SPINNER.len()];
 
+        let count = if state.visible.len() == state.prs.len() {
+            state.prs.len().to_string()
+        } else {
+            format!("{}/{}", state.visible.len(), state.prs.len())
+        };
         let title = if state.loading && state.prs.is_empty() {
             format!("PRs {}", spinner)
         } else if state.loading {
-            format!("PRs ({}) {}", state.prs.len(), spinner)
+            format!("PRs ({}) {}", count, spinner)
         } else {
-            format!("PRs ({})", state.prs.len())
+            format!("PRs ({})", count)
         };
 
         let block = Block::default()
@@ -193,10 +356,11 @@ impl<'a> StatefulWidget for PrListPanel<'a> {
         let inner = block.inner(area);
         block.render(area, buf);
 
-        if !state.gh_available {
-            let line = Line::from(Span::styled("gh CLI not found", self.colors.style_muted()));
+        if !state.forge_available {
+            let [problem, hint] = state.setup_hint;
+            let line = Line::from(Span::styled(problem, self.colors.style_muted()));
             buf.set_line(inner.x, inner.y, &line, inner.width);
-            let hint = Line::from(Span::styled("cli.github.com", self.colors.style_muted()));
+            let hint = Line::from(Span::styled(hint, self.colors.style_muted()));
             if inner.height > 1 {
                 buf.set_line(inner.x, inner.y + 1, &hint, inner.width);
             }
@@ -209,31 +373,70 @@ impl<'a> StatefulWidget for PrListPanel<'a> {
             return;
         }
 
-        let visible_count = inner.height as usize;
+        if inner.height == 0 {
+            return;
+        }
+        let header = render_filter_line(&state.filter, state.searching, self.colors);
+        buf.set_line(inner.x, inner.y, &header, inner.width);
+
+        if state.visible.is_empty() && !state.loading {
+            let line = Line::from(Span::styled("No matching PRs", self.colors.style_muted()));
+            if inner.height > 1 {
+                buf.set_line(inner.x, inner.y + 1, &line, inner.width);
+            }
+            return;
+        }
+
+        let visible_count = inner.height.saturating_sub(1) as usize;
         state.scroll.ensure_visible(visible_count);
 
         for (i, pr) in state
-            .prs
+            .visible
             .iter()
+            .map(|&idx| &state.prs[idx])
             .skip(state.scroll.offset)
             .take(visible_count)
             .enumerate()
         {
-            let y = inner.y + i as u16;
+            let y = inner.y + 1 + i as u16;
             let idx = state.scroll.offset + i;
             let is_selected = self.focused && idx == state.scroll.cursor;
-            let is_current_branch = pr.branch == state.current_branch;
+            let is_current_branch = state.is_current_branch(pr);
 
-            let line = render_pr_line(pr, is_selected, is_current_branch, self.colors, inner.width as usize);
+            let unread = state.unread.contains(&pr.number);
+
+            let line = render_pr_line(pr, is_selected, is_current_branch, unread, self.colors, inner.width as usize);
             buf.set_line(inner.x, y, &line, inner.width);
         }
     }
 }
 
+/// Tabs (numbered for their keys), sort order and search query
+fn render_filter_line(filter: &PrFilter, searching: bool, colors: &Colors) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (i, tab) in PrTab::ALL.iter().enumerate() {
+        let style = if *tab == filter.tab {
+            colors.style_header().add_modifier(Modifier::REVERSED)
+        } else {
+            colors.style_muted()
+        };
+        spans.push(Span::styled(format!("{} {}", i + 1, tab.label()), style));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::styled(format!("↓{} ", filter.sort.label()), colors.style_muted()));
+
+    if searching || !filter.query.is_empty() {
+        let cursor = if searching { "▏" } else { "" };
+        spans.push(Span::styled(format!("/{}{}", filter.query, cursor), colors.style_header()));
+    }
+    Line::from(spans)
+}
+
 fn render_pr_line(
     pr: &PrSummary,
     selected: bool,
     is_current_branch: bool,
+    unread: bool,
     colors: &Colors,
     width: usize,
 ) -> Line<'static> {
@@ -255,7 +458,11 @@ fn render_pr_line(
     } else {
         colors.style_muted()
     };
-    spans.push(Span::styled(format!("{} ", review_indicator), review_style));
+    spans.push(Span::styled(review_indicator.to_string(), review_style));
+
+    // New activity indicator
+    let unread_indicator = if unread { "•" } else { " " };
+    spans.push(Span::styled(format!("{} ", unread_indicator), colors.style_header()));
 
     // PR number
     let pr_num = format!("#{:<5}", pr.number);
@@ -289,7 +496,8 @@ fn render_pr_line(
     spans.push(Span::styled("│ ", sep_style));
 
     // Days ago (fixed width, right side)
-    let days_ago = format!("{:>7}", days_ago_from_date(&pr.updated_at));
+    let updated = pr.updated_at.split('T').next().unwrap_or("");
+    let days_ago = format!("{:>7}", days_ago_from_date(updated));
 
     // Check dots width (1 char per check)
     let checks_width = pr.checks.len();
@@ -308,6 +516,7 @@ fn render_pr_line(
     } else {
         ratatui::style::Style::reset().fg(colors.text)
     };
+    let title_style = if unread { title_style.add_modifier(Modifier::BOLD) } else { title_style };
     spans.push(Span::styled(title_padded, title_style));
 
     // Check status dots (individual, right-aligned before date)
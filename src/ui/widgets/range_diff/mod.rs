@@ -0,0 +1,339 @@
+//! Range-diff between two revisions of a PR branch
+//!
+//! First lists the branch's revisions (reflog, forge force-pushes) to pick
+//! two from; then shows the range-diff, where changed commits expand into
+//! the interdiff of their patches.
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+use std::collections::HashSet;
+
+use crate::config::Colors;
+use crate::event::KeyInput;
+use crate::git::{RangeCommit, RangeEntry};
+
+use super::{InputResult, ScrollState};
+
+/// A revision of the branch to compare
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevisionChoice {
+    pub sha: String,
+    /// Where it comes from, e.g. "feature@{2}: rebase (finish)" or "force-pushed 2024-01-02"
+    pub label: String,
+    /// Commit subject, empty when the commit isn't local yet
+    pub summary: String,
+}
+
+/// Range-diff modal state
+#[derive(Debug, Default)]
+pub struct RangeDiffState {
+    pub visible: bool,
+    pub title: String,
+    /// Newest first
+    revisions: Vec<RevisionChoice>,
+    /// Revision picked first
+    first: Option<usize>,
+    /// Older and newer revision once both are picked
+    picked: Option<(usize, usize)>,
+    entries: Vec<RangeEntry>,
+    expanded: HashSet<usize>,
+    pub scroll: ScrollState,
+    pub error: Option<String>,
+}
+
+impl RangeDiffState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open with the revisions to pick from, newest first
+    pub fn show(&mut self, title: String, revisions: Vec<RevisionChoice>) {
+        *self = Self { visible: true, title, revisions, ..Self::default() };
+        self.scroll.set_len(self.revisions.len());
+    }
+
+    pub fn hide(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Show an error and go back to picking revisions
+    pub fn set_error(&mut self, error: String) {
+        let cursor = self.picked.take().map_or(self.scroll.cursor, |(_, new)| new);
+        self.first = None;
+        self.scroll = ScrollState::new();
+        self.scroll.set_len(self.revisions.len());
+        self.scroll.cursor = cursor;
+        self.error = Some(error);
+    }
+
+    /// Commit ids of the picked revisions, older first
+    pub fn picked_shas(&self) -> Option<(String, String)> {
+        let (old, new) = self.picked?;
+        Some((self.revisions[old].sha.clone(), self.revisions[new].sha.clone()))
+    }
+
+    /// Show the range-diff of the picked revisions; changed commits start expanded
+    pub fn show_entries(&mut self, entries: Vec<RangeEntry>) {
+        self.expanded = entries.iter().enumerate().filter(|(_, e)| e.marker() == '!').map(|(i, _)| i).collect();
+        self.entries = entries;
+        self.scroll = ScrollState::new();
+        self.scroll.set_len(self.display_lines().len());
+    }
+
+    fn showing_entries(&self) -> bool {
+        self.picked.is_some()
+    }
+
+    /// Rows of the range-diff: each entry, followed by its interdiff lines
+    /// when expanded, as (entry index, interdiff line)
+    fn display_lines(&self) -> Vec<(usize, Option<&str>)> {
+        let mut lines = Vec::new();
+        for (i, entry) in self.entries.iter().enumerate() {
+            lines.push((i, None));
+            if self.expanded.contains(&i) {
+                lines.extend(entry.interdiff.lines().map(|line| (i, Some(line))));
+            }
+        }
+        lines
+    }
+
+    /// Handle key input: pick two revisions (Space/Enter), then browse the
+    /// range-diff, expanding changed commits with Enter; Esc steps back
+    pub fn handle_key(&mut self, key: KeyEvent) -> InputResult {
+        self.error = None;
+
+        if KeyInput::is_escape(&key) {
+            if self.showing_entries() {
+                let revisions = std::mem::take(&mut self.revisions);
+                let title = std::mem::take(&mut self.title);
+                self.show(title, revisions);
+            } else if self.first.take().is_none() {
+                self.hide();
+                return InputResult::Cancelled;
+            }
+            return InputResult::Continue;
+        }
+        if KeyInput::is_down(&key) {
+            self.scroll.move_down();
+        } else if KeyInput::is_up(&key) {
+            self.scroll.move_up();
+        } else if KeyInput::is_top(&key) {
+            self.scroll.go_top();
+        } else if KeyInput::is_bottom(&key) {
+            self.scroll.go_bottom();
+        } else if KeyInput::is_select(&key) {
+            if self.showing_entries() {
+                self.toggle_expanded();
+            } else {
+                return self.pick(self.scroll.cursor);
+            }
+        }
+        InputResult::Continue
+    }
+
+    fn pick(&mut self, index: usize) -> InputResult {
+        if index >= self.revisions.len() {
+            return InputResult::Continue;
+        }
+        match self.first {
+            None => {
+                self.first = Some(index);
+                InputResult::Continue
+            }
+            Some(first) if first == index => {
+                self.first = None;
+                InputResult::Continue
+            }
+            // Listed newest first: the lower one is older
+            Some(first) => {
+                self.picked = Some((first.max(index), first.min(index)));
+                InputResult::Submit
+            }
+        }
+    }
+
+    fn toggle_expanded(&mut self) {
+        let Some(&(entry, _)) = self.display_lines().get(self.scroll.cursor) else {
+            return;
+        };
+        if self.entries[entry].interdiff.is_empty() {
+            return;
+        }
+        if !self.expanded.remove(&entry) {
+            self.expanded.insert(entry);
+        }
+        // Keep the cursor on the entry's own row
+        self.scroll.cursor = self.display_lines().iter().position(|(i, _)| *i == entry).unwrap_or(0);
+        self.scroll.set_len(self.display_lines().len());
+    }
+}
+
+/// Range-diff modal widget
+pub struct RangeDiffModal<'a> {
+    colors: &'a Colors,
+    state: &'a mut RangeDiffState,
+}
+
+impl<'a> RangeDiffModal<'a> {
+    pub fn new(colors: &'a Colors, state: &'a mut RangeDiffState) -> Self {
+        Self { colors, state }
+    }
+
+    fn revision_lines(&self, height: usize) -> Vec<Line<'static>> {
+        let text_style = Style::reset().fg(self.colors.text);
+        let mut lines = Vec::new();
+        if self.state.revisions.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "No earlier revisions in the reflog or on the forge",
+                self.colors.style_muted(),
+            )));
+        }
+        for (idx, revision) in self.state.revisions.iter().enumerate().skip(self.state.scroll.offset).take(height) {
+            let first = self.state.first == Some(idx);
+            let style = if idx == self.state.scroll.cursor {
+                self.colors.style_selected()
+            } else if first {
+                self.colors.style_added()
+            } else {
+                text_style
+            };
+            let mark = if first { "[x]" } else { "[ ]" };
+            let short = &revision.sha[..revision.sha.len().min(7)];
+            let summary = if revision.summary.is_empty() { "(not fetched)" } else { revision.summary.as_str() };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} {} {}  ", mark, short, summary), style),
+                Span::styled(revision.label.clone(), self.colors.style_muted()),
+            ]));
+        }
+        lines
+    }
+
+    fn entry_lines(&self, height: usize) -> Vec<Line<'static>> {
+        let text_style = Style::reset().fg(self.colors.text);
+        let mut lines = Vec::new();
+        if self.state.entries.is_empty() {
+            lines.push(Line::from(Span::styled("No commits in either revision", self.colors.style_muted())));
+        }
+        let rows = self.state.display_lines();
+        for (row, (entry, interdiff)) in rows.iter().enumerate().skip(self.state.scroll.offset).take(height) {
+            let selected = row == self.state.scroll.cursor;
+            let line = match interdiff {
+                Some(text) => {
+                    let style = if text.starts_with('+') {
+                        self.colors.style_added()
+                    } else if text.starts_with('-') {
+                        self.colors.style_removed()
+                    } else if text.starts_with("@@") {
+                        self.colors.style_muted()
+                    } else {
+                        text_style
+                    };
+                    Line::from(Span::styled(format!("      {}", text), style))
+                }
+                None => {
+                    let entry = &self.state.entries[*entry];
+                    let side = |commit: &Option<RangeCommit>| match commit {
+                        Some(c) => format!("{:>2}: {}", c.index, c.short_id()),
+                        None => " -: -------".to_string(),
+                    };
+                    let style = match entry.marker() {
+                        '=' => self.colors.style_muted(),
+                        '<' => self.colors.style_removed(),
+                        '>' => self.colors.style_added(),
+                        _ => text_style,
+                    };
+                    let text = format!("{} {} {} {}", side(&entry.old), entry.marker(), side(&entry.new), entry.summary());
+                    Line::from(Span::styled(text, style))
+                }
+            };
+            lines.push(if selected { line.style(self.colors.style_selected()) } else { line });
+        }
+        lines
+    }
+}
+
+impl<'a> Widget for RangeDiffModal<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if !self.state.visible {
+            return;
+        }
+
+        Clear.render(area, buf);
+
+        let title = format!("Range-diff - {}", self.state.title);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.colors.style_border_focused())
+            .title(Span::styled(title, self.colors.style_header()))
+            .title_alignment(Alignment::Center);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let hint = if self.state.showing_entries() {
+            "= same  ! changed  < dropped  > added    Enter expand interdiff, Esc back"
+        } else if self.state.first.is_some() {
+            "Pick the revision to compare with (Space/Enter), Esc unpick"
+        } else {
+            "Pick two revisions (Space/Enter), newest first; Esc close"
+        };
+        let mut lines = vec![Line::from(Span::styled(hint, self.colors.style_muted())), Line::from("")];
+
+        let footer = if self.state.error.is_some() { 2 } else { 0 };
+        let list_height = (inner.height as usize).saturating_sub(lines.len() + footer);
+        self.state.scroll.ensure_visible(list_height);
+
+        if self.state.showing_entries() {
+            lines.extend(self.entry_lines(list_height));
+        } else {
+            lines.extend(self.revision_lines(list_height));
+        }
+
+        if let Some(error) = &self.state.error {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(error.clone(), self.colors.style_removed())));
+        }
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn revision(sha: &str) -> RevisionChoice {
+        RevisionChoice { sha: sha.to_string(), label: String::new(), summary: String::new() }
+    }
+
+    #[test]
+    fn picking_two_revisions_orders_older_first() {
+        let mut state = RangeDiffState::new();
+        state.show("PR #3".to_string(), vec![revision("c"), revision("b"), revision("a")]);
+
+        assert_eq!(state.handle_key(key(KeyCode::Enter)), InputResult::Continue);
+        state.handle_key(key(KeyCode::Down));
+        state.handle_key(key(KeyCode::Down));
+        assert_eq!(state.handle_key(key(KeyCode::Char(' '))), InputResult::Submit);
+        assert_eq!(state.picked_shas(), Some(("a".to_string(), "c".to_string())));
+
+        // Esc goes back to picking, then unpicks, then closes
+        state.handle_key(key(KeyCode::Esc));
+        assert_eq!(state.picked_shas(), None);
+        state.handle_key(key(KeyCode::Enter));
+        assert_eq!(state.handle_key(key(KeyCode::Esc)), InputResult::Continue);
+        assert_eq!(state.handle_key(key(KeyCode::Esc)), InputResult::Cancelled);
+        assert!(!state.visible);
+    }
+}
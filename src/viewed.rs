@@ -0,0 +1,107 @@
+//! Files marked as viewed while reviewing
+//!
+//! Each viewed file is stored with a hash of its full diff, per branch (or
+//! per PR when reviewing in place), in `.git/timecop/viewed.json`. A file
+//! stays viewed only while its diff hashes the same, so new changes to it
+//! bring it back.
+
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Stable hash of a file's diff text
+pub fn diff_hash(diff: &str) -> String {
+    git2::Oid::hash_object(git2::ObjectType::Blob, diff.as_bytes())
+        .map(|oid| oid.to_string())
+        .unwrap_or_default()
+}
+
+/// Viewed files per review scope (branch name or `pr-<n>`): path → diff hash
+#[derive(Debug)]
+pub struct ViewedStore {
+    path: PathBuf,
+    scopes: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl ViewedStore {
+    /// Load the viewed state for the repository at `git_dir`
+    pub fn load(git_dir: &Path) -> Self {
+        let path = git_dir.join("timecop").join("viewed.json");
+        let scopes = std::fs::read(&path)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+        Self { path, scopes }
+    }
+
+    /// Whether a file was viewed with exactly this diff
+    pub fn is_viewed(&self, scope: &str, path: &str, hash: &str) -> bool {
+        self.scopes
+            .get(scope)
+            .and_then(|files| files.get(path))
+            .is_some_and(|viewed| viewed == hash)
+    }
+
+    /// Whether a file has been marked viewed at all, whatever its diff was then
+    pub fn has_entry(&self, scope: &str, path: &str) -> bool {
+        self.scopes.get(scope).is_some_and(|files| files.contains_key(path))
+    }
+
+    /// Mark a file viewed with its current diff hash, or unviewed with None
+    pub fn set(&mut self, scope: &str, path: &str, hash: Option<String>) -> Result<()> {
+        match hash {
+            Some(hash) => {
+                self.scopes
+                    .entry(scope.to_string())
+                    .or_default()
+                    .insert(path.to_string(), hash);
+            }
+            None => {
+                if let Some(files) = self.scopes.get_mut(scope) {
+                    files.remove(path);
+                    if files.is_empty() {
+                        self.scopes.remove(scope);
+                    }
+                }
+            }
+        }
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).context("Failed to create state directory")?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&self.scopes)?).context("Failed to save viewed files")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn viewed_until_diff_changes() {
+        let dir = TempDir::new("viewed");
+        let mut store = ViewedStore::load(&dir);
+        let hash = diff_hash("+one\n");
+        store.set("feature", "a.rs", Some(hash.clone())).unwrap();
+
+        let store = ViewedStore::load(&dir);
+        assert!(store.is_viewed("feature", "a.rs", &hash));
+        assert!(!store.is_viewed("feature", "a.rs", &diff_hash("+one\n+two\n")));
+        assert!(store.has_entry("feature", "a.rs"));
+        assert!(!store.is_viewed("pr-3", "a.rs", &hash));
+    }
+
+    #[test]
+    fn unviewing_drops_empty_scopes() {
+        let dir = TempDir::new("unviewed");
+        let mut store = ViewedStore::load(&dir);
+        store.set("pr-3", "a.rs", Some(diff_hash("x"))).unwrap();
+        store.set("pr-3", "a.rs", None).unwrap();
+        assert!(!store.has_entry("pr-3", "a.rs"));
+        assert!(store.scopes.is_empty());
+    }
+}